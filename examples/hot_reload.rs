@@ -0,0 +1,155 @@
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+/// The platform half of the hot reloading example: a window, a fixed timestep loop, and the game
+/// code loaded from the `hot_reload_game` example library, reloaded whenever it is rebuilt.
+///
+/// Build the library first: `cargo build --example hot_reload_game`. Press L to record a loop,
+/// again to play it back over and over, and once more to stop.
+#[cfg(any(windows, all(unix, not(target_os = "macos"))))]
+use triangle_from_scratch::{
+    event::{ControlFlow, Event, Key, WindowEvent},
+    game_loop::{Game, GameLoop},
+    hot_reload::HotReload,
+    memory::{GameMemoryBlock, FIXED_BASE_ADDRESS},
+    platform::{EventLoop, Window},
+    raster::Framebuffer,
+    replay::LiveLoop,
+};
+
+#[cfg(all(unix, not(target_os = "macos")))]
+use triangle_from_scratch::posix::clock::MonotonicClock as PlatformClock;
+#[cfg(windows)]
+use triangle_from_scratch::win32::clock::PerformanceClock as PlatformClock;
+
+#[cfg(any(windows, all(unix, not(target_os = "macos"))))]
+const PERMANENT_STORAGE_SIZE: usize = 64 * 1024 * 1024;
+#[cfg(any(windows, all(unix, not(target_os = "macos"))))]
+const TRANSIENT_STORAGE_SIZE: usize = 256 * 1024 * 1024;
+
+#[cfg(any(windows, all(unix, not(target_os = "macos"))))]
+struct Platform {
+    window: Window<Framebuffer>,
+    code: HotReload,
+    memory: GameMemoryBlock,
+    live_loop: LiveLoop,
+    /// The events received since the last update.
+    events: Vec<Event>,
+}
+
+#[cfg(any(windows, all(unix, not(target_os = "macos"))))]
+impl Game for Platform {
+    fn event(&mut self, event: Event, control_flow: &mut ControlFlow) {
+        if let Event::WindowEvent { window_id, event } = &event {
+            if *window_id != self.window.id() {
+                return;
+            }
+            match event {
+                WindowEvent::Resized { size, .. } => {
+                    if let Some(framebuffer) = self.window.state_mut() {
+                        framebuffer.resize(size.width, size.height);
+                    }
+                }
+                WindowEvent::CloseRequested => {
+                    if let Err(e) = self.window.destroy() {
+                        println!("Error when destroying the window: {}", e);
+                    }
+                }
+                WindowEvent::Destroyed => *control_flow = ControlFlow::Exit(0),
+                // Kept from the game, or playing back would start another recording.
+                WindowEvent::KeyDown(input) if input.key == Key::L && !input.repeat => {
+                    if let Err(e) = self.live_loop.toggle(self.memory.memory()) {
+                        println!("Error when switching the loop: {}", e);
+                    }
+                    return;
+                }
+                _ => {}
+            }
+        }
+        self.events.push(event);
+    }
+
+    fn update(&mut self, dt: core::time::Duration) {
+        match self
+            .live_loop
+            .process(self.memory.memory(), &self.events, dt)
+        {
+            Ok((events, dt)) => self.code.update(self.memory.memory(), events, dt),
+            Err(e) => println!("Error when recording or playing back: {}", e),
+        }
+        self.events.clear();
+    }
+
+    fn render(&mut self, alpha: f32) {
+        if !self.window.is_alive() {
+            return;
+        }
+        if let Some(framebuffer) = self.window.state_mut() {
+            self.code.render(self.memory.memory(), framebuffer, alpha);
+        }
+        if let Some(framebuffer) = self.window.state() {
+            if let Err(e) = self.window.present(framebuffer) {
+                println!("Error when presenting the frame: {}", e);
+            }
+        }
+        // Between two frames, the only time the code can be swapped.
+        match self.code.reload_if_changed() {
+            Ok(true) => println!("Reloaded the game code."),
+            Ok(false) => {}
+            Err(e) => println!("Could not reload the game code yet: {}", e),
+        }
+    }
+}
+
+#[cfg(any(windows, all(unix, not(target_os = "macos"))))]
+fn main() {
+    let library_path = std::env::current_exe()
+        .expect("Could not find the executable")
+        .with_file_name(format!(
+            "{}hot_reload_game{}",
+            std::env::consts::DLL_PREFIX,
+            std::env::consts::DLL_SUFFIX
+        ));
+    let live_loop = LiveLoop::new(library_path.with_file_name("hot_reload.rec"));
+    let code = HotReload::load(&library_path).unwrap_or_else(|e| {
+        panic!("Could not load {}: {}", library_path.display(), e);
+    });
+
+    // At the same address every run, for the memory to be saved and restored as is.
+    let memory = GameMemoryBlock::new(
+        Some(FIXED_BASE_ADDRESS),
+        PERMANENT_STORAGE_SIZE,
+        TRANSIENT_STORAGE_SIZE,
+    )
+    .unwrap_or_else(|e| panic!("Could not allocate the game memory: {}", e));
+
+    let mut event_loop = EventLoop::new().unwrap_or_else(|e| {
+        panic!("Could not prepare the event loop, error code:{}", e);
+    });
+    let window = Window::new(
+        &event_loop,
+        "Hot Reload",
+        None,
+        [800, 600],
+        Framebuffer::new(0, 0),
+    )
+    .unwrap_or_else(|e| panic!("Failed to create a window: {}", e));
+    window.show();
+
+    let mut platform = Platform {
+        window,
+        code,
+        memory,
+        live_loop,
+        events: Vec::new(),
+    };
+    let exit_code = GameLoop::new(PlatformClock::new(), 60)
+        .run(&mut event_loop, &mut platform)
+        .unwrap_or_else(|e| panic!("Error when getting a message from the queue: {}", e));
+    drop(platform);
+    std::process::exit(exit_code);
+}
+
+#[cfg(not(any(windows, all(unix, not(target_os = "macos")))))]
+fn main() {
+    eprintln!("This example only supports Windows and X11 for now.");
+}
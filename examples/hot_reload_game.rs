@@ -0,0 +1,83 @@
+//! The game half of the `hot_reload` example, built as a library the platform reloads.
+//!
+//! Edit the colors or the speed, rebuild with `cargo build --example hot_reload_game` while the
+//! `hot_reload` example runs, and the square carries on from where it was with the new code.
+
+use core::time::Duration;
+use triangle_from_scratch::{
+    event::{Event, Key, WindowEvent},
+    game::{GameMemory, GAME_API_VERSION},
+    raster::{Framebuffer, Rgba8},
+};
+
+/// Everything the game remembers, kept in the permanent arena.
+struct GameState {
+    /// Where the square is, from 0 to 1, and where it was at the previous update.
+    position: [f32; 2],
+    previous_position: [f32; 2],
+    velocity: [f32; 2],
+    paused: bool,
+}
+
+/// The state, set up on first use.
+fn game_state(memory: &mut GameMemory) -> &mut GameState {
+    // Safety: always asked for as a `GameState`. Changing its fields while the platform runs
+    // needs a restart, not a reload.
+    unsafe {
+        memory.state(|| GameState {
+            position: [0.5, 0.5],
+            previous_position: [0.5, 0.5],
+            velocity: [0.3, 0.2],
+            paused: false,
+        })
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn game_api_version() -> u32 {
+    GAME_API_VERSION
+}
+
+#[no_mangle]
+pub fn game_update(memory: &mut GameMemory, events: &[Event], dt: Duration) {
+    let state = game_state(memory);
+    for event in events {
+        if let Event::WindowEvent {
+            event: WindowEvent::KeyDown(input),
+            ..
+        } = event
+        {
+            if input.key == Key::Space && !input.repeat {
+                state.paused = !state.paused;
+            }
+        }
+    }
+    state.previous_position = state.position;
+    if state.paused {
+        return;
+    }
+    for axis in 0..2 {
+        state.position[axis] += state.velocity[axis] * dt.as_secs_f32();
+        if !(0.0..=1.0).contains(&state.position[axis]) {
+            state.velocity[axis] = -state.velocity[axis];
+            state.position[axis] = state.position[axis].clamp(0.0, 1.0);
+        }
+    }
+}
+
+#[no_mangle]
+pub fn game_render(memory: &mut GameMemory, framebuffer: &mut Framebuffer, alpha: f32) {
+    let state = game_state(memory);
+    framebuffer.clear(Rgba8::BLACK);
+    let [width, height] = [framebuffer.width(), framebuffer.height()];
+    let size = width.min(height) / 8;
+    let center = [0, 1]
+        .map(|axis| state.previous_position[axis] * (1.0 - alpha) + state.position[axis] * alpha);
+    let left = (center[0] * width.saturating_sub(size) as f32) as u32;
+    let top = (center[1] * height.saturating_sub(size) as f32) as u32;
+    for y in top..(top + size).min(height) {
+        for x in left..(left + size).min(width) {
+            framebuffer.set_pixel(x, y, Rgba8::WHITE);
+        }
+    }
+}
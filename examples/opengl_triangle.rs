@@ -0,0 +1,154 @@
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+/// Following the tutorial :
+/// https://rust-tutorials.github.io/triangle-from-scratch/opening_a_window/win32.html
+///
+/// The triangle again, drawn by OpenGL this time.
+#[cfg(windows)]
+use triangle_from_scratch::{
+    event::{ControlFlow, Event, WindowEvent},
+    gl::*,
+    win32::{
+        event_loop::EventLoop,
+        safe::Win32Error,
+        wgl::{GlConfig, GlContext},
+        window::Window,
+    },
+};
+
+#[cfg(windows)]
+const VERTEX_SHADER: &str = r#"#version 330 core
+layout (location = 0) in vec2 position;
+layout (location = 1) in vec3 color;
+out vec3 vertex_color;
+void main() {
+    gl_Position = vec4(position, 0.0, 1.0);
+    vertex_color = color;
+}
+"#;
+
+#[cfg(windows)]
+const FRAGMENT_SHADER: &str = r#"#version 330 core
+in vec3 vertex_color;
+out vec4 final_color;
+void main() {
+    final_color = vec4(vertex_color, 1.0);
+}
+"#;
+
+/// Compiles a shader, panicking with the info log on failure.
+#[cfg(windows)]
+unsafe fn compile_shader(gl: &Gl, shader_type: GLenum, source: &str) -> GLuint {
+    let shader = gl.glCreateShader(shader_type);
+    let source_ptr = source.as_ptr().cast();
+    let source_len = source.len() as GLint;
+    gl.glShaderSource(shader, 1, &source_ptr, &source_len);
+    gl.glCompileShader(shader);
+    let mut success = 0;
+    gl.glGetShaderiv(shader, GL_COMPILE_STATUS, &mut success);
+    if success == 0 {
+        let mut log = vec![0_u8; 1024];
+        let mut log_len = 0;
+        gl.glGetShaderInfoLog(shader, 1024, &mut log_len, log.as_mut_ptr().cast());
+        log.truncate(log_len as usize);
+        panic!("Shader compile error: {}", String::from_utf8_lossy(&log));
+    }
+    shader
+}
+
+#[cfg(windows)]
+fn main() {
+    let mut event_loop = EventLoop::new().unwrap_or_else(|e: Win32Error| {
+        panic!("Could not prepare the event loop, error code:{}", e);
+    });
+    let mut window = Window::new(&event_loop, "OpenGL Triangle", None, [800, 600], ())
+        .unwrap_or_else(|e| panic!("Failed to create a window: {}", e));
+    let context = GlContext::new(&window, GlConfig::default())
+        .unwrap_or_else(|e| panic!("Failed to create an OpenGL context: {}", e));
+    let gl = context.load_gl();
+    let missing = gl.missing_functions();
+    if !missing.is_empty() {
+        panic!("Missing GL functions: {:?}", missing);
+    }
+
+    #[rustfmt::skip]
+    let vertices: [f32; 15] = [
+        // position   color
+         0.0,  0.8,   1.0, 0.0, 0.0,
+         0.8, -0.8,   0.0, 1.0, 0.0,
+        -0.8, -0.8,   0.0, 0.0, 1.0,
+    ];
+    let stride = (5 * core::mem::size_of::<f32>()) as GLsizei;
+    unsafe {
+        let mut vao = 0;
+        gl.glGenVertexArrays(1, &mut vao);
+        gl.glBindVertexArray(vao);
+        let mut vbo = 0;
+        gl.glGenBuffers(1, &mut vbo);
+        gl.glBindBuffer(GL_ARRAY_BUFFER, vbo);
+        gl.glBufferData(
+            GL_ARRAY_BUFFER,
+            core::mem::size_of_val(&vertices) as GLsizeiptr,
+            vertices.as_ptr().cast(),
+            GL_STATIC_DRAW,
+        );
+        gl.glVertexAttribPointer(0, 2, GL_FLOAT, GL_FALSE, stride, core::ptr::null());
+        gl.glEnableVertexAttribArray(0);
+        gl.glVertexAttribPointer(
+            1,
+            3,
+            GL_FLOAT,
+            GL_FALSE,
+            stride,
+            (2 * core::mem::size_of::<f32>()) as *const _,
+        );
+        gl.glEnableVertexAttribArray(1);
+
+        let vertex_shader = compile_shader(&gl, GL_VERTEX_SHADER, VERTEX_SHADER);
+        let fragment_shader = compile_shader(&gl, GL_FRAGMENT_SHADER, FRAGMENT_SHADER);
+        let program = gl.glCreateProgram();
+        gl.glAttachShader(program, vertex_shader);
+        gl.glAttachShader(program, fragment_shader);
+        gl.glLinkProgram(program);
+        gl.glDeleteShader(vertex_shader);
+        gl.glDeleteShader(fragment_shader);
+        gl.glUseProgram(program);
+        gl.glClearColor(0.2, 0.3, 0.3, 1.0);
+    }
+
+    window.show();
+    let exit_code = event_loop
+        .run(|event, control_flow| {
+            if let Event::WindowEvent { event, .. } = event {
+                match event {
+                    WindowEvent::Resized { size, .. } => unsafe {
+                        gl.glViewport(0, 0, size.width as GLsizei, size.height as GLsizei);
+                    },
+                    WindowEvent::Paint => {
+                        unsafe {
+                            gl.glClear(GL_COLOR_BUFFER_BIT);
+                            gl.glDrawArrays(GL_TRIANGLES, 0, 3);
+                        }
+                        if let Err(e) = context.swap_buffers() {
+                            println!("Error when swapping buffers: {}", e);
+                        }
+                    }
+                    WindowEvent::CloseRequested => {
+                        if let Err(e) = window.destroy() {
+                            println!("Error when destroying the window: {}", e);
+                        }
+                    }
+                    WindowEvent::Destroyed => *control_flow = ControlFlow::Exit(0),
+                    _ => {}
+                }
+            }
+        })
+        .unwrap_or_else(|e| panic!("Error when getting a message from the queue: {}", e));
+    drop(context);
+    std::process::exit(exit_code);
+}
+
+#[cfg(not(windows))]
+fn main() {
+    eprintln!("This example only runs on Windows.");
+}
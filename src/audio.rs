@@ -0,0 +1,308 @@
+//! Platform independent audio: the PCM format, the sample conversions and the ring buffer the game
+//! writes its samples into.
+//!
+//! The platform layer takes the samples out of the ring buffer and hands them over to the sound
+//! card, a little ahead of what it is playing, like in Handmade Hero's DirectSound chapter. See
+//! `win32::audio` for the Windows side.
+
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+use std::sync::Arc;
+
+/// The format of the samples: interleaved signed 16 bits PCM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioFormat {
+    /// Frames per second.
+    pub sample_rate: u32,
+    /// Samples per frame, 2 for stereo.
+    pub channels: u16,
+}
+
+impl Default for AudioFormat {
+    /// 48kHz stereo.
+    fn default() -> Self {
+        Self {
+            sample_rate: 48_000,
+            channels: 2,
+        }
+    }
+}
+
+impl AudioFormat {
+    /// The size of a frame, one sample for each channel, in bytes.
+    pub const fn bytes_per_frame(&self) -> u32 {
+        self.channels as u32 * core::mem::size_of::<i16>() as u32
+    }
+
+    /// How long `frames` frames play for.
+    pub fn frames_to_duration(&self, frames: u64) -> Duration {
+        Duration::from_nanos(frames * 1_000_000_000 / u64::from(self.sample_rate.max(1)))
+    }
+
+    /// How many frames play during `duration`, rounded down.
+    pub fn duration_to_frames(&self, duration: Duration) -> u64 {
+        (duration.as_nanos() * u128::from(self.sample_rate) / 1_000_000_000) as u64
+    }
+}
+
+/// Converts a sample from `[-1.0, 1.0]` to 16 bits, clamping it first.
+pub fn f32_to_i16(sample: f32) -> i16 {
+    // NaN clamps to NaN, which casts to 0.
+    (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)).round() as i16
+}
+
+/// Converts a 16 bits sample to `[-1.0, 1.0]`.
+pub fn i16_to_f32(sample: i16) -> f32 {
+    // i16::MIN is the only value out of the symmetric range.
+    (f32::from(sample) / f32::from(i16::MAX)).max(-1.0)
+}
+
+/// Converts the samples of `input` to 16 bits, into `output`.
+///
+/// **Returns:** How many samples were converted, the length of the shortest slice.
+pub fn convert_f32_to_i16(input: &[f32], output: &mut [i16]) -> usize {
+    let len = input.len().min(output.len());
+    for (output, &input) in output.iter_mut().zip(input) {
+        *output = f32_to_i16(input);
+    }
+    len
+}
+
+/// What the two ends of a ring buffer share.
+struct Shared {
+    samples: Box<[UnsafeCell<i16>]>,
+    /// How many samples were ever read, only written by the [`Consumer`].
+    read: AtomicUsize,
+    /// How many samples were ever written, only written by the [`Producer`].
+    write: AtomicUsize,
+}
+
+// Safety: the producer only writes the slots the consumer is done with, and the other way around,
+// the atomic counters making sure each side sees the other's writes.
+unsafe impl Sync for Shared {}
+
+impl Shared {
+    fn capacity(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// How many samples are waiting to be read.
+    fn len(&self) -> usize {
+        let write = self.write.load(Ordering::Acquire);
+        let read = self.read.load(Ordering::Acquire);
+        write.wrapping_sub(read)
+    }
+}
+
+/// Creates a single producer, single consumer ring buffer of `capacity` samples.
+///
+/// Neither end ever blocks nor locks, so the consumer can live on an audio thread while the game
+/// loop produces.
+pub fn ring_buffer(capacity: usize) -> (Producer, Consumer) {
+    let shared = Arc::new(Shared {
+        samples: (0..capacity).map(|_| UnsafeCell::new(0)).collect(),
+        read: AtomicUsize::new(0),
+        write: AtomicUsize::new(0),
+    });
+    (
+        Producer {
+            shared: Arc::clone(&shared),
+        },
+        Consumer { shared },
+    )
+}
+
+/// The writing end of a [`ring_buffer`].
+pub struct Producer {
+    shared: Arc<Shared>,
+}
+
+impl Producer {
+    /// How many samples the buffer holds when full.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// How many samples can be written right now.
+    pub fn free_len(&self) -> usize {
+        self.capacity() - self.shared.len()
+    }
+
+    /// Writes as many samples of `samples` as there is room for.
+    ///
+    /// Samples are interleaved, so only whole frames should be written to keep the channels in
+    /// place.
+    ///
+    /// **Returns:** How many samples were written.
+    pub fn push(&mut self, samples: &[i16]) -> usize {
+        let shared = &*self.shared;
+        let write = shared.write.load(Ordering::Relaxed);
+        let read = shared.read.load(Ordering::Acquire);
+        let free = shared.capacity() - write.wrapping_sub(read);
+        let len = samples.len().min(free);
+        for (i, &sample) in samples[..len].iter().enumerate() {
+            let slot = &shared.samples[write.wrapping_add(i) % shared.capacity()];
+            // Safety: the consumer doesn't touch the free slots.
+            unsafe { *slot.get() = sample };
+        }
+        shared
+            .write
+            .store(write.wrapping_add(len), Ordering::Release);
+        len
+    }
+}
+
+/// The reading end of a [`ring_buffer`].
+pub struct Consumer {
+    shared: Arc<Shared>,
+}
+
+impl Consumer {
+    /// How many samples the buffer holds when full.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// How many samples are waiting to be read.
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// Whether there is nothing to read.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reads as many samples as are available, up to the length of `output`.
+    ///
+    /// **Returns:** How many samples were read.
+    pub fn pop(&mut self, output: &mut [i16]) -> usize {
+        let shared = &*self.shared;
+        let read = shared.read.load(Ordering::Relaxed);
+        let write = shared.write.load(Ordering::Acquire);
+        let len = output.len().min(write.wrapping_sub(read));
+        for (i, output) in output[..len].iter_mut().enumerate() {
+            let slot = &shared.samples[read.wrapping_add(i) % shared.capacity()];
+            // Safety: the producer doesn't touch the written slots.
+            *output = unsafe { *slot.get() };
+        }
+        shared.read.store(read.wrapping_add(len), Ordering::Release);
+        len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_wrap_around_the_end_of_the_buffer() {
+        let (mut producer, mut consumer) = ring_buffer(4);
+        let mut output = [0; 4];
+        assert_eq!(producer.push(&[1, 2, 3]), 3);
+        assert_eq!(consumer.pop(&mut output[..2]), 2);
+        assert_eq!(output[..2], [1, 2]);
+        // Two slots at the end, one at the start is free.
+        assert_eq!(producer.push(&[4, 5, 6]), 3);
+        assert_eq!(consumer.len(), 4);
+        assert_eq!(consumer.pop(&mut output), 4);
+        assert_eq!(output, [3, 4, 5, 6]);
+        // Going around a few more times.
+        for round in 0..10 {
+            let samples = [round, round + 1, round + 2];
+            assert_eq!(producer.push(&samples), 3);
+            assert_eq!(consumer.pop(&mut output), 3);
+            assert_eq!(output[..3], samples);
+        }
+    }
+
+    #[test]
+    fn full_and_empty() {
+        let (mut producer, mut consumer) = ring_buffer(3);
+        assert_eq!(producer.capacity(), 3);
+        assert_eq!(consumer.capacity(), 3);
+        assert!(consumer.is_empty());
+        assert_eq!(producer.free_len(), 3);
+        let mut output = [0; 3];
+        assert_eq!(consumer.pop(&mut output), 0);
+        assert_eq!(producer.push(&[7, 8, 9]), 3);
+        assert_eq!(producer.free_len(), 0);
+        assert_eq!(consumer.len(), 3);
+        assert_eq!(producer.push(&[10]), 0);
+        assert_eq!(consumer.pop(&mut output), 3);
+        assert_eq!(output, [7, 8, 9]);
+        assert!(consumer.is_empty());
+        assert_eq!(producer.free_len(), 3);
+        // A buffer with no room at all is always full and empty.
+        let (mut producer, mut consumer) = ring_buffer(0);
+        assert_eq!(producer.push(&[1]), 0);
+        assert_eq!(consumer.pop(&mut output), 0);
+    }
+
+    #[test]
+    fn writes_are_cut_short_when_full() {
+        let (mut producer, mut consumer) = ring_buffer(4);
+        assert_eq!(producer.push(&[1, 2, 3]), 3);
+        assert_eq!(producer.push(&[4, 5, 6]), 1);
+        let mut output = [0; 2];
+        assert_eq!(consumer.pop(&mut output), 2);
+        assert_eq!(output, [1, 2]);
+        // Only the room made by the read.
+        assert_eq!(producer.push(&[7, 8, 9]), 2);
+        let mut output = [0; 8];
+        assert_eq!(consumer.pop(&mut output), 4);
+        assert_eq!(output[..4], [3, 4, 7, 8]);
+    }
+
+    #[test]
+    fn samples_cross_threads() {
+        let (mut producer, mut consumer) = ring_buffer(64);
+        let total = 10_000;
+        let writer = std::thread::spawn(move || {
+            let mut next = 0;
+            while next < total {
+                let end = (next + 7).min(total);
+                let samples: Vec<i16> = (next..end).map(|sample| sample as i16).collect();
+                next += producer.push(&samples) as i32;
+            }
+        });
+        let mut expected = 0;
+        let mut output = [0; 5];
+        while expected < total {
+            let len = consumer.pop(&mut output);
+            if len == 0 {
+                std::thread::yield_now();
+            }
+            for &sample in &output[..len] {
+                assert_eq!(sample, expected as i16);
+                expected += 1;
+            }
+        }
+        writer.join().unwrap();
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn conversions_clamp() {
+        assert_eq!(f32_to_i16(0.0), 0);
+        assert_eq!(f32_to_i16(1.0), i16::MAX);
+        assert_eq!(f32_to_i16(-1.0), -i16::MAX);
+        assert_eq!(f32_to_i16(0.5), 16384);
+        assert_eq!(f32_to_i16(1.5), i16::MAX);
+        assert_eq!(f32_to_i16(-1.5), -i16::MAX);
+        assert_eq!(f32_to_i16(f32::INFINITY), i16::MAX);
+        assert_eq!(f32_to_i16(f32::NEG_INFINITY), -i16::MAX);
+        assert_eq!(f32_to_i16(f32::NAN), 0);
+        assert_eq!(i16_to_f32(i16::MAX), 1.0);
+        assert_eq!(i16_to_f32(i16::MIN), -1.0);
+        assert_eq!(i16_to_f32(0), 0.0);
+        let mut output = [1; 4];
+        assert_eq!(convert_f32_to_i16(&[1.0, -2.0], &mut output), 2);
+        assert_eq!(output, [i16::MAX, -i16::MAX, 1, 1]);
+        assert_eq!(convert_f32_to_i16(&[0.0; 8], &mut output), 4);
+        assert_eq!(output, [0; 4]);
+    }
+}
@@ -0,0 +1,221 @@
+//! Logical and physical pixels, and the scaling between them.
+//!
+//! Physical pixels are the ones of the screen. Logical pixels are the ones of a 96 DPI screen, the
+//! "100%" scale factor: a window 800 logical pixels wide is 800 physical pixels wide at 96 DPI,
+//! and 1200 at 144 DPI ("150%"). Window sizes are asked for in logical pixels so they look the same
+//! on every monitor, and reported in physical pixels since that's what gets drawn.
+//!
+//! See [High DPI Desktop Application Development on Windows](https://docs.microsoft.com/en-us/windows/win32/hidpi/high-dpi-desktop-application-development-on-windows)
+
+/// Dots per inch of a monitor, in both directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Dpi(pub u32);
+
+impl Dpi {
+    /// The DPI where logical and physical pixels are the same, `USER_DEFAULT_SCREEN_DPI`.
+    pub const BASE: Dpi = Dpi(96);
+
+    /// How many physical pixels make a logical pixel, e.g. 1.5 at 144 DPI.
+    pub fn scale_factor(self) -> f64 {
+        f64::from(self.0) / f64::from(Self::BASE.0)
+    }
+
+    /// Scales a length in logical pixels to physical pixels, rounding to the nearest.
+    ///
+    /// This is `MulDiv(value, dpi, 96)`, which is how Windows scales its own metrics.
+    pub fn to_physical(self, logical: u32) -> u32 {
+        let scaled = u64::from(logical) * u64::from(self.0) + u64::from(Self::BASE.0 / 2);
+        (scaled / u64::from(Self::BASE.0)).min(u64::from(u32::MAX)) as u32
+    }
+
+    /// Scales a length in physical pixels to logical pixels.
+    pub fn to_logical(self, physical: u32) -> f64 {
+        f64::from(physical) / self.scale_factor()
+    }
+}
+
+impl Default for Dpi {
+    fn default() -> Self {
+        Self::BASE
+    }
+}
+
+/// A size in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PhysicalSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl PhysicalSize {
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    /// The size in logical pixels at `dpi`.
+    pub fn to_logical(self, dpi: Dpi) -> LogicalSize {
+        LogicalSize {
+            width: dpi.to_logical(self.width),
+            height: dpi.to_logical(self.height),
+        }
+    }
+}
+
+/// A size in logical pixels, fractional once scaled from physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LogicalSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl LogicalSize {
+    pub const fn new(width: f64, height: f64) -> Self {
+        Self { width, height }
+    }
+
+    /// The size in physical pixels at `dpi`, rounded to the nearest pixel.
+    pub fn to_physical(self, dpi: Dpi) -> PhysicalSize {
+        let scale = |length: f64| (length.max(0.0) * dpi.scale_factor()).round() as u32;
+        PhysicalSize {
+            width: scale(self.width),
+            height: scale(self.height),
+        }
+    }
+}
+
+impl From<[u32; 2]> for LogicalSize {
+    fn from([width, height]: [u32; 2]) -> Self {
+        Self::new(f64::from(width), f64::from(height))
+    }
+}
+
+/// Bounds on the client area size of a window, in logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SizeConstraints {
+    pub min: Option<LogicalSize>,
+    pub max: Option<LogicalSize>,
+}
+
+impl SizeConstraints {
+    /// The smallest client area allowed at `dpi`.
+    pub fn min_physical(&self, dpi: Dpi) -> Option<PhysicalSize> {
+        self.min.map(|min| min.to_physical(dpi))
+    }
+
+    /// The largest client area allowed at `dpi`, never smaller than the smallest.
+    pub fn max_physical(&self, dpi: Dpi) -> Option<PhysicalSize> {
+        let max = self.max?.to_physical(dpi);
+        Some(match self.min_physical(dpi) {
+            Some(min) => PhysicalSize::new(max.width.max(min.width), max.height.max(min.height)),
+            None => max,
+        })
+    }
+
+    /// Brings `size` within the bounds at `dpi`.
+    pub fn clamp(&self, size: PhysicalSize, dpi: Dpi) -> PhysicalSize {
+        let mut size = size;
+        if let Some(max) = self.max_physical(dpi) {
+            size = PhysicalSize::new(size.width.min(max.width), size.height.min(max.height));
+        }
+        if let Some(min) = self.min_physical(dpi) {
+            size = PhysicalSize::new(size.width.max(min.width), size.height.max(min.height));
+        }
+        size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DPIS: [Dpi; 4] = [Dpi(96), Dpi(120), Dpi(144), Dpi(192)];
+
+    #[test]
+    fn lengths_are_rounded_to_the_nearest_pixel() {
+        assert_eq!(DPIS.map(Dpi::scale_factor), [1.0, 1.25, 1.5, 2.0]);
+        assert_eq!(
+            DPIS.map(|dpi| dpi.to_physical(800)),
+            [800, 1000, 1200, 1600]
+        );
+        // 1.25 rounds down, 1.5 and 2.5 round up.
+        assert_eq!(DPIS.map(|dpi| dpi.to_physical(1)), [1, 1, 2, 2]);
+        assert_eq!(DPIS.map(|dpi| dpi.to_physical(3)), [3, 4, 5, 6]);
+        assert_eq!(DPIS.map(|dpi| dpi.to_physical(5)), [5, 6, 8, 10]);
+        assert_eq!(DPIS.map(|dpi| dpi.to_physical(0)), [0; 4]);
+        // Saturating rather than wrapping.
+        assert_eq!(Dpi(192).to_physical(u32::MAX), u32::MAX);
+        let size = LogicalSize::new(3.0, 5.0);
+        let sizes = DPIS.map(|dpi| size.to_physical(dpi));
+        assert_eq!(
+            sizes,
+            [
+                PhysicalSize::new(3, 5),
+                PhysicalSize::new(4, 6),
+                PhysicalSize::new(5, 8),
+                PhysicalSize::new(6, 10),
+            ]
+        );
+        // Negative lengths are empty.
+        assert_eq!(
+            LogicalSize::new(-10.0, 1.0).to_physical(Dpi(144)),
+            PhysicalSize::new(0, 2)
+        );
+    }
+
+    #[test]
+    fn sizes_round_trip() {
+        for &dpi in &DPIS {
+            for &length in &[0, 1, 7, 640, 1080, 3839] {
+                let logical = LogicalSize::from([length, length]);
+                let physical = logical.to_physical(dpi);
+                assert_eq!(physical.width, dpi.to_physical(length));
+                // Off by the rounding to whole physical pixels at most.
+                let back = physical.to_logical(dpi);
+                assert!((back.width - logical.width).abs() <= 0.5 / dpi.scale_factor() + 1e-9);
+                // Fractional logical sizes come back to the same physical size.
+                let physical = PhysicalSize::new(length, length + 1);
+                assert_eq!(physical.to_logical(dpi).to_physical(dpi), physical);
+            }
+        }
+    }
+
+    #[test]
+    fn sizes_are_clamped_to_the_constraints() {
+        let constraints = SizeConstraints {
+            min: Some(LogicalSize::new(320.0, 200.0)),
+            max: Some(LogicalSize::new(800.0, 600.0)),
+        };
+        let clamp = |width, height, dpi| constraints.clamp(PhysicalSize::new(width, height), dpi);
+        assert_eq!(clamp(100, 1000, Dpi::BASE), PhysicalSize::new(320, 600));
+        assert_eq!(clamp(500, 400, Dpi::BASE), PhysicalSize::new(500, 400));
+        assert_eq!(clamp(100, 1000, Dpi(144)), PhysicalSize::new(480, 900));
+        assert_eq!(clamp(2000, 0, Dpi(192)), PhysicalSize::new(1600, 400));
+        // Unconstrained sides are left alone.
+        let constraints = SizeConstraints {
+            min: None,
+            max: Some(LogicalSize::new(800.0, 600.0)),
+        };
+        let size = constraints.clamp(PhysicalSize::new(0, 5000), Dpi(120));
+        assert_eq!(size, PhysicalSize::new(0, 750));
+        let size = SizeConstraints::default().clamp(PhysicalSize::new(7, 5000), Dpi(120));
+        assert_eq!(size, PhysicalSize::new(7, 5000));
+    }
+
+    #[test]
+    fn the_minimum_wins_over_a_smaller_maximum() {
+        let constraints = SizeConstraints {
+            min: Some(LogicalSize::new(400.0, 100.0)),
+            max: Some(LogicalSize::new(200.0, 300.0)),
+        };
+        let dpi = Dpi(144);
+        assert_eq!(
+            constraints.max_physical(dpi),
+            Some(PhysicalSize::new(600, 450))
+        );
+        for &(width, height) in &[(0, 0), (300, 300), (5000, 5000)] {
+            let size = constraints.clamp(PhysicalSize::new(width, height), dpi);
+            assert_eq!(size.width, 600);
+            assert!((150..=450).contains(&size.height));
+        }
+    }
+}
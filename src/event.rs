@@ -0,0 +1,358 @@
+//! Platform independent events, produced by the platform layers out of their native messages.
+
+use crate::dpi::{Dpi, PhysicalSize};
+
+/// Identifies a window, whichever the platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowId(pub usize);
+
+/// Identifies an input device, whichever the platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeviceId(pub usize);
+
+/// What an event loop hands over to its handler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// Something happened to one of the windows.
+    WindowEvent {
+        window_id: WindowId,
+        event: WindowEvent,
+    },
+    /// An input device reported something, whatever the window under the cursor or the focus.
+    DeviceEvent {
+        device_id: DeviceId,
+        event: DeviceEvent,
+    },
+    /// All the pending events have been handled, the loop is about to wait or poll again.
+    MainEventsCleared,
+}
+
+/// Raw input from a device, before the system applies acceleration, key repeat and the like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceEvent {
+    /// The mouse moved, in device units (mickeys) with no acceleration.
+    MouseMotion { delta_x: i32, delta_y: i32 },
+    /// A mouse button was pressed (`true`) or released (`false`).
+    MouseButton { button: MouseButton, pressed: bool },
+    /// The mouse wheel was turned, see [`WindowEvent::MouseWheel`].
+    MouseWheel { delta_x: i32, delta_y: i32 },
+    /// A key was pressed (`true`) or released (`false`), see [`KeyboardInput`] for the scancode.
+    Key {
+        key: Key,
+        scancode: u32,
+        pressed: bool,
+    },
+}
+
+/// What the event loop should do once the current events are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ControlFlow {
+    /// Sleep until new events arrive.
+    #[default]
+    Wait,
+    /// Go through the loop again right away, even when there are no new events.
+    Poll,
+    /// Leave the loop, which returns the given exit code.
+    Exit(i32),
+}
+
+/// The surface shared by the event loops of every backend, to write code generic over them.
+pub trait PlatformEventLoop {
+    type Error;
+
+    /// Runs the loop until the handler asks to [`ControlFlow::Exit`].
+    ///
+    /// **Returns:** The exit code.
+    fn run<F>(&mut self, handler: F) -> Result<i32, Self::Error>
+    where
+        F: FnMut(Event, &mut ControlFlow);
+}
+
+/// Something that happened to a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowEvent {
+    /// The window has been created, but is not shown yet.
+    Created,
+    /// The client area of the window changed size, in physical pixels at the `dpi` of the window.
+    ///
+    /// `size.to_logical(dpi)` is the size in logical pixels.
+    Resized { size: PhysicalSize, dpi: Dpi },
+    /// The DPI of the window changed, e.g. it was moved to another monitor. The window is resized
+    /// to keep its logical size, a [`WindowEvent::Resized`] follows.
+    DpiChanged(Dpi),
+    /// The client area of the window moved, in screen coordinates.
+    Moved { x: i32, y: i32 },
+    /// The window gained (`true`) or lost (`false`) the keyboard focus.
+    Focused(bool),
+    /// The user asked for the window to be closed, e.g. with the close button.
+    CloseRequested,
+    /// The window is being destroyed.
+    Destroyed,
+    /// (Part of) the window must be repainted.
+    Paint,
+    /// A key was pressed, or is auto-repeating.
+    KeyDown(KeyboardInput),
+    /// A key was released.
+    KeyUp(KeyboardInput),
+    /// A character was typed, after the keyboard layout, dead keys and the like were applied.
+    ///
+    /// Control characters (backspace, escape, ...) are left out, see [`WindowEvent::KeyDown`].
+    TextInput(char),
+    /// The cursor moved over the client area, in client coordinates.
+    ///
+    /// While a button is held the window keeps receiving the moves out of its client area, so the
+    /// coordinates can be negative.
+    MouseMove { x: i32, y: i32 },
+    /// The cursor entered the client area.
+    MouseEnter,
+    /// The cursor left the client area.
+    MouseLeave,
+    /// A mouse button was pressed, at the given client coordinates.
+    MouseDown { button: MouseButton, x: i32, y: i32 },
+    /// A mouse button was released, at the given client coordinates.
+    MouseUp { button: MouseButton, x: i32, y: i32 },
+    /// A mouse button was pressed a second time in quick succession, at the given client
+    /// coordinates.
+    ///
+    /// Sent instead of the second [`WindowEvent::MouseDown`], on the platforms that detect them.
+    MouseDoubleClick { button: MouseButton, x: i32, y: i32 },
+    /// The mouse wheel was turned, in [`WHEEL_NOTCH`] units.
+    ///
+    /// A positive `delta_y` is a rotation away from the user, a positive `delta_x` a tilt to the
+    /// right.
+    MouseWheel { delta_x: i32, delta_y: i32 },
+}
+
+/// The wheel rotation of one notch, in the units of [`WindowEvent::MouseWheel`].
+///
+/// High resolution wheels report fractions of it.
+pub const WHEEL_NOTCH: i32 = 120;
+
+/// A button of the mouse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    /// The first extra button, usually "back".
+    X1,
+    /// The second extra button, usually "forward".
+    X2,
+}
+
+/// A key press or release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyboardInput {
+    /// The key, as mapped by the current keyboard layout.
+    pub key: Key,
+    /// The platform specific scancode, which identifies the physical key whatever the layout.
+    ///
+    /// * Win32: the set 1 scancode, prefixed with `0xE0` (as in `0xE01D`) for extended keys.
+    /// * X11: the X key code.
+    pub scancode: u32,
+    /// Whether the key was already down, which is the case for auto-repeat presses.
+    pub repeat: bool,
+    /// How many presses this event stands for, when the platform coalesces auto-repeats.
+    ///
+    /// At least 1, and always 1 for releases.
+    pub repeat_count: u16,
+}
+
+/// A key of the keyboard, named after its US layout label.
+///
+/// The discriminants are saved in recordings (see [`replay`](crate::replay)): new keys go right
+/// before `Unknown`, and bump the recording version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Key {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    Escape,
+    Enter,
+    Tab,
+    Backspace,
+    Space,
+    Insert,
+    Delete,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Left,
+    Up,
+    Right,
+    Down,
+    LeftShift,
+    RightShift,
+    LeftControl,
+    RightControl,
+    LeftAlt,
+    RightAlt,
+    LeftSuper,
+    RightSuper,
+    Menu,
+    CapsLock,
+    NumLock,
+    ScrollLock,
+    PrintScreen,
+    Pause,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadEnter,
+    Minus,
+    Equals,
+    LeftBracket,
+    RightBracket,
+    Backslash,
+    Semicolon,
+    Apostrophe,
+    Grave,
+    Comma,
+    Period,
+    Slash,
+    /// A key with no variant of its own.
+    Unknown,
+}
+
+impl Key {
+    /// The key of discriminant `value`.
+    pub fn from_u8(value: u8) -> Option<Key> {
+        if value <= Key::Unknown as u8 {
+            // Safety: a `repr(u8)` enum without explicit discriminants, so all are taken up to
+            // the last one.
+            Some(unsafe { core::mem::transmute::<u8, Key>(value) })
+        } else {
+            None
+        }
+    }
+
+    /// The letter keys, in alphabetical order.
+    pub const LETTERS: [Key; 26] = [
+        Key::A,
+        Key::B,
+        Key::C,
+        Key::D,
+        Key::E,
+        Key::F,
+        Key::G,
+        Key::H,
+        Key::I,
+        Key::J,
+        Key::K,
+        Key::L,
+        Key::M,
+        Key::N,
+        Key::O,
+        Key::P,
+        Key::Q,
+        Key::R,
+        Key::S,
+        Key::T,
+        Key::U,
+        Key::V,
+        Key::W,
+        Key::X,
+        Key::Y,
+        Key::Z,
+    ];
+    /// The digit keys of the main block, from 0 to 9.
+    pub const DIGITS: [Key; 10] = [
+        Key::Digit0,
+        Key::Digit1,
+        Key::Digit2,
+        Key::Digit3,
+        Key::Digit4,
+        Key::Digit5,
+        Key::Digit6,
+        Key::Digit7,
+        Key::Digit8,
+        Key::Digit9,
+    ];
+    /// The digit keys of the numeric keypad, from 0 to 9.
+    pub const NUMPAD_DIGITS: [Key; 10] = [
+        Key::Numpad0,
+        Key::Numpad1,
+        Key::Numpad2,
+        Key::Numpad3,
+        Key::Numpad4,
+        Key::Numpad5,
+        Key::Numpad6,
+        Key::Numpad7,
+        Key::Numpad8,
+        Key::Numpad9,
+    ];
+    /// The function keys, from F1 to F12.
+    pub const FUNCTION_KEYS: [Key; 12] = [
+        Key::F1,
+        Key::F2,
+        Key::F3,
+        Key::F4,
+        Key::F5,
+        Key::F6,
+        Key::F7,
+        Key::F8,
+        Key::F9,
+        Key::F10,
+        Key::F11,
+        Key::F12,
+    ];
+}
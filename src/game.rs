@@ -0,0 +1,222 @@
+//! The interface between the platform layer and the game, when the game is a separate library.
+//!
+//! The platform owns the window, the input and the memory, and calls into the game through the
+//! functions below, exported by the game library with `#[no_mangle]`. Because the library can be
+//! swapped while the game runs (see [`hot_reload`](crate::hot_reload)), the game keeps all of its
+//! state in [`GameMemory`], never in statics.
+//!
+//! The update and render functions use the Rust ABI, so both sides have to be built by the same
+//! compiler against the same version of this crate. [`GAME_API_VERSION`] catches the obvious
+//! mismatches.
+
+use crate::{
+    event::Event,
+    memory::{align_up, MemoryArena},
+    raster::Framebuffer,
+};
+use core::{
+    mem::{align_of, size_of},
+    time::Duration,
+};
+
+/// Bumped whenever the functions, the events or [`GameMemory`] change.
+pub const GAME_API_VERSION: u32 = 4;
+
+/// The name of the exported [`GameApiVersionFn`].
+pub const GAME_API_VERSION_SYMBOL: &str = "game_api_version";
+/// The name of the exported [`GameUpdateFn`].
+pub const GAME_UPDATE_SYMBOL: &str = "game_update";
+/// The name of the exported [`GameRenderFn`].
+pub const GAME_RENDER_SYMBOL: &str = "game_render";
+
+/// `game_api_version`, returning the [`GAME_API_VERSION`] the game was built with.
+pub type GameApiVersionFn = extern "C" fn() -> u32;
+
+/// `game_update`, advancing the game by `dt` after the `events` received since the last update.
+pub type GameUpdateFn = fn(memory: &mut GameMemory, events: &[Event], dt: Duration);
+
+/// `game_render`, drawing the game `alpha` of the way between the last two updates.
+pub type GameRenderFn = fn(memory: &mut GameMemory, framebuffer: &mut Framebuffer, alpha: f32);
+
+/// The memory the platform hands to the game, which outlives any one load of the game library.
+///
+/// The permanent arena holds the game state, the transient arena can be thrown away at any time
+/// (e.g. caches): the game has to check [`MemoryArena::used`] to know whether it was. Both start
+/// zeroed.
+#[repr(C)]
+#[derive(Debug)]
+pub struct GameMemory {
+    /// Set once the game state is in the permanent arena, see [`GameMemory::state`].
+    pub is_initialized: bool,
+    pub permanent: MemoryArena,
+    pub transient: MemoryArena,
+}
+
+impl GameMemory {
+    /// Hands out two blocks of memory.
+    ///
+    /// ## Safety
+    /// * Both blocks must be valid for reads and writes of their size, zeroed, and not overlap.
+    /// * They must outlive the returned value, and not be accessed but through it.
+    pub unsafe fn from_raw_parts(
+        permanent_storage: *mut u8,
+        permanent_storage_size: usize,
+        transient_storage: *mut u8,
+        transient_storage_size: usize,
+    ) -> Self {
+        Self {
+            is_initialized: false,
+            permanent: MemoryArena::from_raw_parts(permanent_storage, permanent_storage_size),
+            transient: MemoryArena::from_raw_parts(transient_storage, transient_storage_size),
+        }
+    }
+
+    /// The game state, the first thing in the permanent arena, set up with `init` the first time.
+    ///
+    /// The size and alignment of `T` are kept in front of the state, and checked on every call:
+    /// asking for the state as a type of another layout panics.
+    ///
+    /// ## Safety
+    /// The state is only ever found by its place, so it must always be asked for as the same `T`,
+    /// including after a [`GameMemory::restore`] or once the game library is reloaded. Another
+    /// type of the same layout goes unnoticed.
+    pub unsafe fn state<T>(&mut self, init: impl FnOnce() -> T) -> &mut T {
+        let layout = StateLayout::of::<T>();
+        let base = self.permanent.base() as usize;
+        let layout_offset = align_up(base, align_of::<StateLayout>()) - base;
+        let offset = align_up(
+            base + layout_offset + size_of::<StateLayout>(),
+            align_of::<T>(),
+        ) - base;
+        if !self.is_initialized {
+            assert_eq!(self.permanent.used(), 0, "the game state must come first");
+            self.permanent
+                .push(layout)
+                .and_then(|_| self.permanent.push(init()))
+                .expect("no room for the game state in the permanent arena");
+            self.is_initialized = true;
+        }
+        assert!(offset + size_of::<T>() <= self.permanent.used());
+        // Pushed above, at the same places since the arena was empty.
+        let stored = &*self
+            .permanent
+            .base()
+            .add(layout_offset)
+            .cast::<StateLayout>();
+        assert_eq!(*stored, layout, "the game state was set up as another type");
+        &mut *self.permanent.base().add(offset).cast::<T>()
+    }
+
+    /// Copies the game state, to go back to it later with [`GameMemory::restore`].
+    ///
+    /// Only what is allocated in the permanent arena is copied: the transient arena is emptied by
+    /// a restore.
+    pub fn snapshot(&self) -> MemorySnapshot {
+        MemorySnapshot {
+            base_address: self.permanent.base() as usize,
+            is_initialized: self.is_initialized,
+            permanent: self.permanent.used_bytes().to_vec(),
+        }
+    }
+
+    /// Puts back a snapshot, and empties the transient arena.
+    ///
+    /// Panics if the snapshot comes from memory elsewhere, its pointers would be meaningless,
+    /// or doesn't fit in the permanent arena.
+    pub fn restore(&mut self, snapshot: &MemorySnapshot) {
+        assert_eq!(
+            snapshot.base_address,
+            self.permanent.base() as usize,
+            "the snapshot was taken from memory at another address"
+        );
+        assert!(snapshot.permanent.len() <= self.permanent.size());
+        self.permanent.set_used(snapshot.permanent.len());
+        if !snapshot.permanent.is_empty() {
+            unsafe {
+                self.permanent
+                    .base()
+                    .copy_from_nonoverlapping(snapshot.permanent.as_ptr(), snapshot.permanent.len())
+            };
+        }
+        self.transient.clear();
+        self.is_initialized = snapshot.is_initialized;
+    }
+}
+
+/// The size and alignment of the game state, stored in front of it, see [`GameMemory::state`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StateLayout {
+    size: usize,
+    align: usize,
+}
+
+impl StateLayout {
+    fn of<T>() -> Self {
+        Self {
+            size: size_of::<T>(),
+            align: align_of::<T>(),
+        }
+    }
+}
+
+/// A copy of the permanent arena of a [`GameMemory`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemorySnapshot {
+    /// Where the permanent arena was, pointers into it are only valid there.
+    pub base_address: usize,
+    pub is_initialized: bool,
+    /// The allocated part of the permanent arena.
+    pub permanent: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heap_memory(permanent: &mut [u64]) -> GameMemory {
+        unsafe {
+            GameMemory::from_raw_parts(
+                permanent.as_mut_ptr().cast(),
+                permanent.len() * 8,
+                [].as_mut_ptr(),
+                0,
+            )
+        }
+    }
+
+    #[test]
+    fn the_state_is_set_up_once() {
+        let mut permanent = [0_u64; 8];
+        let mut memory = heap_memory(&mut permanent);
+        unsafe {
+            *memory.state(|| 41_u64) += 1;
+            assert_eq!(*memory.state(|| 0_u64), 42);
+        }
+        // After the layout, aligned for the state.
+        assert_eq!(memory.permanent.used(), size_of::<StateLayout>() + 8);
+        let snapshot = memory.snapshot();
+        unsafe { *memory.state(|| 0_u64) = 7 };
+        memory.restore(&snapshot);
+        assert_eq!(unsafe { *memory.state(|| 0_u64) }, 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "the game state was set up as another type")]
+    fn the_state_layout_is_checked() {
+        let mut permanent = [0_u64; 8];
+        let mut memory = heap_memory(&mut permanent);
+        unsafe {
+            memory.state(|| 0_u32);
+            memory.state(|| false);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "no room for the game state")]
+    fn the_state_must_fit() {
+        let mut permanent = [0_u64; 2];
+        let mut memory = heap_memory(&mut permanent);
+        unsafe { memory.state(|| [0_u8; 64]) };
+    }
+}
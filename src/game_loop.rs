@@ -0,0 +1,263 @@
+//! A fixed timestep game loop, on top of any backend's event loop.
+//!
+//! The game logic runs at a fixed rate whatever the frame rate, and rendering interpolates
+//! between the last two logic states, see [Fix Your Timestep!](https://gafferongames.com/post/fix_your_timestep/).
+//! The scheduling only depends on a [`Clock`], so it can be driven deterministically by a fake one.
+
+use crate::{
+    event::{ControlFlow, Event, PlatformEventLoop},
+    timing::Clock,
+};
+use core::time::Duration;
+
+/// What a [`GameLoop`] drives.
+pub trait Game {
+    /// Handles an event from the event loop, before the next frame.
+    fn event(&mut self, _event: Event, _control_flow: &mut ControlFlow) {}
+
+    /// Advances the game logic by `dt`, always the same duration.
+    fn update(&mut self, dt: Duration);
+
+    /// Renders the game, `alpha` of the way (from 0 to 1) between the previous logic state and
+    /// the current one.
+    fn render(&mut self, alpha: f32);
+}
+
+/// What [`FixedTimestep::advance`] decided for a frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Steps {
+    /// How many updates to run.
+    pub updates: u32,
+    /// How far the time left over is into the next update, from 0 to 1.
+    pub alpha: f32,
+    /// Whether time was dropped to stay within the frame time limit.
+    pub clamped: bool,
+}
+
+/// The scheduling of a fixed timestep loop, without the loop.
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    dt: Duration,
+    /// The most time a single frame can account for, see [`FixedTimestep::advance`].
+    pub max_frame_time: Duration,
+    accumulator: Duration,
+    last_time: Option<Duration>,
+}
+
+impl FixedTimestep {
+    /// Schedules `update_rate` updates per second.
+    pub fn new(update_rate: u32) -> Self {
+        Self::with_dt(Duration::from_nanos(
+            1_000_000_000 / u64::from(update_rate.max(1)),
+        ))
+    }
+
+    /// Schedules an update every `dt`.
+    ///
+    /// `max_frame_time` defaults to 250ms or 8 updates, whichever is longer.
+    pub fn with_dt(dt: Duration) -> Self {
+        let dt = dt.max(Duration::from_nanos(1));
+        Self {
+            dt,
+            max_frame_time: (dt * 8).max(Duration::from_millis(250)),
+            accumulator: Duration::ZERO,
+            last_time: None,
+        }
+    }
+
+    /// The duration of an update.
+    pub fn dt(&self) -> Duration {
+        self.dt
+    }
+
+    /// Forgets the time elapsed so far, e.g. after a pause. The next frame runs no update.
+    pub fn reset(&mut self) {
+        self.accumulator = Duration::ZERO;
+        self.last_time = None;
+    }
+
+    /// Accounts for the time elapsed since the last call, `now` coming from a monotonic clock.
+    ///
+    /// A frame longer than [`FixedTimestep::max_frame_time`] only counts for that long: when
+    /// updates take longer than `dt` to run, catching up would make the next frame even longer,
+    /// and so on (the "spiral of death"). The game slows down instead.
+    ///
+    /// The first call only starts the clock.
+    pub fn advance(&mut self, now: Duration) -> Steps {
+        let elapsed = match self.last_time.replace(now) {
+            Some(last_time) => now.saturating_sub(last_time),
+            None => Duration::ZERO,
+        };
+        let clamped = elapsed > self.max_frame_time;
+        self.accumulator += elapsed.min(self.max_frame_time);
+        let mut updates = 0;
+        while self.accumulator >= self.dt {
+            self.accumulator -= self.dt;
+            updates += 1;
+        }
+        Steps {
+            updates,
+            alpha: self.accumulator.as_secs_f32() / self.dt.as_secs_f32(),
+            clamped,
+        }
+    }
+}
+
+/// Drives a [`Game`]: events, fixed rate updates, then rendering, as fast as possible.
+pub struct GameLoop<C: Clock> {
+    clock: C,
+    timestep: FixedTimestep,
+}
+
+impl<C: Clock> GameLoop<C> {
+    /// A loop running `update_rate` updates per second, measured with `clock`.
+    pub fn new(clock: C, update_rate: u32) -> Self {
+        Self {
+            clock,
+            timestep: FixedTimestep::new(update_rate),
+        }
+    }
+
+    /// The clock the loop is measured with.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// The scheduling, to tweak [`FixedTimestep::max_frame_time`].
+    pub fn timestep_mut(&mut self) -> &mut FixedTimestep {
+        &mut self.timestep
+    }
+
+    /// Runs one frame: the updates due by now, then rendering.
+    pub fn frame<G: Game>(&mut self, game: &mut G) -> Steps {
+        let steps = self.timestep.advance(self.clock.now());
+        for _ in 0..steps.updates {
+            game.update(self.timestep.dt());
+        }
+        game.render(steps.alpha);
+        steps
+    }
+
+    /// Runs `game` on `event_loop` until it asks to [`ControlFlow::Exit`].
+    ///
+    /// Events go to [`Game::event`] as they come, and a frame is run each time the loop is done
+    /// with them. The loop never waits for events: a [`ControlFlow::Wait`] is turned into
+    /// [`ControlFlow::Poll`].
+    ///
+    /// **Returns:** The exit code.
+    pub fn run<E, G>(&mut self, event_loop: &mut E, game: &mut G) -> Result<i32, E::Error>
+    where
+        E: PlatformEventLoop,
+        G: Game,
+    {
+        event_loop.run(|event, control_flow| {
+            match event {
+                Event::MainEventsCleared => {
+                    self.frame(game);
+                }
+                event => game.event(event, control_flow),
+            }
+            if *control_flow == ControlFlow::Wait {
+                *control_flow = ControlFlow::Poll;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timing::tests::FakeClock;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    /// Records what the loop asked for.
+    #[derive(Debug, Default)]
+    struct Recorder {
+        updates: Vec<Duration>,
+        alphas: Vec<f32>,
+    }
+
+    impl Game for Recorder {
+        fn update(&mut self, dt: Duration) {
+            self.updates.push(dt);
+        }
+
+        fn render(&mut self, alpha: f32) {
+            self.alphas.push(alpha);
+        }
+    }
+
+    /// Checks the updates and `alpha` of `steps`, the latter being a float ratio.
+    fn assert_steps(steps: Steps, updates: u32, alpha: f32) {
+        assert_eq!(steps.updates, updates);
+        assert!(
+            (steps.alpha - alpha).abs() < 1e-6,
+            "alpha {} instead of {}",
+            steps.alpha,
+            alpha
+        );
+    }
+
+    #[test]
+    fn updates_are_counted_and_the_rest_interpolated() {
+        let mut timestep = FixedTimestep::with_dt(10 * MS);
+        // The first frame only starts the clock.
+        assert_steps(timestep.advance(Duration::from_secs(100)), 0, 0.0);
+        assert_steps(timestep.advance(Duration::from_secs(100) + 25 * MS), 2, 0.5);
+        // The 5ms left over count toward the next frame.
+        assert_steps(timestep.advance(Duration::from_secs(100) + 32 * MS), 1, 0.2);
+        let steps = timestep.advance(Duration::from_secs(100) + 32 * MS);
+        assert_steps(steps, 0, 0.2);
+        assert!(!steps.clamped);
+        // Time going backwards counts for nothing.
+        assert_steps(timestep.advance(Duration::from_secs(99)), 0, 0.2);
+        // Neither does a pause.
+        timestep.reset();
+        timestep.advance(Duration::from_secs(200));
+        assert_steps(timestep.advance(Duration::from_secs(200) + 10 * MS), 1, 0.0);
+    }
+
+    #[test]
+    fn long_frames_are_clamped() {
+        let mut timestep = FixedTimestep::with_dt(10 * MS);
+        assert_eq!(timestep.max_frame_time, 250 * MS);
+        timestep.advance(Duration::ZERO);
+        // Right at the limit, everything counts.
+        let steps = timestep.advance(250 * MS);
+        assert_eq!(steps.updates, 25);
+        assert!(!steps.clamped);
+        // Past it, a frame only runs 25 updates however long it was.
+        let steps = timestep.advance(Duration::from_secs(10));
+        assert_eq!(steps.updates, 25);
+        assert_eq!(steps.alpha, 0.0);
+        assert!(steps.clamped);
+        timestep.max_frame_time = 35 * MS;
+        let steps = timestep.advance(Duration::from_secs(20));
+        assert_eq!((steps.updates, steps.alpha, steps.clamped), (3, 0.5, true));
+        // The limit is at least 8 updates long.
+        assert_eq!(FixedTimestep::with_dt(50 * MS).max_frame_time, 400 * MS);
+    }
+
+    #[test]
+    fn frames_run_the_updates_then_render() {
+        let clock = FakeClock::default();
+        let mut game_loop = GameLoop::new(&clock, 50);
+        let mut game = Recorder::default();
+        assert_eq!(game_loop.frame(&mut game).updates, 0);
+        clock.advance(50 * MS);
+        assert_eq!(game_loop.frame(&mut game).updates, 2);
+        clock.advance(20 * MS);
+        assert_eq!(game_loop.frame(&mut game).updates, 1);
+        assert_eq!(game.updates, [20 * MS; 3]);
+        assert_eq!(game.alphas, [0.0, 0.5, 0.5]);
+        // A hitch doesn't make the game catch up all at once.
+        clock.advance(Duration::from_secs(5));
+        let steps = game_loop.frame(&mut game);
+        // 10ms left over and 250ms of the hitch.
+        assert_steps(steps, 13, 0.0);
+        assert!(steps.clamped);
+        assert_eq!(game.updates.len(), 16);
+        assert_eq!(game.alphas.len(), 4);
+    }
+}
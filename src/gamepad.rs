@@ -0,0 +1,210 @@
+//! Platform independent gamepad state, with the deadzone handling.
+//!
+//! Raw sticks never rest exactly at the center, so values close to it have to be ignored, and the
+//! rest rescaled so the output still covers the whole range.
+
+use crate::win32::{
+    XINPUT_GAMEPAD_LEFT_THUMB_DEADZONE, XINPUT_GAMEPAD_RIGHT_THUMB_DEADZONE,
+    XINPUT_GAMEPAD_TRIGGER_THRESHOLD,
+};
+
+/// The buttons held on a gamepad, as a bit set.
+///
+/// The bits are the XInput ones, see `XINPUT_GAMEPAD_*`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct GamepadButtons(pub u16);
+
+impl GamepadButtons {
+    pub const DPAD_UP: Self = Self(0x0001);
+    pub const DPAD_DOWN: Self = Self(0x0002);
+    pub const DPAD_LEFT: Self = Self(0x0004);
+    pub const DPAD_RIGHT: Self = Self(0x0008);
+    pub const START: Self = Self(0x0010);
+    pub const BACK: Self = Self(0x0020);
+    pub const LEFT_THUMB: Self = Self(0x0040);
+    pub const RIGHT_THUMB: Self = Self(0x0080);
+    pub const LEFT_SHOULDER: Self = Self(0x0100);
+    pub const RIGHT_SHOULDER: Self = Self(0x0200);
+    pub const A: Self = Self(0x1000);
+    pub const B: Self = Self(0x2000);
+    pub const X: Self = Self(0x4000);
+    pub const Y: Self = Self(0x8000);
+
+    /// Whether all the buttons of `other` are held.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// The deadzones applied when normalising raw gamepad values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deadzones {
+    /// The radius under which the left stick is centered, out of 32767.
+    pub left_stick: i16,
+    /// The radius under which the right stick is centered, out of 32767.
+    pub right_stick: i16,
+    /// The value under which the triggers are released, out of 255.
+    pub trigger: u8,
+}
+
+impl Default for Deadzones {
+    /// The values recommended for XInput controllers.
+    fn default() -> Self {
+        Self {
+            left_stick: XINPUT_GAMEPAD_LEFT_THUMB_DEADZONE,
+            right_stick: XINPUT_GAMEPAD_RIGHT_THUMB_DEADZONE,
+            trigger: XINPUT_GAMEPAD_TRIGGER_THRESHOLD,
+        }
+    }
+}
+
+/// The state of a gamepad, normalised.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GamepadState {
+    pub buttons: GamepadButtons,
+    /// `[x, y]` within the unit circle, positive y is up.
+    pub left_stick: [f32; 2],
+    /// `[x, y]` within the unit circle, positive y is up.
+    pub right_stick: [f32; 2],
+    /// From 0 (released) to 1 (fully pressed).
+    pub left_trigger: f32,
+    /// From 0 (released) to 1 (fully pressed).
+    pub right_trigger: f32,
+}
+
+impl GamepadState {
+    /// Normalises raw values, in the ranges XInput reports them.
+    pub fn from_raw(
+        buttons: u16,
+        triggers: [u8; 2],
+        left_stick: [i16; 2],
+        right_stick: [i16; 2],
+        deadzones: &Deadzones,
+    ) -> Self {
+        Self {
+            buttons: GamepadButtons(buttons),
+            left_stick: normalize_stick(left_stick[0], left_stick[1], deadzones.left_stick),
+            right_stick: normalize_stick(right_stick[0], right_stick[1], deadzones.right_stick),
+            left_trigger: normalize_trigger(triggers[0], deadzones.trigger),
+            right_trigger: normalize_trigger(triggers[1], deadzones.trigger),
+        }
+    }
+}
+
+/// Normalises a raw stick position, applying a radial deadzone.
+///
+/// The deadzone is applied to the distance from the center rather than to each axis, so the stick
+/// doesn't snap to the axes near the center.
+///
+/// **Returns:** `[x, y]` within the unit circle, `[0.0, 0.0]` inside the deadzone.
+pub fn normalize_stick(x: i16, y: i16, deadzone: i16) -> [f32; 2] {
+    const MAX: f32 = i16::MAX as f32;
+    let [x, y] = [f32::from(x), f32::from(y)];
+    let deadzone = f32::from(deadzone.max(0)).min(MAX - 1.0);
+    let magnitude = (x * x + y * y).sqrt();
+    if magnitude <= deadzone {
+        return [0.0, 0.0];
+    }
+    // i16::MIN makes the raw range slightly lopsided, and the corners go beyond MAX.
+    let normalized = (magnitude.min(MAX) - deadzone) / (MAX - deadzone);
+    let scale = normalized / magnitude;
+    [x * scale, y * scale]
+}
+
+/// Normalises a raw trigger value, applying a threshold.
+///
+/// **Returns:** A value from 0 to 1, 0 up to the threshold.
+pub fn normalize_trigger(value: u8, threshold: u8) -> f32 {
+    if value <= threshold {
+        0.0
+    } else {
+        f32::from(value - threshold) / f32::from(u8::MAX - threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn length([x, y]: [f32; 2]) -> f32 {
+        (x * x + y * y).sqrt()
+    }
+
+    #[test]
+    fn sticks_inside_the_deadzone_are_centered() {
+        let deadzone = Deadzones::default().left_stick;
+        assert_eq!(normalize_stick(0, 0, deadzone), [0.0, 0.0]);
+        assert_eq!(normalize_stick(deadzone, 0, deadzone), [0.0, 0.0]);
+        assert_eq!(normalize_stick(0, -deadzone, deadzone), [0.0, 0.0]);
+        let [x, y] = normalize_stick(deadzone + 1, 0, deadzone);
+        assert!(x > 0.0 && x < 0.001);
+        assert_eq!(y, 0.0);
+    }
+
+    #[test]
+    fn full_deflection_is_one() {
+        let deadzone = Deadzones::default().right_stick;
+        assert_eq!(normalize_stick(i16::MAX, 0, deadzone), [1.0, 0.0]);
+        assert_eq!(normalize_stick(0, i16::MAX, deadzone), [0.0, 1.0]);
+        assert_eq!(normalize_stick(-i16::MAX, 0, deadzone), [-1.0, 0.0]);
+        // One further than the positive side, but no further than -1.
+        assert_eq!(normalize_stick(i16::MIN, 0, deadzone), [-1.0, 0.0]);
+        assert_eq!(normalize_stick(0, i16::MIN, deadzone), [0.0, -1.0]);
+        // Even with no deadzone at all.
+        assert_eq!(normalize_stick(i16::MIN, 0, 0), [-1.0, 0.0]);
+        // The corners of the raw square stay on the unit circle.
+        for &(x, y) in &[
+            (i16::MAX, i16::MAX),
+            (i16::MIN, i16::MIN),
+            (i16::MIN, i16::MAX),
+        ] {
+            assert!((length(normalize_stick(x, y, deadzone)) - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn the_deadzone_is_radial() {
+        let deadzone = 7849;
+        // Each axis is inside the deadzone, the distance from the center isn't.
+        let [x, y] = normalize_stick(6000, -6000, deadzone);
+        assert!(x > 0.0 && y < 0.0);
+        assert_eq!(x, -y);
+        // The direction is kept.
+        let [x, y] = normalize_stick(20000, 10000, deadzone);
+        assert!((x / y - 2.0).abs() < 1e-6);
+        // Closer to the center on the diagonal.
+        assert_eq!(normalize_stick(5000, 5000, deadzone), [0.0, 0.0]);
+        // The output grows with the distance to the deadzone, the same in every direction.
+        let straight = length(normalize_stick(12000, 0, deadzone));
+        let diagonal = length(normalize_stick(8485, 8485, deadzone));
+        assert!((straight - diagonal).abs() < 1e-3);
+    }
+
+    #[test]
+    fn triggers_are_thresholded() {
+        let threshold = Deadzones::default().trigger;
+        assert_eq!(normalize_trigger(0, threshold), 0.0);
+        assert_eq!(normalize_trigger(threshold, threshold), 0.0);
+        assert!(normalize_trigger(threshold + 1, threshold) > 0.0);
+        assert_eq!(normalize_trigger(u8::MAX, threshold), 1.0);
+        assert_eq!(normalize_trigger(u8::MAX, 0), 1.0);
+        assert_eq!(normalize_trigger(u8::MAX, u8::MAX), 0.0);
+    }
+
+    #[test]
+    fn raw_states_are_normalized() {
+        let state = GamepadState::from_raw(
+            0x1001,
+            [u8::MAX, 10],
+            [i16::MAX, 100],
+            [0, i16::MIN],
+            &Deadzones::default(),
+        );
+        assert!(state.buttons.contains(GamepadButtons::A));
+        assert!(state.buttons.contains(GamepadButtons::DPAD_UP));
+        assert!(!state.buttons.contains(GamepadButtons::B));
+        assert_eq!([state.left_trigger, state.right_trigger], [1.0, 0.0]);
+        assert!((length(state.left_stick) - 1.0).abs() < 1e-6);
+        assert_eq!(state.right_stick, [0.0, -1.0]);
+    }
+}
@@ -0,0 +1,206 @@
+//! OpenGL types, constants, and a table of function pointers loaded at runtime.
+//!
+//! Only GL 1.1 is exported by the system libraries, everything newer has to be asked for to the
+//! platform (`wglGetProcAddress`, ...) once a context is current. [`Gl::load`] does that for every
+//! function declared in the table, through whichever loader the platform layer hands it.
+#![allow(non_snake_case)]
+#![allow(non_camel_case_types)]
+
+use core::ffi::c_void;
+use std::os::raw::{c_char, c_double, c_float, c_int, c_uchar, c_uint};
+
+pub type GLenum = c_uint;
+pub type GLboolean = c_uchar;
+pub type GLbitfield = c_uint;
+pub type GLint = c_int;
+pub type GLuint = c_uint;
+pub type GLsizei = c_int;
+pub type GLfloat = c_float;
+pub type GLclampf = c_float;
+pub type GLdouble = c_double;
+pub type GLchar = c_char;
+pub type GLubyte = c_uchar;
+pub type GLsizeiptr = isize;
+pub type GLintptr = isize;
+
+pub const GL_FALSE: GLboolean = 0;
+pub const GL_TRUE: GLboolean = 1;
+
+pub const GL_NO_ERROR: GLenum = 0;
+
+pub const GL_DEPTH_BUFFER_BIT: GLbitfield = 0x00000100;
+pub const GL_STENCIL_BUFFER_BIT: GLbitfield = 0x00000400;
+pub const GL_COLOR_BUFFER_BIT: GLbitfield = 0x00004000;
+
+pub const GL_TRIANGLES: GLenum = 0x0004;
+
+pub const GL_FLOAT: GLenum = 0x1406;
+
+pub const GL_VENDOR: GLenum = 0x1F00;
+pub const GL_RENDERER: GLenum = 0x1F01;
+pub const GL_VERSION: GLenum = 0x1F02;
+pub const GL_SHADING_LANGUAGE_VERSION: GLenum = 0x8B8C;
+
+pub const GL_ARRAY_BUFFER: GLenum = 0x8892;
+pub const GL_STATIC_DRAW: GLenum = 0x88E4;
+
+pub const GL_FRAGMENT_SHADER: GLenum = 0x8B30;
+pub const GL_VERTEX_SHADER: GLenum = 0x8B31;
+pub const GL_COMPILE_STATUS: GLenum = 0x8B81;
+pub const GL_LINK_STATUS: GLenum = 0x8B82;
+pub const GL_INFO_LOG_LENGTH: GLenum = 0x8B84;
+
+/// Declares the [`Gl`] function table: one field, one loader entry and one method per function.
+///
+/// GL functions use the `system` ABI: `stdcall` on 32 bits Windows, `C` everywhere else.
+macro_rules! gl_functions {
+    ($(
+        $(#[$meta:meta])*
+        fn $name:ident($($arg:ident: $arg_ty:ty),* $(,)?) $(-> $ret:ty)?;
+    )*) => {
+        /// The OpenGL functions, loaded for the context that was current during [`Gl::load`].
+        pub struct Gl {
+            $(
+                $name: Option<unsafe extern "system" fn($($arg_ty),*) $(-> $ret)?>,
+            )*
+        }
+
+        impl Gl {
+            /// Loads every function of the table through `loader`.
+            ///
+            /// The loader gets the name of a function and returns its address, or null if it's
+            /// not available.
+            ///
+            /// ## Safety
+            ///
+            /// The addresses returned by `loader` must be those of the named GL functions.
+            pub unsafe fn load<F>(mut loader: F) -> Self
+            where
+                F: FnMut(&str) -> *const c_void,
+            {
+                Self {
+                    $(
+                        $name: core::mem::transmute::<
+                            *const c_void,
+                            Option<unsafe extern "system" fn($($arg_ty),*) $(-> $ret)?>,
+                        >(loader(stringify!($name))),
+                    )*
+                }
+            }
+
+            /// The names of the functions the loader couldn't find.
+            pub fn missing_functions(&self) -> Vec<&'static str> {
+                let mut missing = Vec::new();
+                $(
+                    if self.$name.is_none() {
+                        missing.push(stringify!($name));
+                    }
+                )*
+                missing
+            }
+
+            $(
+                $(#[$meta])*
+                ///
+                /// ## Safety
+                ///
+                /// The context this table was loaded for must be current, and the arguments valid
+                /// as per the GL specification.
+                ///
+                /// ## Panics
+                ///
+                /// If the function wasn't loaded.
+                #[inline]
+                pub unsafe fn $name(&self, $($arg: $arg_ty),*) $(-> $ret)? {
+                    match self.$name {
+                        Some(f) => f($($arg),*),
+                        None => panic!(concat!(stringify!($name), " is not loaded")),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+gl_functions! {
+    /// [`glGetError`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glGetError.xhtml)
+    fn glGetError() -> GLenum;
+    /// [`glGetString`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glGetString.xhtml)
+    fn glGetString(name: GLenum) -> *const GLubyte;
+    /// [`glViewport`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glViewport.xhtml)
+    fn glViewport(x: GLint, y: GLint, width: GLsizei, height: GLsizei);
+    /// [`glClearColor`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glClearColor.xhtml)
+    fn glClearColor(red: GLclampf, green: GLclampf, blue: GLclampf, alpha: GLclampf);
+    /// [`glClear`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glClear.xhtml)
+    fn glClear(mask: GLbitfield);
+    /// [`glDrawArrays`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glDrawArrays.xhtml)
+    fn glDrawArrays(mode: GLenum, first: GLint, count: GLsizei);
+
+    /// [`glGenVertexArrays`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glGenVertexArrays.xhtml)
+    fn glGenVertexArrays(n: GLsizei, arrays: *mut GLuint);
+    /// [`glBindVertexArray`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glBindVertexArray.xhtml)
+    fn glBindVertexArray(array: GLuint);
+    /// [`glDeleteVertexArrays`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glDeleteVertexArrays.xhtml)
+    fn glDeleteVertexArrays(n: GLsizei, arrays: *const GLuint);
+    /// [`glGenBuffers`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glGenBuffers.xhtml)
+    fn glGenBuffers(n: GLsizei, buffers: *mut GLuint);
+    /// [`glBindBuffer`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glBindBuffer.xhtml)
+    fn glBindBuffer(target: GLenum, buffer: GLuint);
+    /// [`glBufferData`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glBufferData.xhtml)
+    fn glBufferData(target: GLenum, size: GLsizeiptr, data: *const c_void, usage: GLenum);
+    /// [`glDeleteBuffers`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glDeleteBuffers.xhtml)
+    fn glDeleteBuffers(n: GLsizei, buffers: *const GLuint);
+    /// [`glVertexAttribPointer`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glVertexAttribPointer.xhtml)
+    fn glVertexAttribPointer(
+        index: GLuint,
+        size: GLint,
+        type_: GLenum,
+        normalized: GLboolean,
+        stride: GLsizei,
+        pointer: *const c_void,
+    );
+    /// [`glEnableVertexAttribArray`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glEnableVertexAttribArray.xhtml)
+    fn glEnableVertexAttribArray(index: GLuint);
+
+    /// [`glCreateShader`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glCreateShader.xhtml)
+    fn glCreateShader(shader_type: GLenum) -> GLuint;
+    /// [`glShaderSource`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glShaderSource.xhtml)
+    fn glShaderSource(
+        shader: GLuint,
+        count: GLsizei,
+        string: *const *const GLchar,
+        length: *const GLint,
+    );
+    /// [`glCompileShader`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glCompileShader.xhtml)
+    fn glCompileShader(shader: GLuint);
+    /// [`glGetShaderiv`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glGetShader.xhtml)
+    fn glGetShaderiv(shader: GLuint, pname: GLenum, params: *mut GLint);
+    /// [`glGetShaderInfoLog`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glGetShaderInfoLog.xhtml)
+    fn glGetShaderInfoLog(
+        shader: GLuint,
+        max_length: GLsizei,
+        length: *mut GLsizei,
+        info_log: *mut GLchar,
+    );
+    /// [`glDeleteShader`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glDeleteShader.xhtml)
+    fn glDeleteShader(shader: GLuint);
+    /// [`glCreateProgram`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glCreateProgram.xhtml)
+    fn glCreateProgram() -> GLuint;
+    /// [`glAttachShader`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glAttachShader.xhtml)
+    fn glAttachShader(program: GLuint, shader: GLuint);
+    /// [`glLinkProgram`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glLinkProgram.xhtml)
+    fn glLinkProgram(program: GLuint);
+    /// [`glGetProgramiv`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glGetProgram.xhtml)
+    fn glGetProgramiv(program: GLuint, pname: GLenum, params: *mut GLint);
+    /// [`glGetProgramInfoLog`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glGetProgramInfoLog.xhtml)
+    fn glGetProgramInfoLog(
+        program: GLuint,
+        max_length: GLsizei,
+        length: *mut GLsizei,
+        info_log: *mut GLchar,
+    );
+    /// [`glUseProgram`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glUseProgram.xhtml)
+    fn glUseProgram(program: GLuint);
+    /// [`glDeleteProgram`](https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glDeleteProgram.xhtml)
+    fn glDeleteProgram(program: GLuint);
+}
@@ -0,0 +1,21 @@
+//! A backend without any window system, for CI and tests.
+//!
+//! It exposes the same `EventLoop` and `Window` surface as the native backends. Nothing ever
+//! happens on its own: events are injected by the caller, see [`event_loop::EventLoop::inject`],
+//! and every framebuffer presented to a window is kept in memory, see
+//! [`event_loop::EventLoop::presented_frames`]. The monitors are made up as well, see
+//! [`event_loop::EventLoop::set_monitors`].
+
+pub mod event_loop;
+pub mod monitor;
+pub mod window;
+
+/// An error reported by the headless backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeadlessError(pub &'static str);
+impl core::fmt::Display for HeadlessError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "HeadlessError({})", self.0)
+    }
+}
+impl std::error::Error for HeadlessError {}
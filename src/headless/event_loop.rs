@@ -0,0 +1,385 @@
+//! A closure based event loop, fed by injected events.
+
+use super::{
+    monitor::{self, Monitor},
+    HeadlessError,
+};
+use crate::{
+    dpi::Dpi,
+    event::{ControlFlow, DeviceEvent, DeviceId, Event, PlatformEventLoop, WindowEvent, WindowId},
+    monitor::MonitorInfo,
+    raster::Framebuffer,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+};
+
+/// What the backend knows about a window, kept after the window is gone.
+#[derive(Debug, Default)]
+pub(crate) struct WindowRecord {
+    pub(crate) title: String,
+    pub(crate) position: [i32; 2],
+    pub(crate) size: [u32; 2],
+    pub(crate) dpi: Dpi,
+    pub(crate) visible: bool,
+    pub(crate) alive: bool,
+    pub(crate) frames: Vec<Framebuffer>,
+}
+
+/// The fake window system, shared by the event loop and its windows.
+#[derive(Debug, Default)]
+pub(crate) struct Backend {
+    next_id: Cell<usize>,
+    pub(crate) pending: RefCell<VecDeque<Event>>,
+    pub(crate) windows: RefCell<HashMap<WindowId, WindowRecord>>,
+    pub(crate) monitors: RefCell<Vec<MonitorInfo>>,
+}
+
+impl Backend {
+    /// Registers a new window.
+    pub(crate) fn create_window(&self, record: WindowRecord) -> WindowId {
+        // Start at 1 so no window is ever mistaken for a null handle.
+        let id = WindowId(self.next_id.get() + 1);
+        self.next_id.set(id.0);
+        self.windows.borrow_mut().insert(id, record);
+        id
+    }
+
+    /// Queues `event` for `window_id`, updating what the backend knows about the window.
+    pub(crate) fn push_event(&self, window_id: WindowId, event: WindowEvent) {
+        if let Some(record) = self.windows.borrow_mut().get_mut(&window_id) {
+            match event {
+                WindowEvent::Resized { size, dpi } => {
+                    record.size = [size.width, size.height];
+                    record.dpi = dpi;
+                }
+                WindowEvent::DpiChanged(dpi) => record.dpi = dpi,
+                WindowEvent::Moved { x, y } => record.position = [x, y],
+                _ => {}
+            }
+        }
+        self.pending
+            .borrow_mut()
+            .push_back(Event::WindowEvent { window_id, event });
+    }
+
+    fn pop_event(&self) -> Option<Event> {
+        self.pending.borrow_mut().pop_front()
+    }
+}
+
+/// Runs headless windows, see [`EventLoop::run`].
+pub struct EventLoop {
+    backend: Rc<Backend>,
+}
+
+impl EventLoop {
+    /// Prepares an event loop with no window and no pending event, and a single monitor, see
+    /// [`monitor::default_monitor`].
+    pub fn new() -> Result<Self, HeadlessError> {
+        let backend = Backend {
+            monitors: RefCell::new(vec![monitor::default_monitor()]),
+            ..Backend::default()
+        };
+        Ok(Self {
+            backend: Rc::new(backend),
+        })
+    }
+
+    pub(crate) fn backend(&self) -> &Rc<Backend> {
+        &self.backend
+    }
+
+    /// Queues `event` as if the window system had sent it to the window `window_id`.
+    ///
+    /// * [`WindowEvent::Resized`], [`WindowEvent::Moved`] and [`WindowEvent::DpiChanged`] also
+    ///   change the size, position and DPI the window reports.
+    /// * Injecting [`WindowEvent::Destroyed`] doesn't destroy the window, see
+    ///   [`Window::destroy`](super::window::Window::destroy).
+    pub fn inject(&self, window_id: WindowId, event: WindowEvent) {
+        self.backend.push_event(window_id, event);
+    }
+
+    /// Queues `event` as if the input device `device_id` had reported it.
+    pub fn inject_device_event(&self, device_id: DeviceId, event: DeviceEvent) {
+        self.backend
+            .pending
+            .borrow_mut()
+            .push_back(Event::DeviceEvent { device_id, event });
+    }
+
+    /// Replaces the fake monitors, in the order [`EventLoop::available_monitors`] lists them.
+    ///
+    /// The handles to the previous monitors now refer to the new monitors at the same place in
+    /// the list, or to nothing.
+    pub fn set_monitors(&self, monitors: Vec<MonitorInfo>) {
+        *self.backend.monitors.borrow_mut() = monitors;
+    }
+
+    /// Every fake monitor.
+    pub fn available_monitors(&self) -> Vec<Monitor> {
+        let count = self.backend.monitors.borrow().len();
+        (0..count)
+            .map(|index| Monitor::new(Rc::clone(&self.backend), index))
+            .collect()
+    }
+
+    /// The first fake monitor marked as primary, if there is one.
+    pub fn primary_monitor(&self) -> Option<Monitor> {
+        let monitors = self.backend.monitors.borrow();
+        let index = monitors.iter().position(|monitor| monitor.is_primary)?;
+        Some(Monitor::new(Rc::clone(&self.backend), index))
+    }
+
+    /// Every framebuffer presented to the window `window_id` so far, oldest first.
+    ///
+    /// Available even once the window has been destroyed.
+    pub fn presented_frames(&self, window_id: WindowId) -> Vec<Framebuffer> {
+        self.backend
+            .windows
+            .borrow()
+            .get(&window_id)
+            .map(|record| record.frames.clone())
+            .unwrap_or_default()
+    }
+
+    /// The title of the window `window_id`, if there ever was such a window.
+    pub fn window_title(&self, window_id: WindowId) -> Option<String> {
+        let windows = self.backend.windows.borrow();
+        windows.get(&window_id).map(|record| record.title.clone())
+    }
+
+    /// Whether the window `window_id` has been shown, and not destroyed since.
+    pub fn is_window_visible(&self, window_id: WindowId) -> bool {
+        let windows = self.backend.windows.borrow();
+        windows
+            .get(&window_id)
+            .is_some_and(|record| record.alive && record.visible)
+    }
+
+    /// Runs the loop until the handler asks to [`ControlFlow::Exit`].
+    ///
+    /// The handler is called for every event, then once with [`Event::MainEventsCleared`] before
+    /// the loop goes around again depending on the [`ControlFlow`] it left behind.
+    ///
+    /// * The handler may inject more events, through
+    ///   [`Window::inject`](super::window::Window::inject).
+    ///
+    /// **Returns:** The exit code, or an error when the handler asks to [`ControlFlow::Wait`]
+    /// while there is nothing left to handle, since nothing else could ever wake the loop up.
+    pub fn run<F>(&mut self, mut handler: F) -> Result<i32, HeadlessError>
+    where
+        F: FnMut(Event, &mut ControlFlow),
+    {
+        let backend = Rc::clone(&self.backend);
+        let mut control_flow = ControlFlow::default();
+        let result = loop {
+            if control_flow == ControlFlow::Wait && backend.pending.borrow().is_empty() {
+                break Err(HeadlessError("waiting with no event left to inject"));
+            }
+            // Only the events queued so far, the ones the handler injects wait for the next round.
+            let queued = backend.pending.borrow().len();
+            for _ in 0..queued {
+                match backend.pop_event() {
+                    Some(event) => handler(event, &mut control_flow),
+                    None => break,
+                }
+            }
+            handler(Event::MainEventsCleared, &mut control_flow);
+            if let ControlFlow::Exit(exit_code) = control_flow {
+                break Ok(exit_code);
+            }
+        };
+        // Don't leak events into a later run.
+        backend.pending.borrow_mut().clear();
+        result
+    }
+}
+
+impl PlatformEventLoop for EventLoop {
+    type Error = HeadlessError;
+
+    fn run<F>(&mut self, handler: F) -> Result<i32, HeadlessError>
+    where
+        F: FnMut(Event, &mut ControlFlow),
+    {
+        EventLoop::run(self, handler)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dpi::PhysicalSize,
+        event::{Key, MouseButton},
+        headless::window::Window,
+        raster::Rgba8,
+    };
+
+    /// Runs `event_loop` for a single round, collecting the events.
+    fn run_once(event_loop: &mut EventLoop) -> Vec<Event> {
+        let mut events = Vec::new();
+        let exit_code = event_loop
+            .run(|event, control_flow| {
+                events.push(event);
+                if event == Event::MainEventsCleared {
+                    *control_flow = ControlFlow::Exit(0);
+                }
+            })
+            .unwrap();
+        assert_eq!(exit_code, 0);
+        events
+    }
+
+    #[test]
+    fn events_are_delivered_in_order() {
+        let mut event_loop = EventLoop::new().unwrap();
+        let window = Window::new(&event_loop, "test", None, [640, 480], ()).unwrap();
+        let window_id = window.id();
+        window.inject(WindowEvent::MouseMove { x: 1, y: 2 });
+        let device_id = DeviceId(7);
+        let device_event = DeviceEvent::MouseButton {
+            button: MouseButton::Left,
+            pressed: true,
+        };
+        event_loop.inject_device_event(device_id, device_event);
+        event_loop.inject(window_id, WindowEvent::Focused(true));
+        let window_event = |event| Event::WindowEvent { window_id, event };
+        assert_eq!(
+            run_once(&mut event_loop),
+            [
+                window_event(WindowEvent::Created),
+                window_event(WindowEvent::Resized {
+                    size: PhysicalSize::new(640, 480),
+                    dpi: Dpi::BASE,
+                }),
+                window_event(WindowEvent::MouseMove { x: 1, y: 2 }),
+                Event::DeviceEvent {
+                    device_id,
+                    event: device_event,
+                },
+                window_event(WindowEvent::Focused(true)),
+                Event::MainEventsCleared,
+            ]
+        );
+    }
+
+    #[test]
+    fn events_injected_by_the_handler_wait_for_the_next_round() {
+        let mut event_loop = EventLoop::new().unwrap();
+        let window = Window::new(&event_loop, "test", None, [1, 1], ()).unwrap();
+        let window_id = window.id();
+        let mut events = Vec::new();
+        let mut rounds = 0;
+        let exit_code = event_loop
+            .run(|event, control_flow| {
+                events.push(event);
+                if event == Event::MainEventsCleared {
+                    rounds += 1;
+                    match rounds {
+                        1 => window.inject(WindowEvent::Paint),
+                        _ => *control_flow = ControlFlow::Exit(0),
+                    }
+                }
+            })
+            .unwrap();
+        assert_eq!(exit_code, 0);
+        assert_eq!(
+            &events[2..],
+            [
+                Event::MainEventsCleared,
+                Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::Paint,
+                },
+                Event::MainEventsCleared,
+            ]
+        );
+    }
+
+    #[test]
+    fn exit_returns_the_exit_code_and_drops_the_rest() {
+        let mut event_loop = EventLoop::new().unwrap();
+        let window = Window::new(&event_loop, "test", None, [1, 1], ()).unwrap();
+        let mut handled = 0;
+        let exit_code = event_loop
+            .run(|_, control_flow| {
+                handled += 1;
+                *control_flow = ControlFlow::Exit(42);
+            })
+            .unwrap();
+        // Exiting takes effect once the round is over.
+        assert_eq!(exit_code, 42);
+        assert_eq!(handled, 3);
+        // The events left over don't leak into the next run.
+        window.inject(WindowEvent::Focused(false));
+        let events = run_once(&mut event_loop);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1], Event::MainEventsCleared);
+    }
+
+    #[test]
+    fn waiting_with_nothing_to_inject_is_an_error() {
+        // Not even a single round.
+        let mut event_loop = EventLoop::new().unwrap();
+        let mut handled = 0;
+        let result = event_loop.run(|_, _| handled += 1);
+        assert!(result.is_err());
+        assert_eq!(handled, 0);
+        // Once the events run out.
+        event_loop.inject_device_event(
+            DeviceId(0),
+            DeviceEvent::Key {
+                key: Key::A,
+                scancode: 0x1E,
+                pressed: true,
+            },
+        );
+        let result = event_loop.run(|_, _| handled += 1);
+        assert!(result.is_err());
+        assert_eq!(handled, 2);
+        // Polling goes on regardless, once the loop got going.
+        event_loop.inject_device_event(
+            DeviceId(0),
+            DeviceEvent::MouseMotion {
+                delta_x: 1,
+                delta_y: 0,
+            },
+        );
+        let mut rounds = 0;
+        let result = event_loop.run(|_, control_flow| {
+            rounds += 1;
+            *control_flow = match rounds {
+                3 => ControlFlow::Exit(0),
+                _ => ControlFlow::Poll,
+            };
+        });
+        assert_eq!(result, Ok(0));
+    }
+
+    #[test]
+    fn presented_frames_are_captured() {
+        let event_loop = EventLoop::new().unwrap();
+        let mut window = Window::new(&event_loop, "test", None, [2, 2], ()).unwrap();
+        let window_id = window.id();
+        assert!(event_loop.presented_frames(window_id).is_empty());
+        let mut first = Framebuffer::new(2, 2);
+        first.set_pixel(1, 0, Rgba8::new(255, 0, 0, 255));
+        window.present(&first).unwrap();
+        let mut second = first.clone();
+        second.set_pixel(0, 1, Rgba8::new(0, 0, 255, 255));
+        window.present(&second).unwrap();
+        assert_eq!(
+            event_loop.presented_frames(window_id),
+            [first.clone(), second.clone()]
+        );
+        // The frames outlive the window, which can't be presented to anymore.
+        window.destroy().unwrap();
+        assert!(window.present(&first).is_err());
+        assert_eq!(event_loop.presented_frames(window_id), [first, second]);
+        assert!(event_loop.presented_frames(WindowId(0)).is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+//! Monitors that only exist in memory, set up by the caller.
+
+use super::{event_loop::Backend, HeadlessError};
+use crate::{
+    dpi::{Dpi, PhysicalSize},
+    monitor::{MonitorInfo, VideoMode},
+};
+use std::rc::Rc;
+
+/// The monitor every headless event loop starts with: a primary 1920x1080 monitor at 60Hz and
+/// 96 DPI, which can also be switched to 1280x720.
+pub fn default_monitor() -> MonitorInfo {
+    let mode = |width, height| VideoMode {
+        size: PhysicalSize::new(width, height),
+        bits_per_pixel: 32,
+        refresh_rate: 60,
+    };
+    MonitorInfo {
+        name: "HEADLESS1".to_owned(),
+        position: [0, 0],
+        size: PhysicalSize::new(1920, 1080),
+        refresh_rate: 60,
+        dpi: Dpi::BASE,
+        is_primary: true,
+        video_modes: vec![mode(1920, 1080), mode(1280, 720)],
+    }
+}
+
+/// A fake monitor, at a place in the list of its event loop.
+///
+/// See [`EventLoop::set_monitors`](super::event_loop::EventLoop::set_monitors).
+#[derive(Debug, Clone)]
+pub struct Monitor {
+    backend: Rc<Backend>,
+    index: usize,
+}
+
+impl Monitor {
+    pub(crate) fn new(backend: Rc<Backend>, index: usize) -> Self {
+        Self { backend, index }
+    }
+
+    /// Everything the monitor reports about itself, or an error once it is gone from the list.
+    pub fn info(&self) -> Result<MonitorInfo, HeadlessError> {
+        self.with_info(MonitorInfo::clone)
+    }
+
+    /// See [`MonitorInfo::name`].
+    pub fn name(&self) -> Result<String, HeadlessError> {
+        self.with_info(|info| info.name.clone())
+    }
+
+    /// See [`MonitorInfo::position`].
+    pub fn position(&self) -> Result<[i32; 2], HeadlessError> {
+        self.with_info(|info| info.position)
+    }
+
+    /// See [`MonitorInfo::size`].
+    pub fn size(&self) -> Result<PhysicalSize, HeadlessError> {
+        self.with_info(|info| info.size)
+    }
+
+    /// See [`MonitorInfo::is_primary`].
+    pub fn is_primary(&self) -> Result<bool, HeadlessError> {
+        self.with_info(|info| info.is_primary)
+    }
+
+    /// See [`MonitorInfo::dpi`], [`Dpi::BASE`] once the monitor is gone.
+    pub fn dpi(&self) -> Dpi {
+        self.with_info(|info| info.dpi).unwrap_or(Dpi::BASE)
+    }
+
+    /// How many physical pixels make a logical pixel on this monitor.
+    pub fn scale_factor(&self) -> f64 {
+        self.dpi().scale_factor()
+    }
+
+    /// See [`MonitorInfo::refresh_rate`].
+    pub fn refresh_rate(&self) -> Result<u32, HeadlessError> {
+        self.with_info(|info| info.refresh_rate)
+    }
+
+    /// See [`MonitorInfo::video_modes`].
+    pub fn video_modes(&self) -> Result<Vec<VideoMode>, HeadlessError> {
+        self.with_info(|info| info.video_modes.clone())
+    }
+
+    fn with_info<R>(&self, f: impl FnOnce(&MonitorInfo) -> R) -> Result<R, HeadlessError> {
+        let monitors = self.backend.monitors.borrow();
+        monitors
+            .get(self.index)
+            .map(f)
+            .ok_or(HeadlessError("the monitor is gone"))
+    }
+}
+
+impl PartialEq for Monitor {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.backend, &other.backend) && self.index == other.index
+    }
+}
+impl Eq for Monitor {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::WindowEvent,
+        headless::{event_loop::EventLoop, window::Window},
+    };
+
+    fn monitor_at(name: &str, position: [i32; 2], is_primary: bool) -> MonitorInfo {
+        MonitorInfo {
+            name: name.to_owned(),
+            position,
+            size: PhysicalSize::new(1280, 1024),
+            refresh_rate: 75,
+            dpi: Dpi(144),
+            is_primary,
+            video_modes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn starts_with_the_default_monitor() {
+        let event_loop = EventLoop::new().unwrap();
+        let monitors = event_loop.available_monitors();
+        assert_eq!(monitors.len(), 1);
+        let primary = event_loop.primary_monitor().unwrap();
+        assert_eq!(primary, monitors[0]);
+        assert_eq!(primary.info().unwrap(), default_monitor());
+        assert_eq!(primary.position().unwrap(), [0, 0]);
+        assert_eq!(primary.size().unwrap(), PhysicalSize::new(1920, 1080));
+        assert_eq!(primary.refresh_rate().unwrap(), 60);
+        assert_eq!(primary.scale_factor(), 1.0);
+        let sizes: Vec<_> = primary
+            .video_modes()
+            .unwrap()
+            .iter()
+            .map(|mode| mode.size)
+            .collect();
+        assert_eq!(
+            sizes,
+            [PhysicalSize::new(1920, 1080), PhysicalSize::new(1280, 720)]
+        );
+    }
+
+    #[test]
+    fn set_monitors_replaces_the_list() {
+        let event_loop = EventLoop::new().unwrap();
+        let old = event_loop.available_monitors();
+        event_loop.set_monitors(vec![
+            monitor_at("LEFT", [-1280, 0], false),
+            monitor_at("MIDDLE", [0, 0], true),
+            monitor_at("RIGHT", [1280, 0], true),
+        ]);
+        let monitors = event_loop.available_monitors();
+        let names: Vec<_> = monitors
+            .iter()
+            .map(|monitor| monitor.name().unwrap())
+            .collect();
+        assert_eq!(names, ["LEFT", "MIDDLE", "RIGHT"]);
+        // The old handle now refers to whatever is first.
+        assert_eq!(old[0].name().unwrap(), "LEFT");
+        assert_eq!(old[0].dpi(), Dpi(144));
+        // The first primary monitor wins.
+        let primary = event_loop.primary_monitor().unwrap();
+        assert_eq!(primary, monitors[1]);
+        // Handles past the end of the list are gone.
+        event_loop.set_monitors(vec![monitor_at("ONLY", [0, 0], false)]);
+        assert!(event_loop.primary_monitor().is_none());
+        assert!(monitors[1].info().is_err());
+        assert_eq!(monitors[1].dpi(), Dpi::BASE);
+        event_loop.set_monitors(Vec::new());
+        assert!(event_loop.available_monitors().is_empty());
+    }
+
+    #[test]
+    fn windows_are_on_the_monitor_of_their_corner() {
+        let event_loop = EventLoop::new().unwrap();
+        event_loop.set_monitors(vec![
+            monitor_at("LEFT", [-1280, 0], false),
+            monitor_at("MIDDLE", [0, 0], true),
+        ]);
+        let monitors = event_loop.available_monitors();
+        let window = Window::new(&event_loop, "test", Some([-1, 0]), [100, 100], ()).unwrap();
+        assert_eq!(window.current_monitor(), Some(monitors[0].clone()));
+        window.inject(WindowEvent::Moved { x: 0, y: 0 });
+        assert_eq!(window.current_monitor(), Some(monitors[1].clone()));
+        // Off every monitor, the primary one.
+        window.inject(WindowEvent::Moved { x: 0, y: 1024 });
+        assert_eq!(window.current_monitor(), Some(monitors[1].clone()));
+    }
+}
@@ -0,0 +1,159 @@
+//! A window that only exists in memory.
+
+use super::{
+    event_loop::{Backend, EventLoop, WindowRecord},
+    monitor::Monitor,
+    HeadlessError,
+};
+use crate::{
+    dpi::{Dpi, PhysicalSize},
+    event::{WindowEvent, WindowId},
+    raster::Framebuffer,
+};
+use std::rc::Rc;
+
+/// A fake window owning a state of type `S`.
+///
+/// * The state lives as long as the window does, and is dropped when the window is destroyed.
+/// * Dropping the `Window` destroys the window if that didn't happen already.
+pub struct Window<S: 'static> {
+    id: WindowId,
+    backend: Rc<Backend>,
+    state: Option<S>,
+}
+
+impl<S: 'static> Window<S> {
+    /// Creates a window handled by `event_loop`, moving `state` into it.
+    ///
+    /// * Queues [`WindowEvent::Created`] then [`WindowEvent::Resized`], like the native backends.
+    /// * The window is not initially shown, see [`Window::show`].
+    pub fn new(
+        event_loop: &EventLoop,
+        window_name: &str,
+        coordinates: Option<[i32; 2]>,
+        size: [i32; 2],
+        state: S,
+    ) -> Result<Self, HeadlessError> {
+        let backend = Rc::clone(event_loop.backend());
+        let [width, height] = [size[0].max(0) as u32, size[1].max(0) as u32];
+        let id = backend.create_window(WindowRecord {
+            title: window_name.to_owned(),
+            position: coordinates.unwrap_or([0, 0]),
+            size: [width, height],
+            dpi: Dpi::BASE,
+            visible: false,
+            alive: true,
+            frames: Vec::new(),
+        });
+        backend.push_event(id, WindowEvent::Created);
+        backend.push_event(
+            id,
+            WindowEvent::Resized {
+                size: PhysicalSize::new(width, height),
+                dpi: Dpi::BASE,
+            },
+        );
+        Ok(Self {
+            id,
+            backend,
+            state: Some(state),
+        })
+    }
+
+    /// The identifier used for this window in the events.
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
+    /// Whether the window still exists.
+    pub fn is_alive(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Shows the window, queuing a [`WindowEvent::Paint`] the first time.
+    ///
+    /// **Returns:** Whether the window was previously visible.
+    pub fn show(&self) -> bool {
+        if !self.is_alive() {
+            return false;
+        }
+        let was_visible = self.with_record(|record| core::mem::replace(&mut record.visible, true));
+        if !was_visible {
+            self.backend.push_event(self.id, WindowEvent::Paint);
+        }
+        was_visible
+    }
+
+    /// The size of the window, in physical pixels, as set on creation or by the last injected
+    /// [`WindowEvent::Resized`].
+    pub fn client_size(&self) -> Result<[u32; 2], HeadlessError> {
+        Ok(self.with_record(|record| record.size))
+    }
+
+    /// The DPI of the window, [`Dpi::BASE`] unless changed by an injected
+    /// [`WindowEvent::DpiChanged`] or [`WindowEvent::Resized`].
+    pub fn dpi(&self) -> Dpi {
+        self.with_record(|record| record.dpi)
+    }
+
+    /// The fake monitor the top left corner of the window is on, or else the primary one.
+    pub fn current_monitor(&self) -> Option<Monitor> {
+        let position = self.with_record(|record| record.position);
+        let monitors = self.backend.monitors.borrow();
+        let index = monitors
+            .iter()
+            .position(|monitor| monitor.contains(position))
+            .or_else(|| monitors.iter().position(|monitor| monitor.is_primary))?;
+        Some(Monitor::new(Rc::clone(&self.backend), index))
+    }
+
+    /// Keeps a copy of `framebuffer`, see [`EventLoop::presented_frames`].
+    pub fn present(&self, framebuffer: &Framebuffer) -> Result<(), HeadlessError> {
+        if !self.is_alive() {
+            return Err(HeadlessError("presenting to a destroyed window"));
+        }
+        self.with_record(|record| record.frames.push(framebuffer.clone()));
+        Ok(())
+    }
+
+    /// Queues `event` as if the window system had sent it to this window.
+    ///
+    /// See [`EventLoop::inject`].
+    pub fn inject(&self, event: WindowEvent) {
+        self.backend.push_event(self.id, event);
+    }
+
+    /// The state of the window, if it still exists.
+    pub fn state(&self) -> Option<&S> {
+        self.state.as_ref()
+    }
+
+    /// The state of the window, if it still exists.
+    pub fn state_mut(&mut self) -> Option<&mut S> {
+        self.state.as_mut()
+    }
+
+    /// Destroys the window, dropping its state and queuing a [`WindowEvent::Destroyed`].
+    ///
+    /// Does nothing if the window is already gone.
+    pub fn destroy(&mut self) -> Result<(), HeadlessError> {
+        if self.state.take().is_some() {
+            self.with_record(|record| record.alive = false);
+            self.backend.push_event(self.id, WindowEvent::Destroyed);
+        }
+        Ok(())
+    }
+
+    fn with_record<R>(&self, f: impl FnOnce(&mut WindowRecord) -> R) -> R {
+        let mut windows = self.backend.windows.borrow_mut();
+        // Records are never removed.
+        f(windows.get_mut(&self.id).expect("unknown headless window"))
+    }
+}
+
+impl<S: 'static> Drop for Window<S> {
+    fn drop(&mut self) {
+        // Call `destroy` first to find out whether it failed.
+        let _ = self.destroy();
+    }
+}
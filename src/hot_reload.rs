@@ -0,0 +1,317 @@
+//! Game code reloaded while the game runs, the way Handmade Hero does it.
+//!
+//! The game is built as a `cdylib` exporting the functions of the [`game`](crate::game) interface.
+//! Rather than the library itself, a copy of it is loaded: the linker can then overwrite the
+//! original (Windows locks loaded DLLs), and every load has a new path (`dlopen` hands back the
+//! library already loaded for a path). When the original changes, the new copy is loaded and its
+//! functions replace the old ones, between two frames. The [`GameMemory`] stays with the platform,
+//! so the game picks up where it was.
+
+use crate::{event::Event, game::*, raster::Framebuffer};
+use core::{ffi::c_void, mem::ManuallyDrop, time::Duration};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+#[cfg(unix)]
+use crate::posix::{library::Library, DlError as LibraryError};
+#[cfg(windows)]
+use crate::win32::{library::Library, safe::Win32Error as LibraryError};
+
+/// Why the game code could not be loaded.
+#[derive(Debug)]
+pub enum HotReloadError {
+    /// The library could not be read or copied.
+    Io(std::io::Error),
+    /// The copy could not be loaded.
+    Library(LibraryError),
+    /// The library does not export this function.
+    MissingSymbol(&'static str, LibraryError),
+    /// The library was built for another [`GAME_API_VERSION`].
+    ApiVersion(u32),
+}
+impl core::fmt::Display for HotReloadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "HotReloadError(io: {})", e),
+            Self::Library(e) => write!(f, "HotReloadError(library: {})", e),
+            Self::MissingSymbol(name, e) => write!(f, "HotReloadError(missing {}: {})", name, e),
+            Self::ApiVersion(version) => write!(
+                f,
+                "HotReloadError(api version {}, expected {})",
+                version, GAME_API_VERSION
+            ),
+        }
+    }
+}
+impl std::error::Error for HotReloadError {}
+
+/// The functions of one load of the game library.
+struct GameCode {
+    update: GameUpdateFn,
+    render: GameRenderFn,
+    /// Unloaded before the copy is deleted, see the `Drop` implementation.
+    library: ManuallyDrop<Library>,
+    /// The copy the library was loaded from, deleted when unloaded.
+    copy_path: PathBuf,
+}
+
+impl GameCode {
+    /// Loads the copy at `copy_path` and looks up the game functions.
+    fn load(copy_path: PathBuf) -> Result<Self, HotReloadError> {
+        let library = Library::load(&copy_path).map_err(HotReloadError::Library)?;
+        let symbol = |name: &'static str| {
+            library
+                .symbol(name)
+                .map_err(|e| HotReloadError::MissingSymbol(name, e))
+        };
+        // Safety: the symbols are the ones of the `game` interface, with its signatures.
+        let api_version = unsafe {
+            core::mem::transmute::<*mut c_void, GameApiVersionFn>(symbol(GAME_API_VERSION_SYMBOL)?)
+        };
+        let version = api_version();
+        if version != GAME_API_VERSION {
+            return Err(HotReloadError::ApiVersion(version));
+        }
+        let update = unsafe {
+            core::mem::transmute::<*mut c_void, GameUpdateFn>(symbol(GAME_UPDATE_SYMBOL)?)
+        };
+        let render = unsafe {
+            core::mem::transmute::<*mut c_void, GameRenderFn>(symbol(GAME_RENDER_SYMBOL)?)
+        };
+        Ok(Self {
+            update,
+            render,
+            library: ManuallyDrop::new(library),
+            copy_path,
+        })
+    }
+}
+
+impl Drop for GameCode {
+    fn drop(&mut self) {
+        // Windows won't delete a loaded DLL.
+        unsafe { ManuallyDrop::drop(&mut self.library) };
+        let _ = fs::remove_file(&self.copy_path);
+    }
+}
+
+/// The game library, reloaded whenever it changes on disk.
+pub struct HotReload {
+    source_path: PathBuf,
+    /// When the source was last modified, as of the current load.
+    last_write_time: SystemTime,
+    code: GameCode,
+    reload_count: u32,
+}
+
+impl HotReload {
+    /// Loads the game library at `source_path`.
+    pub fn load<P: Into<PathBuf>>(source_path: P) -> Result<Self, HotReloadError> {
+        let source_path = source_path.into();
+        let (last_write_time, code) = Self::load_copy(&source_path, 0)?;
+        Ok(Self {
+            source_path,
+            last_write_time,
+            code,
+            reload_count: 0,
+        })
+    }
+
+    /// Copies the library at `source_path` next to it and loads the copy.
+    fn load_copy(source_path: &Path, index: u32) -> Result<(SystemTime, GameCode), HotReloadError> {
+        let last_write_time = Self::write_time(source_path)?;
+        let copy_path = Self::copy_path(source_path, index);
+        fs::copy(source_path, &copy_path).map_err(HotReloadError::Io)?;
+        match GameCode::load(copy_path.clone()) {
+            Ok(code) => Ok((last_write_time, code)),
+            Err(e) => {
+                let _ = fs::remove_file(&copy_path);
+                Err(e)
+            }
+        }
+    }
+
+    /// Where to copy the library at `source_path` for its `index`th load, `<stem>_hot_<index>`
+    /// with the same extension.
+    fn copy_path(source_path: &Path, index: u32) -> PathBuf {
+        let mut copy_name = source_path.file_stem().unwrap_or_default().to_owned();
+        copy_name.push(format!("_hot_{}", index));
+        // Not `set_extension`, which would take a dotted stem's `.so_hot_1` for the extension.
+        if let Some(extension) = source_path.extension() {
+            copy_name.push(".");
+            copy_name.push(extension);
+        }
+        source_path.with_file_name(copy_name)
+    }
+
+    fn write_time(path: &Path) -> Result<SystemTime, HotReloadError> {
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(HotReloadError::Io)
+    }
+
+    /// The library being watched.
+    pub fn source_path(&self) -> &Path {
+        &self.source_path
+    }
+
+    /// How many times the library was reloaded.
+    pub fn reload_count(&self) -> u32 {
+        self.reload_count
+    }
+
+    /// Reloads the library if it changed since it was last loaded. To call between frames.
+    ///
+    /// When the new library can't be loaded (e.g. the linker is still writing it), the current
+    /// code is kept and the load is tried again on the next call.
+    ///
+    /// **Returns:** Whether the code was swapped.
+    pub fn reload_if_changed(&mut self) -> Result<bool, HotReloadError> {
+        if Self::write_time(&self.source_path)? == self.last_write_time {
+            return Ok(false);
+        }
+        let (last_write_time, code) = Self::load_copy(&self.source_path, self.reload_count + 1)?;
+        self.last_write_time = last_write_time;
+        self.code = code;
+        self.reload_count += 1;
+        Ok(true)
+    }
+
+    /// Calls the game's [`GameUpdateFn`].
+    pub fn update(&self, memory: &mut GameMemory, events: &[Event], dt: Duration) {
+        (self.code.update)(memory, events, dt)
+    }
+
+    /// Calls the game's [`GameRenderFn`].
+    pub fn render(&self, memory: &mut GameMemory, framebuffer: &mut Framebuffer, alpha: f32) {
+        (self.code.render)(memory, framebuffer, alpha)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{process::Command, time::Duration};
+
+    /// A directory of its own for the test `name`.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("hot_reload_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Builds a library exporting the game functions at `path`, reporting `api_version`.
+    ///
+    /// The functions do nothing and are never called, only the version is.
+    fn build_game(path: &Path, api_version: u32) {
+        let source = path.with_extension("rs");
+        let code = format!(
+            "#[no_mangle] pub extern \"C\" fn {}() -> u32 {{ {} }}\n\
+             #[no_mangle] pub fn {}() {{}}\n\
+             #[no_mangle] pub fn {}() {{}}\n",
+            GAME_API_VERSION_SYMBOL, api_version, GAME_UPDATE_SYMBOL, GAME_RENDER_SYMBOL
+        );
+        fs::write(&source, code).unwrap();
+        let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+        let status = Command::new(rustc)
+            .args(["--crate-type", "cdylib", "--crate-name", "game", "-o"])
+            .arg(path)
+            .arg(&source)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    /// Makes `path` look modified a minute after `write_time`, whatever the file system's
+    /// resolution.
+    fn touch_after(path: &Path, write_time: SystemTime) {
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(write_time + Duration::from_secs(60))
+            .unwrap();
+    }
+
+    fn library_path(dir: &Path) -> PathBuf {
+        dir.join("game")
+            .with_extension(std::env::consts::DLL_EXTENSION)
+    }
+
+    #[test]
+    fn copies_are_named_after_the_library() {
+        let copy = HotReload::copy_path(Path::new("target/debug/libgame.so"), 3);
+        assert_eq!(copy, Path::new("target/debug/libgame_hot_3.so"));
+        let copy = HotReload::copy_path(Path::new("game.dll"), 0);
+        assert_eq!(copy, Path::new("game_hot_0.dll"));
+        let copy = HotReload::copy_path(Path::new("bin/game"), 12);
+        assert_eq!(copy, Path::new("bin/game_hot_12"));
+        // Only the last extension is kept apart.
+        let copy = HotReload::copy_path(Path::new("libgame.so.1"), 1);
+        assert_eq!(copy, Path::new("libgame.so_hot_1.1"));
+    }
+
+    #[test]
+    fn not_a_library_fails_to_load() {
+        let dir = temp_dir("not_a_library");
+        let source = library_path(&dir);
+        fs::write(&source, b"not a library").unwrap();
+        let result = HotReload::load(&source);
+        assert!(matches!(result, Err(HotReloadError::Library(_))));
+        // The copy doesn't stay behind.
+        assert!(!HotReload::copy_path(&source, 0).exists());
+        assert!(matches!(
+            HotReload::load(dir.join("missing")),
+            Err(HotReloadError::Io(_))
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn libraries_of_another_api_version_are_rejected() {
+        let dir = temp_dir("api_version");
+        let source = library_path(&dir);
+        build_game(&source, GAME_API_VERSION + 1);
+        let result = HotReload::load(&source);
+        assert!(
+            matches!(result, Err(HotReloadError::ApiVersion(version)) if version == GAME_API_VERSION + 1)
+        );
+        assert!(!HotReload::copy_path(&source, 0).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_reloads_keep_the_current_code() {
+        let dir = temp_dir("failed_reload");
+        let source = library_path(&dir);
+        build_game(&source, GAME_API_VERSION);
+        let mut hot_reload = HotReload::load(&source).unwrap();
+        let first_copy = HotReload::copy_path(&source, 0);
+        assert!(first_copy.exists());
+        assert!(!hot_reload.reload_if_changed().unwrap());
+
+        // Half written by the linker.
+        let write_time = HotReload::write_time(&source).unwrap();
+        fs::write(&source, b"not a library yet").unwrap();
+        touch_after(&source, write_time);
+        assert!(matches!(
+            hot_reload.reload_if_changed(),
+            Err(HotReloadError::Library(_))
+        ));
+        assert_eq!(hot_reload.reload_count(), 0);
+        assert!(first_copy.exists());
+        assert!(!HotReload::copy_path(&source, 1).exists());
+
+        // Tried again once the linker is done.
+        build_game(&source, GAME_API_VERSION);
+        touch_after(&source, write_time + Duration::from_secs(60));
+        assert!(hot_reload.reload_if_changed().unwrap());
+        assert_eq!(hot_reload.reload_count(), 1);
+        assert!(!first_copy.exists());
+        assert!(HotReload::copy_path(&source, 1).exists());
+        assert!(!hot_reload.reload_if_changed().unwrap());
+        drop(hot_reload);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,2 +1,20 @@
-#[cfg(windows)]
-pub mod win32;
\ No newline at end of file
+pub mod audio;
+pub mod dpi;
+pub mod event;
+pub mod game;
+pub mod game_loop;
+pub mod gamepad;
+pub mod gl;
+pub mod headless;
+#[cfg(any(windows, unix))]
+pub mod hot_reload;
+pub mod memory;
+pub mod monitor;
+#[cfg(any(windows, all(unix, not(target_os = "macos"))))]
+pub mod platform;
+pub mod posix;
+pub mod raster;
+pub mod replay;
+pub mod timing;
+pub mod win32;
+pub mod x11;
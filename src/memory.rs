@@ -0,0 +1,373 @@
+//! Memory allocated once up-front, then handed out by bump allocation, the way Handmade Hero does.
+//!
+//! The platform allocates all the memory the game will ever use in one [`GameMemoryBlock`], at a
+//! fixed address when asked to. Every pointer into it then stays valid from one run to the next,
+//! so the block can be saved and restored as is (e.g. to replay a recording). The game carves its
+//! data out of [`MemoryArena`]s, and frees everything at once.
+
+use core::{
+    mem::{align_of, size_of},
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+#[cfg(any(windows, unix))]
+use crate::game::GameMemory;
+#[cfg(unix)]
+pub use crate::posix::{memory::VirtualMemory, Errno as MemoryError};
+#[cfg(windows)]
+pub use crate::win32::{memory::VirtualMemory, safe::Win32Error as MemoryError};
+
+/// Where to put the game memory for it to be at the same address every run, 2TiB, far from
+/// where the system puts anything else.
+#[cfg(target_pointer_width = "64")]
+pub const FIXED_BASE_ADDRESS: usize = 2 << 40;
+
+/// Rounds `value` up to a multiple of `alignment`, a power of two.
+pub const fn align_up(value: usize, alignment: usize) -> usize {
+    (value + alignment - 1) & !(alignment - 1)
+}
+
+/// A bump allocator over a block of memory it doesn't own.
+///
+/// Allocations are never freed one by one: the whole arena is cleared at once, or everything
+/// allocated within a [`TemporaryMemory`] scope is dropped at the end of it.
+#[repr(C)]
+#[derive(Debug)]
+pub struct MemoryArena {
+    base: *mut u8,
+    size: usize,
+    used: usize,
+    /// How many [`TemporaryMemory`] scopes are open.
+    temporary_count: u32,
+}
+
+impl MemoryArena {
+    /// An arena over `size` bytes from `base`.
+    ///
+    /// ## Safety
+    /// * The block must be valid for reads and writes of `size` bytes.
+    /// * It must outlive the arena, and not be accessed but through it.
+    pub unsafe fn from_raw_parts(base: *mut u8, size: usize) -> Self {
+        Self {
+            base,
+            size,
+            used: 0,
+            temporary_count: 0,
+        }
+    }
+
+    /// The start of the block.
+    pub fn base(&self) -> *mut u8 {
+        self.base
+    }
+
+    /// The size of the block, in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// How many bytes are allocated, counting padding.
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    /// How many bytes are left, ignoring alignment.
+    pub fn remaining(&self) -> usize {
+        self.size - self.used
+    }
+
+    /// The allocated bytes.
+    pub fn used_bytes(&self) -> &[u8] {
+        if self.used == 0 {
+            return &[];
+        }
+        unsafe { core::slice::from_raw_parts(self.base, self.used) }
+    }
+
+    /// Allocates `size` bytes aligned to `alignment`, a power of two. The bytes are left as the
+    /// last allocation there left them.
+    ///
+    /// **Returns:** `None` when the arena is full.
+    pub fn alloc(&mut self, size: usize, alignment: usize) -> Option<NonNull<u8>> {
+        assert!(
+            alignment.is_power_of_two(),
+            "alignment must be a power of two"
+        );
+        let address = self.base as usize + self.used;
+        let padding = align_up(address, alignment) - address;
+        let end = self.used.checked_add(padding)?.checked_add(size)?;
+        if end > self.size {
+            return None;
+        }
+        let allocation = unsafe { self.base.add(self.used + padding) };
+        self.used = end;
+        NonNull::new(allocation)
+    }
+
+    /// Allocates `size` zeroed bytes aligned to `alignment`, a power of two.
+    ///
+    /// **Returns:** `None` when the arena is full.
+    pub fn alloc_zeroed(&mut self, size: usize, alignment: usize) -> Option<NonNull<u8>> {
+        let allocation = self.alloc(size, alignment)?;
+        unsafe { allocation.as_ptr().write_bytes(0, size) };
+        Some(allocation)
+    }
+
+    /// Moves `value` into the arena. It will never be dropped.
+    ///
+    /// **Returns:** `None` when the arena is full.
+    pub fn push<T>(&mut self, value: T) -> Option<NonNull<T>> {
+        let allocation = self.alloc(size_of::<T>(), align_of::<T>())?.cast::<T>();
+        unsafe { allocation.as_ptr().write(value) };
+        Some(allocation)
+    }
+
+    /// Allocates `len` copies of `value`.
+    ///
+    /// **Returns:** `None` when the arena is full.
+    pub fn push_array<T: Copy>(&mut self, len: usize, value: T) -> Option<NonNull<[T]>> {
+        let size = size_of::<T>().checked_mul(len)?;
+        let allocation = self.alloc(size, align_of::<T>())?.cast::<T>();
+        for i in 0..len {
+            unsafe { allocation.as_ptr().add(i).write(value) };
+        }
+        Some(NonNull::slice_from_raw_parts(allocation, len))
+    }
+
+    /// Allocates an arena of `size` bytes within this one, e.g. for a subsystem to clear on its
+    /// own.
+    ///
+    /// **Returns:** `None` when the arena is full.
+    pub fn sub_arena(&mut self, size: usize, alignment: usize) -> Option<MemoryArena> {
+        let base = self.alloc(size, alignment)?;
+        Some(unsafe { Self::from_raw_parts(base.as_ptr(), size) })
+    }
+
+    /// Opens a scope, at the end of which everything allocated in it is freed.
+    pub fn begin_temporary(&mut self) -> TemporaryMemory<'_> {
+        self.temporary_count += 1;
+        TemporaryMemory {
+            used: self.used,
+            arena: self,
+        }
+    }
+
+    /// Frees everything, invalidating every allocation.
+    pub fn clear(&mut self) {
+        self.check();
+        self.used = 0;
+    }
+
+    /// Restores the allocations of a snapshot, whose bytes were put back in the block.
+    pub(crate) fn set_used(&mut self, used: usize) {
+        assert!(used <= self.size, "more memory used than the arena has");
+        self.check();
+        self.used = used;
+    }
+
+    /// Panics if a [`TemporaryMemory`] scope is still open, e.g. to call at the end of a frame.
+    pub fn check(&self) {
+        assert_eq!(self.temporary_count, 0, "a temporary memory scope is open");
+    }
+}
+
+/// A scope of a [`MemoryArena`], freeing what was allocated in it when dropped.
+///
+/// Derefs to the arena, to allocate through it.
+pub struct TemporaryMemory<'a> {
+    arena: &'a mut MemoryArena,
+    /// How much of the arena was used when the scope was opened.
+    used: usize,
+}
+
+impl Deref for TemporaryMemory<'_> {
+    type Target = MemoryArena;
+
+    fn deref(&self) -> &MemoryArena {
+        self.arena
+    }
+}
+
+impl DerefMut for TemporaryMemory<'_> {
+    fn deref_mut(&mut self) -> &mut MemoryArena {
+        self.arena
+    }
+}
+
+impl Drop for TemporaryMemory<'_> {
+    fn drop(&mut self) {
+        debug_assert!(self.arena.used >= self.used);
+        self.arena.used = self.used;
+        self.arena.temporary_count -= 1;
+    }
+}
+
+/// All the memory of the game, in one block: the permanent storage, then the transient storage.
+#[cfg(any(windows, unix))]
+pub struct GameMemoryBlock {
+    /// Points into `block`, declared first to go before it.
+    memory: GameMemory,
+    block: VirtualMemory,
+}
+
+#[cfg(any(windows, unix))]
+impl GameMemoryBlock {
+    /// Allocates the storages, at `base_address` if there is one, e.g. [`FIXED_BASE_ADDRESS`].
+    pub fn new(
+        base_address: Option<usize>,
+        permanent_storage_size: usize,
+        transient_storage_size: usize,
+    ) -> Result<Self, MemoryError> {
+        let block = VirtualMemory::allocate(
+            base_address,
+            permanent_storage_size + transient_storage_size,
+        )?;
+        // Safety: the block is zeroed, and only ever accessed through the `GameMemory` it's kept
+        // with.
+        let memory = unsafe {
+            GameMemory::from_raw_parts(
+                block.as_ptr(),
+                permanent_storage_size,
+                block.as_ptr().add(permanent_storage_size),
+                transient_storage_size,
+            )
+        };
+        Ok(Self { memory, block })
+    }
+
+    /// Where the block starts.
+    pub fn base_address(&self) -> usize {
+        self.block.as_ptr() as usize
+    }
+
+    /// The memory, to hand to the game.
+    pub fn memory(&mut self) -> &mut GameMemory {
+        &mut self.memory
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A block aligned enough for every test.
+    #[repr(C, align(64))]
+    struct Block([u8; 256]);
+
+    fn arena_over(block: &mut Block) -> MemoryArena {
+        unsafe { MemoryArena::from_raw_parts(block.0.as_mut_ptr(), block.0.len()) }
+    }
+
+    #[test]
+    fn allocations_are_padded_to_their_alignment() {
+        let mut block = Block([0; 256]);
+        let mut arena = arena_over(&mut block);
+        let base = arena.base() as usize;
+        let first = arena.alloc(1, 1).unwrap();
+        assert_eq!(first.as_ptr() as usize, base);
+        let second = arena.alloc(8, 8).unwrap();
+        assert_eq!(second.as_ptr() as usize, base + 8);
+        assert_eq!(arena.used(), 16);
+        let third = arena.alloc(2, 64).unwrap();
+        assert_eq!(third.as_ptr() as usize, base + 64);
+        assert_eq!(arena.used(), 66);
+        assert_eq!(arena.remaining(), 190);
+        // Already aligned, no padding.
+        arena.alloc(2, 2).unwrap();
+        assert_eq!(arena.used(), 68);
+        let value = arena.push(0x0102_0304_u32).unwrap();
+        assert_eq!(value.as_ptr() as usize % align_of::<u32>(), 0);
+        assert_eq!(unsafe { *value.as_ptr() }, 0x0102_0304);
+        let array = arena.push_array(3, 7_u64).unwrap();
+        assert_eq!(array.as_ptr() as *mut u64 as usize, base + 72);
+        assert_eq!(unsafe { array.as_ref() }, [7, 7, 7]);
+    }
+
+    #[test]
+    #[should_panic(expected = "power of two")]
+    fn alignments_are_powers_of_two() {
+        let mut block = Block([0; 256]);
+        arena_over(&mut block).alloc(1, 3);
+    }
+
+    #[test]
+    fn full_arenas_give_none() {
+        let mut block = Block([0xAA; 256]);
+        let mut arena = arena_over(&mut block);
+        assert!(arena.alloc(257, 1).is_none());
+        assert_eq!(arena.used(), 0);
+        arena.alloc(255, 1).unwrap();
+        // Room for a byte, but not once aligned.
+        assert!(arena.alloc(1, 2).is_none());
+        assert_eq!(arena.used(), 255);
+        let last = arena.alloc_zeroed(1, 1).unwrap();
+        assert_eq!(unsafe { *last.as_ptr() }, 0);
+        assert_eq!(arena.remaining(), 0);
+        assert!(arena.alloc(0, 1).is_some());
+        assert!(arena.alloc(1, 1).is_none());
+        assert!(arena.push(0_u8).is_none());
+        assert!(arena.sub_arena(1, 1).is_none());
+    }
+
+    #[test]
+    fn huge_sizes_give_none_rather_than_overflowing() {
+        let mut block = Block([0; 256]);
+        let mut arena = arena_over(&mut block);
+        arena.alloc(1, 1).unwrap();
+        assert!(arena.alloc(usize::MAX, 1).is_none());
+        // Only overflowing once padded.
+        assert!(arena.alloc(usize::MAX - 2, 8).is_none());
+        assert!(arena.push_array(usize::MAX / 2, 0_u32).is_none());
+        assert_eq!(arena.used(), 1);
+    }
+
+    #[test]
+    fn temporary_scopes_roll_back() {
+        let mut block = Block([0; 256]);
+        let mut arena = arena_over(&mut block);
+        arena.alloc(10, 1).unwrap();
+        {
+            let mut temporary = arena.begin_temporary();
+            temporary.alloc(100, 1).unwrap();
+            {
+                let mut nested = temporary.begin_temporary();
+                nested.alloc(50, 1).unwrap();
+                assert_eq!(nested.used(), 160);
+            }
+            assert_eq!(temporary.used(), 110);
+            temporary.alloc(1, 64).unwrap();
+            assert_eq!(temporary.used(), 129);
+        }
+        assert_eq!(arena.used(), 10);
+        arena.check();
+        arena.clear();
+        assert_eq!(arena.used(), 0);
+        assert!(arena.used_bytes().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "a temporary memory scope is open")]
+    fn clear_panics_while_a_scope_is_open() {
+        let mut block = Block([0; 256]);
+        let mut arena = arena_over(&mut block);
+        let mut temporary = arena.begin_temporary();
+        temporary.alloc(1, 1).unwrap();
+        temporary.clear();
+    }
+
+    #[test]
+    fn sub_arenas_are_carved_out() {
+        let mut block = Block([0; 256]);
+        let mut arena = arena_over(&mut block);
+        arena.alloc(1, 1).unwrap();
+        let mut sub = arena.sub_arena(100, 16).unwrap();
+        assert_eq!(sub.base() as usize, arena.base() as usize + 16);
+        assert_eq!(sub.size(), 100);
+        assert_eq!(arena.used(), 116);
+        sub.alloc(100, 1).unwrap();
+        assert!(sub.alloc(1, 1).is_none());
+        sub.clear();
+        assert_eq!(arena.used(), 116);
+    }
+}
@@ -0,0 +1,103 @@
+//! Monitors and their display modes.
+
+use crate::dpi::{Dpi, PhysicalSize};
+
+/// A display mode of a monitor, what exclusive fullscreen switches to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VideoMode {
+    /// The resolution, in physical pixels.
+    pub size: PhysicalSize,
+    /// The color depth, 0 to keep the current one.
+    pub bits_per_pixel: u32,
+    /// In hertz, 0 to keep the current one.
+    pub refresh_rate: u32,
+}
+
+impl VideoMode {
+    /// A mode of `size`, at the current color depth and refresh rate.
+    pub const fn new(size: PhysicalSize) -> Self {
+        Self {
+            size,
+            bits_per_pixel: 0,
+            refresh_rate: 0,
+        }
+    }
+}
+
+/// Everything a monitor reports about itself, at one point in time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitorInfo {
+    /// The name of the display device, e.g. `\\.\DISPLAY1` on Windows.
+    pub name: String,
+    /// The top left corner, in virtual screen coordinates: the primary monitor is at `[0, 0]`.
+    pub position: [i32; 2],
+    /// The resolution, in physical pixels.
+    pub size: PhysicalSize,
+    /// In hertz, 0 when unknown.
+    pub refresh_rate: u32,
+    pub dpi: Dpi,
+    pub is_primary: bool,
+    /// The display modes the monitor can be switched to, without duplicates.
+    pub video_modes: Vec<VideoMode>,
+}
+
+impl MonitorInfo {
+    /// How many physical pixels make a logical pixel on this monitor.
+    pub fn scale_factor(&self) -> f64 {
+        self.dpi.scale_factor()
+    }
+
+    /// Whether the point, in virtual screen coordinates, is on this monitor.
+    pub fn contains(&self, [x, y]: [i32; 2]) -> bool {
+        let [left, top] = self.position;
+        x >= left
+            && y >= top
+            && i64::from(x) < i64::from(left) + i64::from(self.size.width)
+            && i64::from(y) < i64::from(top) + i64::from(self.size.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor_at(position: [i32; 2], width: u32, height: u32) -> MonitorInfo {
+        MonitorInfo {
+            name: String::new(),
+            position,
+            size: PhysicalSize::new(width, height),
+            refresh_rate: 0,
+            dpi: Dpi::BASE,
+            is_primary: false,
+            video_modes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn contains_includes_the_top_left_edges_only() {
+        let monitor = monitor_at([0, 0], 1920, 1080);
+        assert!(monitor.contains([0, 0]));
+        assert!(monitor.contains([1919, 1079]));
+        assert!(!monitor.contains([1920, 0]));
+        assert!(!monitor.contains([0, 1080]));
+        assert!(!monitor.contains([-1, 0]));
+        assert!(!monitor.contains([0, -1]));
+        // Left of and above the primary monitor.
+        let monitor = monitor_at([-1280, -1024], 1280, 1024);
+        assert!(monitor.contains([-1280, -1024]));
+        assert!(monitor.contains([-1, -1]));
+        assert!(!monitor.contains([0, -1]));
+        assert!(!monitor.contains([-1, 0]));
+        assert!(!monitor.contains([-1281, -1]));
+    }
+
+    #[test]
+    fn contains_does_not_overflow() {
+        let monitor = monitor_at([i32::MAX - 10, i32::MAX - 10], u32::MAX, u32::MAX);
+        assert!(monitor.contains([i32::MAX, i32::MAX]));
+        assert!(!monitor.contains([i32::MIN, i32::MAX]));
+        // An empty monitor contains nothing, not even its corner.
+        let monitor = monitor_at([0, 0], 0, 0);
+        assert!(!monitor.contains([0, 0]));
+    }
+}
@@ -0,0 +1,10 @@
+//! The windowing backend of the target platform, under common names.
+//!
+//! Every backend exposes the same `EventLoop` and `Window` surface, so code written against this
+//! module runs wherever one of them is available.
+
+#[cfg(windows)]
+pub use crate::win32::{event_loop::EventLoop, safe::Win32Error as Error, window::Window};
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub use crate::x11::{event_loop::EventLoop, window::Window, X11Error as Error};
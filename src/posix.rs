@@ -0,0 +1,117 @@
+#![allow(non_camel_case_types)]
+
+#[cfg(unix)]
+use std::os::raw::{c_char, c_void};
+/// The bits of the POSIX C library the platform layer needs, written by hand like the `win32`
+/// bindings. The C library itself is already linked in by `std`.
+///
+/// See the [POSIX specification](https://pubs.opengroup.org/onlinepubs/9699919799/)
+use std::os::raw::{c_int, c_long};
+
+#[cfg(unix)]
+pub mod clock;
+#[cfg(unix)]
+pub mod library;
+#[cfg(unix)]
+pub mod memory;
+
+pub type time_t = c_long;
+pub type off_t = i64;
+pub type size_t = usize;
+pub type clockid_t = c_int;
+
+/// See [`clock_gettime`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/clock_gettime.html)
+#[cfg(not(target_os = "macos"))]
+pub const CLOCK_MONOTONIC: clockid_t = 1;
+#[cfg(target_os = "macos")]
+pub const CLOCK_MONOTONIC: clockid_t = 6;
+
+/// See [`dlopen`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/dlopen.html)
+#[cfg(not(target_os = "macos"))]
+pub const RTLD_NOW: c_int = 0x2;
+#[cfg(not(target_os = "macos"))]
+pub const RTLD_LOCAL: c_int = 0;
+#[cfg(target_os = "macos")]
+pub const RTLD_NOW: c_int = 0x2;
+#[cfg(target_os = "macos")]
+pub const RTLD_LOCAL: c_int = 0x4;
+
+/// See [`mmap`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/mmap.html)
+pub const PROT_READ: c_int = 0x1;
+pub const PROT_WRITE: c_int = 0x2;
+pub const MAP_PRIVATE: c_int = 0x02;
+#[cfg(not(target_os = "macos"))]
+pub const MAP_ANONYMOUS: c_int = 0x20;
+#[cfg(target_os = "macos")]
+pub const MAP_ANONYMOUS: c_int = 0x1000;
+pub const MAP_FAILED: usize = !0;
+
+/// See [`<errno.h>`](https://pubs.opengroup.org/onlinepubs/9699919799/basedefs/errno.h.html)
+pub const EINTR: c_int = 4;
+
+/// See [`<time.h>`](https://pubs.opengroup.org/onlinepubs/9699919799/basedefs/time.h.html)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct timespec {
+    pub tv_sec: time_t,
+    pub tv_nsec: c_long,
+}
+
+#[cfg(unix)]
+extern "C" {
+    /// [`clock_gettime`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/clock_gettime.html)
+    pub fn clock_gettime(clock_id: clockid_t, tp: *mut timespec) -> c_int;
+
+    /// [`nanosleep`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/nanosleep.html)
+    pub fn nanosleep(rqtp: *const timespec, rmtp: *mut timespec) -> c_int;
+
+    /// [`mmap`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/mmap.html)
+    pub fn mmap(
+        addr: *mut c_void,
+        len: size_t,
+        prot: c_int,
+        flags: c_int,
+        fildes: c_int,
+        off: off_t,
+    ) -> *mut c_void;
+
+    /// [`munmap`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/munmap.html)
+    pub fn munmap(addr: *mut c_void, len: size_t) -> c_int;
+
+    /// [`dlopen`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/dlopen.html)
+    pub fn dlopen(file: *const c_char, mode: c_int) -> *mut c_void;
+
+    /// [`dlsym`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/dlsym.html)
+    pub fn dlsym(handle: *mut c_void, name: *const c_char) -> *mut c_void;
+
+    /// [`dlclose`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/dlclose.html)
+    pub fn dlclose(handle: *mut c_void) -> c_int;
+
+    /// [`dlerror`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/dlerror.html)
+    pub fn dlerror() -> *mut c_char;
+}
+
+/// An `errno` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Errno(pub c_int);
+impl core::fmt::Display for Errno {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Errno({})", self.0)
+    }
+}
+impl std::error::Error for Errno {}
+
+/// The `errno` of the calling thread.
+pub fn errno() -> Errno {
+    Errno(std::io::Error::last_os_error().raw_os_error().unwrap_or(0))
+}
+
+/// What `dlerror` reported, the dynamic linker's messages being the only details there are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DlError(pub String);
+impl core::fmt::Display for DlError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "DlError({})", self.0)
+    }
+}
+impl std::error::Error for DlError {}
@@ -0,0 +1,75 @@
+//! A [`Clock`] on top of `clock_gettime`.
+
+use super::*;
+use crate::timing::Clock;
+use core::time::Duration;
+
+/// The `CLOCK_MONOTONIC` clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MonotonicClock;
+
+impl MonotonicClock {
+    /// The clock, which has no state of its own.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Clock for MonotonicClock {
+    fn now(&self) -> Duration {
+        let mut time = timespec::default();
+        // Can't fail with a valid pointer and a clock every POSIX system has.
+        unsafe { clock_gettime(CLOCK_MONOTONIC, &mut time) };
+        Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+    }
+
+    fn sleep(&self, duration: Duration) {
+        let mut request = timespec {
+            tv_sec: duration.as_secs() as time_t,
+            tv_nsec: duration.subsec_nanos() as c_long,
+        };
+        let mut remaining = timespec::default();
+        while unsafe { nanosleep(&request, &mut remaining) } != 0 {
+            // Interrupted by a signal, sleep what's left. Anything else (an invalid duration) would
+            // fail again the same way.
+            if errno() != Errno(EINTR) {
+                break;
+            }
+            request = remaining;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timing::FrameClock;
+
+    #[test]
+    fn the_clock_never_goes_backwards() {
+        let clock = MonotonicClock::new();
+        let mut last = clock.now();
+        for _ in 0..1000 {
+            let now = clock.now();
+            assert!(now >= last);
+            last = now;
+        }
+    }
+
+    #[test]
+    fn sleeping_advances_the_clock() {
+        let clock = MonotonicClock::new();
+        let start = clock.now();
+        clock.sleep(Duration::from_millis(5));
+        assert!(clock.now() - start >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn frames_last_the_target_frame_time() {
+        let target = Duration::from_millis(5);
+        let mut frame_clock = FrameClock::with_frame_time(MonotonicClock::new(), target);
+        let timing = frame_clock.end_frame();
+        assert!(timing.frame_time >= target);
+        assert!(!timing.missed);
+    }
+}
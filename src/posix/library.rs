@@ -0,0 +1,65 @@
+//! Shared libraries loaded at runtime, through `dlopen`.
+
+use super::*;
+use std::{ffi::CStr, os::unix::ffi::OsStrExt, path::Path};
+
+/// The last `dlerror` message, or `fallback` if there is none.
+fn last_dl_error(fallback: &str) -> DlError {
+    let message = unsafe { dlerror() };
+    if message.is_null() {
+        DlError(fallback.to_owned())
+    } else {
+        DlError(
+            unsafe { CStr::from_ptr(message) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+}
+
+/// A shared library, closed on drop.
+pub struct Library {
+    handle: *mut c_void,
+}
+
+impl Library {
+    /// Loads the library at `path`, resolving all its symbols right away.
+    ///
+    /// See [`dlopen`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/dlopen.html)
+    pub fn load(path: &Path) -> Result<Self, DlError> {
+        let path_c: Vec<c_char> = path
+            .as_os_str()
+            .as_bytes()
+            .iter()
+            .map(|&b| b as c_char)
+            .chain(Some(0))
+            .collect();
+        let handle = unsafe { dlopen(path_c.as_ptr(), RTLD_NOW | RTLD_LOCAL) };
+        if handle.is_null() {
+            Err(last_dl_error("could not load the library"))
+        } else {
+            Ok(Self { handle })
+        }
+    }
+
+    /// Looks up the address of the symbol `name`.
+    ///
+    /// See [`dlsym`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/dlsym.html)
+    pub fn symbol(&self, name: &str) -> Result<*mut c_void, DlError> {
+        let name_c: Vec<c_char> = name.bytes().map(|b| b as c_char).chain(Some(0)).collect();
+        // Clear any older error, a symbol may legitimately be null.
+        unsafe { dlerror() };
+        let address = unsafe { dlsym(self.handle, name_c.as_ptr()) };
+        if address.is_null() {
+            Err(last_dl_error("the symbol is null"))
+        } else {
+            Ok(address)
+        }
+    }
+}
+
+impl Drop for Library {
+    fn drop(&mut self) {
+        unsafe { dlclose(self.handle) };
+    }
+}
@@ -0,0 +1,70 @@
+//! Pages straight from the kernel, through `mmap`.
+
+use super::*;
+use core::ptr::null_mut;
+
+/// `EEXIST`, when the pages can't be placed where asked.
+const EEXIST: c_int = 17;
+
+/// A block of anonymous private pages, unmapped on drop.
+pub struct VirtualMemory {
+    base: *mut u8,
+    size: usize,
+}
+
+impl VirtualMemory {
+    /// Maps `size` zeroed bytes, at `base_address` if there is one.
+    ///
+    /// `base_address` has to be a multiple of the page size. It is only a hint to the kernel,
+    /// which is free to map the pages elsewhere: that fails rather than clobber a mapping already
+    /// there, like `MAP_FIXED` would.
+    ///
+    /// See [`mmap`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/mmap.html)
+    pub fn allocate(base_address: Option<usize>, size: usize) -> Result<Self, Errno> {
+        let requested = base_address.map_or(null_mut(), |address| address as *mut c_void);
+        let base = unsafe {
+            mmap(
+                requested,
+                size,
+                PROT_READ | PROT_WRITE,
+                MAP_PRIVATE | MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        if base as usize == MAP_FAILED {
+            return Err(errno());
+        }
+        let memory = Self {
+            base: base.cast(),
+            size,
+        };
+        // Dropped on the way out when misplaced.
+        if !requested.is_null() && base != requested {
+            return Err(Errno(EEXIST));
+        }
+        Ok(memory)
+    }
+
+    /// The first byte.
+    pub fn as_ptr(&self) -> *mut u8 {
+        self.base
+    }
+
+    /// The size in bytes, as asked for.
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the block is empty, which it never is.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+}
+
+impl Drop for VirtualMemory {
+    /// See [`munmap`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/munmap.html)
+    fn drop(&mut self) {
+        unsafe { munmap(self.base.cast(), self.size) };
+    }
+}
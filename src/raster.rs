@@ -0,0 +1,328 @@
+//! A software rasterizer drawing into a CPU side [`Framebuffer`].
+//!
+//! Nothing in here touches the platform, the platform layers only get to present the result.
+
+/// An RGBA color, 8 bits per channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(C)]
+pub struct Rgba8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba8 {
+    pub const TRANSPARENT: Self = Self::new(0, 0, 0, 0);
+    pub const BLACK: Self = Self::new(0, 0, 0, 255);
+    pub const WHITE: Self = Self::new(255, 255, 255, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Converts a `[r, g, b, a]` color with channels in `0.0..=1.0`, clamping the channels
+    /// outside of that range.
+    pub fn from_f32s(color: [f32; 4]) -> Self {
+        let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Self::new(
+            channel(color[0]),
+            channel(color[1]),
+            channel(color[2]),
+            channel(color[3]),
+        )
+    }
+}
+
+/// A grid of pixels, stored row by row from the top-left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Framebuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<Rgba8>,
+}
+
+impl Framebuffer {
+    /// Creates a framebuffer filled with [`Rgba8::TRANSPARENT`].
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![Rgba8::TRANSPARENT; width as usize * height as usize],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// All the pixels, row by row from the top-left corner.
+    pub fn pixels(&self) -> &[Rgba8] {
+        &self.pixels
+    }
+
+    /// All the pixels, row by row from the top-left corner.
+    pub fn pixels_mut(&mut self) -> &mut [Rgba8] {
+        &mut self.pixels
+    }
+
+    /// The pixels as `RGBA8` bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        // Safety: `Rgba8` is four `u8`s, `repr(C)`, so without any padding.
+        unsafe { core::slice::from_raw_parts(self.pixels.as_ptr().cast(), self.pixels.len() * 4) }
+    }
+
+    /// The pixel at `(x, y)`, `None` if out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Rgba8> {
+        self.index(x, y).map(|i| self.pixels[i])
+    }
+
+    /// Sets the pixel at `(x, y)`, out of bounds coordinates are ignored.
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: Rgba8) {
+        if let Some(i) = self.index(x, y) {
+            self.pixels[i] = color;
+        }
+    }
+
+    /// Fills the whole framebuffer with `color`.
+    pub fn clear(&mut self, color: Rgba8) {
+        self.pixels.iter_mut().for_each(|pixel| *pixel = color);
+    }
+
+    /// Changes the size of the framebuffer, clearing it to [`Rgba8::TRANSPARENT`].
+    pub fn resize(&mut self, width: u32, height: u32) {
+        *self = Self::new(width, height);
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+/// A triangle corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vertex {
+    /// In pixels, `x` to the right and `y` down from the top-left corner of the framebuffer.
+    pub position: [f32; 2],
+    /// `[r, g, b, a]`, each channel in `0.0..=1.0`.
+    pub color: [f32; 4],
+}
+
+/// Twice the signed area of the triangle `(a, b, p)`.
+///
+/// Positive when `p` is on the inner side of the edge `a -> b` of a triangle wound like
+/// `(0, 0), (1, 0), (0, 1)`, zero when `p` is on the edge.
+fn edge_function(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
+
+/// Whether `a -> b` is a top or a left edge, with the winding of [`edge_function`].
+///
+/// Pixels whose center lies exactly on an edge only belong to the triangle for top and left
+/// edges, so that triangles sharing an edge never draw the same pixel twice.
+fn is_top_left(a: [f32; 2], b: [f32; 2]) -> bool {
+    let is_top = a[1] == b[1] && b[0] > a[0];
+    let is_left = b[1] < a[1];
+    is_top || is_left
+}
+
+/// Draws a triangle, interpolating the colors of its vertices across its surface.
+///
+/// * A pixel is covered when its center is inside the triangle, following the top-left rule
+///   on the edges.
+/// * Both windings are drawn, degenerate triangles aren't.
+/// * Pixels are overwritten, there is no blending.
+pub fn draw_triangle(framebuffer: &mut Framebuffer, vertices: &[Vertex; 3]) {
+    let [v0, mut v1, mut v2] = *vertices;
+    let mut area = edge_function(v0.position, v1.position, v2.position);
+    if area == 0.0 || !area.is_finite() {
+        return;
+    }
+    if area < 0.0 {
+        core::mem::swap(&mut v1, &mut v2);
+        area = -area;
+    }
+    let (p0, p1, p2) = (v0.position, v1.position, v2.position);
+
+    // Only go through the pixels of the bounding box, clipped to the framebuffer.
+    let clip = |value: f32, size: u32| value.max(0.0).min(size as f32) as u32;
+    let x_min = clip(p0[0].min(p1[0]).min(p2[0]).floor(), framebuffer.width());
+    let x_max = clip(p0[0].max(p1[0]).max(p2[0]).ceil(), framebuffer.width());
+    let y_min = clip(p0[1].min(p1[1]).min(p2[1]).floor(), framebuffer.height());
+    let y_max = clip(p0[1].max(p1[1]).max(p2[1]).ceil(), framebuffer.height());
+
+    let edges = [
+        (p1, p2, is_top_left(p1, p2)),
+        (p2, p0, is_top_left(p2, p0)),
+        (p0, p1, is_top_left(p0, p1)),
+    ];
+    let colors = [v0.color, v1.color, v2.color];
+
+    for y in y_min..y_max {
+        for x in x_min..x_max {
+            let center = [x as f32 + 0.5, y as f32 + 0.5];
+            let mut weights = [0.0; 3];
+            let mut inside = true;
+            for (weight, &(a, b, top_left)) in weights.iter_mut().zip(edges.iter()) {
+                *weight = edge_function(a, b, center);
+                inside &= *weight > 0.0 || (*weight == 0.0 && top_left);
+            }
+            if !inside {
+                continue;
+            }
+
+            // The barycentric coordinates of the pixel center, opposite each edge's vertex.
+            let mut color = [0.0; 4];
+            for (vertex_color, weight) in colors.iter().zip(weights.iter()) {
+                for (channel, vertex_channel) in color.iter_mut().zip(vertex_color.iter()) {
+                    *channel += vertex_channel * weight / area;
+                }
+            }
+            framebuffer.set_pixel(x, y, Rgba8::from_f32s(color));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vertex(x: f32, y: f32) -> Vertex {
+        Vertex {
+            position: [x, y],
+            color: [1.0, 1.0, 1.0, 1.0],
+        }
+    }
+
+    /// Draws a white triangle on a transparent framebuffer.
+    fn draw(width: u32, height: u32, positions: [[f32; 2]; 3]) -> Framebuffer {
+        let mut framebuffer = Framebuffer::new(width, height);
+        let [a, b, c] = positions;
+        draw_triangle(
+            &mut framebuffer,
+            &[vertex(a[0], a[1]), vertex(b[0], b[1]), vertex(c[0], c[1])],
+        );
+        framebuffer
+    }
+
+    /// The covered pixels as `#`, one line per row.
+    fn coverage(framebuffer: &Framebuffer) -> String {
+        framebuffer
+            .pixels()
+            .chunks(framebuffer.width() as usize)
+            .map(|row| {
+                row.iter()
+                    .map(|&pixel| {
+                        if pixel == Rgba8::TRANSPARENT {
+                            '.'
+                        } else {
+                            '#'
+                        }
+                    })
+                    .chain(Some('\n'))
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_the_reference_triangle() {
+        let framebuffer = draw(8, 8, [[1.0, 1.0], [7.0, 2.0], [3.0, 7.0]]);
+        let expected = "\
+            ........\n\
+            .###....\n\
+            .######.\n\
+            ..####..\n\
+            ..###...\n\
+            ..##....\n\
+            ........\n\
+            ........\n";
+        assert_eq!(coverage(&framebuffer), expected);
+    }
+
+    #[test]
+    fn shared_edges_are_drawn_once() {
+        // The diagonal goes through the centers of the pixels on it.
+        let upper = draw(4, 4, [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0]]);
+        let lower = draw(4, 4, [[0.0, 0.0], [4.0, 4.0], [0.0, 4.0]]);
+        assert_eq!(coverage(&upper), "####\n.###\n..##\n...#\n");
+        assert_eq!(coverage(&lower), "....\n#...\n##..\n###.\n");
+
+        // Same with a vertical edge through the centers of a column.
+        let left = draw(4, 2, [[0.0, 0.0], [2.5, 0.0], [2.5, 2.0]]);
+        let right = draw(4, 2, [[2.5, 0.0], [4.0, 0.0], [4.0, 2.0]]);
+        let right_below = draw(4, 2, [[0.0, 0.0], [2.5, 2.0], [0.0, 2.0]]);
+        let rest = draw(4, 2, [[2.5, 0.0], [4.0, 2.0], [2.5, 2.0]]);
+        for (x, y) in (0..4).flat_map(|x| (0..2).map(move |y| (x, y))) {
+            let drawn = [&left, &right, &right_below, &rest]
+                .iter()
+                .filter(|framebuffer| framebuffer.pixel(x, y) != Some(Rgba8::TRANSPARENT))
+                .count();
+            assert_eq!(drawn, 1, "pixel ({}, {})", x, y);
+        }
+    }
+
+    #[test]
+    fn both_windings_are_drawn_the_same() {
+        let clockwise = draw(8, 8, [[1.0, 1.0], [7.0, 2.0], [3.0, 7.0]]);
+        let counter_clockwise = draw(8, 8, [[1.0, 1.0], [3.0, 7.0], [7.0, 2.0]]);
+        assert_eq!(clockwise, counter_clockwise);
+    }
+
+    #[test]
+    fn degenerate_triangles_draw_nothing() {
+        let empty = Framebuffer::new(4, 4);
+        let collinear = draw(4, 4, [[0.0, 0.0], [2.0, 2.0], [4.0, 4.0]]);
+        let point = draw(4, 4, [[1.5, 1.5], [1.5, 1.5], [1.5, 1.5]]);
+        let not_a_number = draw(4, 4, [[0.0, 0.0], [f32::NAN, 4.0], [0.0, 4.0]]);
+        assert_eq!(collinear, empty);
+        assert_eq!(point, empty);
+        assert_eq!(not_a_number, empty);
+    }
+
+    #[test]
+    fn triangles_are_clipped_at_the_border() {
+        let positions = [[-4.0, -2.0], [5.0, 1.0], [2.0, 9.0]];
+        let clipped = draw(6, 4, positions);
+        assert_eq!(coverage(&clipped), "###...\n#####.\n####..\n####..\n");
+
+        // The same pixels as a framebuffer large enough for the whole triangle.
+        let shifted = positions.map(|[x, y]| [x + 4.0, y + 2.0]);
+        let whole = draw(16, 16, shifted);
+        for (x, y) in (0..6).flat_map(|x| (0..4).map(move |y| (x, y))) {
+            assert_eq!(clipped.pixel(x, y), whole.pixel(x + 4, y + 2));
+        }
+    }
+
+    #[test]
+    fn colors_are_interpolated() {
+        let mut framebuffer = Framebuffer::new(3, 3);
+        let red = [1.0, 0.0, 0.0, 1.0];
+        let vertices = [
+            Vertex {
+                position: [0.0, 0.0],
+                color: red,
+            },
+            Vertex {
+                position: [3.0, 0.0],
+                color: red,
+            },
+            Vertex {
+                position: [0.0, 3.0],
+                color: [0.0, 0.0, 1.0, 1.0],
+            },
+        ];
+        draw_triangle(&mut framebuffer, &vertices);
+        // 1/6 of the way to the blue vertex.
+        assert_eq!(framebuffer.pixel(0, 0), Some(Rgba8::new(213, 0, 43, 255)));
+        assert_eq!(framebuffer.pixel(2, 2), Some(Rgba8::TRANSPARENT));
+    }
+}
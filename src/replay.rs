@@ -0,0 +1,851 @@
+//! Input recording and loop playback, for live loop editing the way Handmade Hero does it.
+//!
+//! A recording starts with a snapshot of the game memory, followed by the input of every update.
+//! Playing it back restores the snapshot and feeds the recorded input to the game instead of the
+//! real one, looping forever: with the game code reloaded on the fly (see
+//! [`hot_reload`](crate::hot_reload)), every change shows up on the same few seconds of gameplay.
+//!
+//! ## Format
+//! Everything is little endian.
+//! * The header: [`RECORDING_MAGIC`], the [`RECORDING_VERSION`] as a `u16`, the
+//!   [`GAME_API_VERSION`] of the game that recorded it as a `u32`.
+//! * The snapshot: the base address as a `u64`, whether the game was initialized as a `u8`, the
+//!   size of the permanent arena as a `u64` followed by its bytes.
+//! * Until the end of the file, the updates: `dt` in nanoseconds as a `u64`, the number of events
+//!   as a `u32` followed by the events, each a tag byte followed by its fields.
+
+use crate::{
+    dpi::{Dpi, PhysicalSize},
+    event::{DeviceEvent, DeviceId, Event, Key, KeyboardInput, MouseButton, WindowEvent, WindowId},
+    game::{GameMemory, MemorySnapshot, GAME_API_VERSION},
+};
+use core::time::Duration;
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+/// The first bytes of a recording.
+pub const RECORDING_MAGIC: [u8; 4] = *b"TFSR";
+/// Bumped whenever the format changes, [`Key`]s included.
+pub const RECORDING_VERSION: u16 = 3;
+
+/// Why a recording could not be read or written.
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    /// The file doesn't start with [`RECORDING_MAGIC`].
+    NotARecording,
+    /// The file has another [`RECORDING_VERSION`].
+    UnsupportedVersion(u16),
+    /// The game that recorded the file was built for another [`GAME_API_VERSION`], its memory
+    /// would be read as something else.
+    GameApiVersion(u32),
+    /// The file is damaged, e.g. cut short or with an unknown tag.
+    Corrupt(&'static str),
+    /// The snapshot was taken from game memory at this other address.
+    BaseAddress(usize),
+}
+impl core::fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "ReplayError(io: {})", e),
+            Self::NotARecording => write!(f, "ReplayError(not a recording)"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "ReplayError(version {}, expected {})",
+                version, RECORDING_VERSION
+            ),
+            Self::GameApiVersion(version) => write!(
+                f,
+                "ReplayError(game api version {}, expected {})",
+                version, GAME_API_VERSION
+            ),
+            Self::Corrupt(reason) => write!(f, "ReplayError(corrupt: {})", reason),
+            Self::BaseAddress(address) => {
+                write!(f, "ReplayError(recorded at address {:#x})", address)
+            }
+        }
+    }
+}
+impl std::error::Error for ReplayError {}
+impl From<io::Error> for ReplayError {
+    fn from(e: io::Error) -> Self {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            Self::Corrupt("cut short")
+        } else {
+            Self::Io(e)
+        }
+    }
+}
+
+/// The input of one update.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedUpdate {
+    pub dt: Duration,
+    pub events: Vec<Event>,
+}
+
+/// A recording, read back whole.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recording {
+    /// The game memory when the recording started.
+    pub snapshot: MemorySnapshot,
+    pub updates: Vec<RecordedUpdate>,
+}
+
+impl Recording {
+    /// Writes the recording to `writer`.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write_header(writer, &self.snapshot)?;
+        for update in &self.updates {
+            write_update(writer, &update.events, update.dt)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a whole recording from `reader`.
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self, ReplayError> {
+        let snapshot = read_header(reader)?;
+        let mut updates = Vec::new();
+        while let Some(update) = read_update(reader)? {
+            updates.push(update);
+        }
+        Ok(Self { snapshot, updates })
+    }
+
+    /// Reads the recording at `path`.
+    pub fn load(path: &Path) -> Result<Self, ReplayError> {
+        Self::read_from(&mut BufReader::new(File::open(path)?))
+    }
+}
+
+/// Writes a recording as it happens.
+pub struct Recorder<W: Write> {
+    writer: W,
+    update_count: usize,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Starts a recording from the game memory as it is now.
+    pub fn start(mut writer: W, memory: &GameMemory) -> io::Result<Self> {
+        write_header(&mut writer, &memory.snapshot())?;
+        Ok(Self {
+            writer,
+            update_count: 0,
+        })
+    }
+
+    /// How many updates were recorded.
+    pub fn update_count(&self) -> usize {
+        self.update_count
+    }
+
+    /// Records the input of an update.
+    pub fn record(&mut self, events: &[Event], dt: Duration) -> io::Result<()> {
+        write_update(&mut self.writer, events, dt)?;
+        self.update_count += 1;
+        Ok(())
+    }
+
+    /// Ends the recording.
+    ///
+    /// **Returns:** The writer, flushed.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Plays a recording back, over and over.
+pub struct Player {
+    recording: Recording,
+    /// The update to play next.
+    next_update: usize,
+}
+
+impl Player {
+    /// Prepares to play `recording`, from the start.
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            next_update: 0,
+        }
+    }
+
+    /// The recording being played.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// The input of the next update, restoring the game memory first when starting over.
+    pub fn next(&mut self, memory: &mut GameMemory) -> Result<(&[Event], Duration), ReplayError> {
+        if self.next_update >= self.recording.updates.len() {
+            self.next_update = 0;
+        }
+        if self.next_update == 0 {
+            let snapshot = &self.recording.snapshot;
+            if snapshot.base_address != memory.permanent.base() as usize {
+                return Err(ReplayError::BaseAddress(snapshot.base_address));
+            }
+            if snapshot.permanent.len() > memory.permanent.size() {
+                return Err(ReplayError::Corrupt(
+                    "snapshot bigger than the permanent arena",
+                ));
+            }
+            memory.restore(snapshot);
+        }
+        match self.recording.updates.get(self.next_update) {
+            Some(update) => {
+                self.next_update += 1;
+                Ok((&update.events, update.dt))
+            }
+            // Nothing recorded, stay on the snapshot.
+            None => Ok((&[], Duration::ZERO)),
+        }
+    }
+}
+
+/// Where a [`LiveLoop`] is at.
+enum LoopState {
+    Idle,
+    Recording(Recorder<BufWriter<File>>),
+    Playing(Player),
+}
+
+/// A recording file driven by one key: recording, then playing it back, then back to normal.
+pub struct LiveLoop {
+    path: PathBuf,
+    state: LoopState,
+}
+
+impl LiveLoop {
+    /// A loop recorded to `path`, idle for now.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            state: LoopState::Idle,
+        }
+    }
+
+    /// The recording file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the input is being recorded.
+    pub fn is_recording(&self) -> bool {
+        matches!(self.state, LoopState::Recording(_))
+    }
+
+    /// Whether the input is being played back.
+    pub fn is_playing(&self) -> bool {
+        matches!(self.state, LoopState::Playing(_))
+    }
+
+    /// Moves on: idle to recording, recording to playing back, playing back to idle. To call
+    /// between updates, e.g. when the loop key is pressed.
+    ///
+    /// Playing back restores the memory from the start of the recording. Going back to idle
+    /// leaves the memory as the playback left it.
+    pub fn toggle(&mut self, memory: &mut GameMemory) -> Result<(), ReplayError> {
+        self.state = match core::mem::replace(&mut self.state, LoopState::Idle) {
+            LoopState::Idle => {
+                let file = BufWriter::new(File::create(&self.path)?);
+                LoopState::Recording(Recorder::start(file, memory)?)
+            }
+            LoopState::Recording(recorder) => {
+                recorder.finish()?;
+                LoopState::Playing(Player::new(Recording::load(&self.path)?))
+            }
+            LoopState::Playing(_) => LoopState::Idle,
+        };
+        Ok(())
+    }
+
+    /// The input for the next update: `events` and `dt` as they are when idle, also written
+    /// down when recording, or replaced by the recorded ones when playing back.
+    pub fn process<'a>(
+        &'a mut self,
+        memory: &mut GameMemory,
+        events: &'a [Event],
+        dt: Duration,
+    ) -> Result<(&'a [Event], Duration), ReplayError> {
+        match &mut self.state {
+            LoopState::Idle => Ok((events, dt)),
+            LoopState::Recording(recorder) => {
+                recorder.record(events, dt)?;
+                Ok((events, dt))
+            }
+            LoopState::Playing(player) => player.next(memory),
+        }
+    }
+}
+
+fn write_header<W: Write>(writer: &mut W, snapshot: &MemorySnapshot) -> io::Result<()> {
+    writer.write_all(&RECORDING_MAGIC)?;
+    writer.write_all(&RECORDING_VERSION.to_le_bytes())?;
+    writer.write_all(&GAME_API_VERSION.to_le_bytes())?;
+    writer.write_all(&(snapshot.base_address as u64).to_le_bytes())?;
+    writer.write_all(&[snapshot.is_initialized as u8])?;
+    writer.write_all(&(snapshot.permanent.len() as u64).to_le_bytes())?;
+    writer.write_all(&snapshot.permanent)
+}
+
+fn read_header<R: Read>(reader: &mut R) -> Result<MemorySnapshot, ReplayError> {
+    let mut magic = [0; 4];
+    reader
+        .read_exact(&mut magic)
+        .map_err(|_| ReplayError::NotARecording)?;
+    if magic != RECORDING_MAGIC {
+        return Err(ReplayError::NotARecording);
+    }
+    let version = u16::from_le_bytes(read_array(reader)?);
+    if version != RECORDING_VERSION {
+        return Err(ReplayError::UnsupportedVersion(version));
+    }
+    let game_api_version = u32::from_le_bytes(read_array(reader)?);
+    if game_api_version != GAME_API_VERSION {
+        return Err(ReplayError::GameApiVersion(game_api_version));
+    }
+    let base_address = read_usize(reader)?;
+    let is_initialized = read_bool(reader)?;
+    let permanent_len = read_usize(reader)?;
+    let mut permanent = Vec::new();
+    reader
+        .take(permanent_len as u64)
+        .read_to_end(&mut permanent)?;
+    if permanent.len() != permanent_len {
+        return Err(ReplayError::Corrupt("cut short"));
+    }
+    Ok(MemorySnapshot {
+        base_address,
+        is_initialized,
+        permanent,
+    })
+}
+
+fn write_update<W: Write>(writer: &mut W, events: &[Event], dt: Duration) -> io::Result<()> {
+    writer.write_all(&(dt.as_nanos() as u64).to_le_bytes())?;
+    writer.write_all(&(events.len() as u32).to_le_bytes())?;
+    for event in events {
+        write_event(writer, event)?;
+    }
+    Ok(())
+}
+
+/// **Returns:** `None` at the end of the recording.
+fn read_update<R: Read>(reader: &mut R) -> Result<Option<RecordedUpdate>, ReplayError> {
+    let mut dt = [0; 8];
+    // The end of the file is only fine right between two updates.
+    let mut read = 0;
+    while read < dt.len() {
+        match reader.read(&mut dt[read..]) {
+            Ok(0) if read == 0 => return Ok(None),
+            Ok(0) => return Err(ReplayError::Corrupt("cut short")),
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    let dt = Duration::from_nanos(u64::from_le_bytes(dt));
+    let event_count = u32::from_le_bytes(read_array(reader)?);
+    let mut events = Vec::new();
+    for _ in 0..event_count {
+        events.push(read_event(reader)?);
+    }
+    Ok(Some(RecordedUpdate { dt, events }))
+}
+
+fn write_event<W: Write>(writer: &mut W, event: &Event) -> io::Result<()> {
+    match *event {
+        Event::WindowEvent { window_id, event } => {
+            writer.write_all(&[0])?;
+            writer.write_all(&(window_id.0 as u64).to_le_bytes())?;
+            write_window_event(writer, &event)
+        }
+        Event::DeviceEvent { device_id, event } => {
+            writer.write_all(&[1])?;
+            writer.write_all(&(device_id.0 as u64).to_le_bytes())?;
+            write_device_event(writer, &event)
+        }
+        Event::MainEventsCleared => writer.write_all(&[2]),
+    }
+}
+
+fn read_event<R: Read>(reader: &mut R) -> Result<Event, ReplayError> {
+    match read_u8(reader)? {
+        0 => Ok(Event::WindowEvent {
+            window_id: WindowId(read_usize(reader)?),
+            event: read_window_event(reader)?,
+        }),
+        1 => Ok(Event::DeviceEvent {
+            device_id: DeviceId(read_usize(reader)?),
+            event: read_device_event(reader)?,
+        }),
+        2 => Ok(Event::MainEventsCleared),
+        _ => Err(ReplayError::Corrupt("unknown event")),
+    }
+}
+
+fn write_window_event<W: Write>(writer: &mut W, event: &WindowEvent) -> io::Result<()> {
+    let pair = |a: i32, b: i32| {
+        let mut bytes = [0; 8];
+        bytes[..4].copy_from_slice(&a.to_le_bytes());
+        bytes[4..].copy_from_slice(&b.to_le_bytes());
+        bytes
+    };
+    match *event {
+        WindowEvent::Created => writer.write_all(&[0]),
+        WindowEvent::Resized { size, dpi } => {
+            writer.write_all(&[1])?;
+            writer.write_all(&pair(size.width as i32, size.height as i32))?;
+            writer.write_all(&dpi.0.to_le_bytes())
+        }
+        WindowEvent::Moved { x, y } => {
+            writer.write_all(&[2])?;
+            writer.write_all(&pair(x, y))
+        }
+        WindowEvent::Focused(focused) => writer.write_all(&[3, focused as u8]),
+        WindowEvent::CloseRequested => writer.write_all(&[4]),
+        WindowEvent::Destroyed => writer.write_all(&[5]),
+        WindowEvent::Paint => writer.write_all(&[6]),
+        WindowEvent::KeyDown(input) => {
+            writer.write_all(&[7])?;
+            write_keyboard_input(writer, &input)
+        }
+        WindowEvent::KeyUp(input) => {
+            writer.write_all(&[8])?;
+            write_keyboard_input(writer, &input)
+        }
+        WindowEvent::TextInput(c) => {
+            writer.write_all(&[9])?;
+            writer.write_all(&(c as u32).to_le_bytes())
+        }
+        WindowEvent::MouseMove { x, y } => {
+            writer.write_all(&[10])?;
+            writer.write_all(&pair(x, y))
+        }
+        WindowEvent::MouseEnter => writer.write_all(&[11]),
+        WindowEvent::MouseLeave => writer.write_all(&[12]),
+        WindowEvent::MouseDown { button, x, y } => {
+            writer.write_all(&[13, button as u8])?;
+            writer.write_all(&pair(x, y))
+        }
+        WindowEvent::MouseUp { button, x, y } => {
+            writer.write_all(&[14, button as u8])?;
+            writer.write_all(&pair(x, y))
+        }
+        WindowEvent::MouseDoubleClick { button, x, y } => {
+            writer.write_all(&[15, button as u8])?;
+            writer.write_all(&pair(x, y))
+        }
+        WindowEvent::MouseWheel { delta_x, delta_y } => {
+            writer.write_all(&[16])?;
+            writer.write_all(&pair(delta_x, delta_y))
+        }
+        WindowEvent::DpiChanged(dpi) => {
+            writer.write_all(&[17])?;
+            writer.write_all(&dpi.0.to_le_bytes())
+        }
+    }
+}
+
+fn read_window_event<R: Read>(reader: &mut R) -> Result<WindowEvent, ReplayError> {
+    Ok(match read_u8(reader)? {
+        0 => WindowEvent::Created,
+        1 => WindowEvent::Resized {
+            size: PhysicalSize::new(read_i32(reader)? as u32, read_i32(reader)? as u32),
+            dpi: Dpi(u32::from_le_bytes(read_array(reader)?)),
+        },
+        2 => WindowEvent::Moved {
+            x: read_i32(reader)?,
+            y: read_i32(reader)?,
+        },
+        3 => WindowEvent::Focused(read_bool(reader)?),
+        4 => WindowEvent::CloseRequested,
+        5 => WindowEvent::Destroyed,
+        6 => WindowEvent::Paint,
+        7 => WindowEvent::KeyDown(read_keyboard_input(reader)?),
+        8 => WindowEvent::KeyUp(read_keyboard_input(reader)?),
+        9 => WindowEvent::TextInput(
+            char::from_u32(u32::from_le_bytes(read_array(reader)?))
+                .ok_or(ReplayError::Corrupt("invalid character"))?,
+        ),
+        10 => WindowEvent::MouseMove {
+            x: read_i32(reader)?,
+            y: read_i32(reader)?,
+        },
+        11 => WindowEvent::MouseEnter,
+        12 => WindowEvent::MouseLeave,
+        13 => WindowEvent::MouseDown {
+            button: read_mouse_button(reader)?,
+            x: read_i32(reader)?,
+            y: read_i32(reader)?,
+        },
+        14 => WindowEvent::MouseUp {
+            button: read_mouse_button(reader)?,
+            x: read_i32(reader)?,
+            y: read_i32(reader)?,
+        },
+        15 => WindowEvent::MouseDoubleClick {
+            button: read_mouse_button(reader)?,
+            x: read_i32(reader)?,
+            y: read_i32(reader)?,
+        },
+        16 => WindowEvent::MouseWheel {
+            delta_x: read_i32(reader)?,
+            delta_y: read_i32(reader)?,
+        },
+        17 => WindowEvent::DpiChanged(Dpi(u32::from_le_bytes(read_array(reader)?))),
+        _ => return Err(ReplayError::Corrupt("unknown window event")),
+    })
+}
+
+fn write_device_event<W: Write>(writer: &mut W, event: &DeviceEvent) -> io::Result<()> {
+    match *event {
+        DeviceEvent::MouseMotion { delta_x, delta_y } => {
+            writer.write_all(&[0])?;
+            writer.write_all(&delta_x.to_le_bytes())?;
+            writer.write_all(&delta_y.to_le_bytes())
+        }
+        DeviceEvent::MouseButton { button, pressed } => {
+            writer.write_all(&[1, button as u8, pressed as u8])
+        }
+        DeviceEvent::MouseWheel { delta_x, delta_y } => {
+            writer.write_all(&[2])?;
+            writer.write_all(&delta_x.to_le_bytes())?;
+            writer.write_all(&delta_y.to_le_bytes())
+        }
+        DeviceEvent::Key {
+            key,
+            scancode,
+            pressed,
+        } => {
+            writer.write_all(&[3, key as u8])?;
+            writer.write_all(&scancode.to_le_bytes())?;
+            writer.write_all(&[pressed as u8])
+        }
+    }
+}
+
+fn read_device_event<R: Read>(reader: &mut R) -> Result<DeviceEvent, ReplayError> {
+    Ok(match read_u8(reader)? {
+        0 => DeviceEvent::MouseMotion {
+            delta_x: read_i32(reader)?,
+            delta_y: read_i32(reader)?,
+        },
+        1 => DeviceEvent::MouseButton {
+            button: read_mouse_button(reader)?,
+            pressed: read_bool(reader)?,
+        },
+        2 => DeviceEvent::MouseWheel {
+            delta_x: read_i32(reader)?,
+            delta_y: read_i32(reader)?,
+        },
+        3 => DeviceEvent::Key {
+            key: read_key(reader)?,
+            scancode: u32::from_le_bytes(read_array(reader)?),
+            pressed: read_bool(reader)?,
+        },
+        _ => return Err(ReplayError::Corrupt("unknown device event")),
+    })
+}
+
+fn write_keyboard_input<W: Write>(writer: &mut W, input: &KeyboardInput) -> io::Result<()> {
+    writer.write_all(&[input.key as u8])?;
+    writer.write_all(&input.scancode.to_le_bytes())?;
+    writer.write_all(&[input.repeat as u8])?;
+    writer.write_all(&input.repeat_count.to_le_bytes())
+}
+
+fn read_keyboard_input<R: Read>(reader: &mut R) -> Result<KeyboardInput, ReplayError> {
+    Ok(KeyboardInput {
+        key: read_key(reader)?,
+        scancode: u32::from_le_bytes(read_array(reader)?),
+        repeat: read_bool(reader)?,
+        repeat_count: u16::from_le_bytes(read_array(reader)?),
+    })
+}
+
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> Result<[u8; N], ReplayError> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_u8<R: Read>(reader: &mut R) -> Result<u8, ReplayError> {
+    Ok(read_array::<R, 1>(reader)?[0])
+}
+
+fn read_bool<R: Read>(reader: &mut R) -> Result<bool, ReplayError> {
+    match read_u8(reader)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(ReplayError::Corrupt("invalid boolean")),
+    }
+}
+
+fn read_i32<R: Read>(reader: &mut R) -> Result<i32, ReplayError> {
+    Ok(i32::from_le_bytes(read_array(reader)?))
+}
+
+fn read_usize<R: Read>(reader: &mut R) -> Result<usize, ReplayError> {
+    let value = u64::from_le_bytes(read_array(reader)?);
+    if value > usize::MAX as u64 {
+        return Err(ReplayError::Corrupt("value too big for this platform"));
+    }
+    Ok(value as usize)
+}
+
+fn read_key<R: Read>(reader: &mut R) -> Result<Key, ReplayError> {
+    Key::from_u8(read_u8(reader)?).ok_or(ReplayError::Corrupt("unknown key"))
+}
+
+fn read_mouse_button<R: Read>(reader: &mut R) -> Result<MouseButton, ReplayError> {
+    Ok(match read_u8(reader)? {
+        0 => MouseButton::Left,
+        1 => MouseButton::Right,
+        2 => MouseButton::Middle,
+        3 => MouseButton::X1,
+        4 => MouseButton::X2,
+        _ => return Err(ReplayError::Corrupt("unknown mouse button")),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Game memory backed by the heap, along with its blocks.
+    fn heap_memory() -> (GameMemory, Vec<u64>, Vec<u64>) {
+        let mut permanent = vec![0_u64; 64];
+        let mut transient = vec![0_u64; 64];
+        let memory = unsafe {
+            GameMemory::from_raw_parts(
+                permanent.as_mut_ptr().cast(),
+                permanent.len() * 8,
+                transient.as_mut_ptr().cast(),
+                transient.len() * 8,
+            )
+        };
+        (memory, permanent, transient)
+    }
+
+    fn window_event(event: WindowEvent) -> Event {
+        Event::WindowEvent {
+            window_id: WindowId(0x1234),
+            event,
+        }
+    }
+
+    fn every_event() -> Vec<Event> {
+        let input = KeyboardInput {
+            key: Key::Unknown,
+            scancode: 0x1e,
+            repeat: true,
+            repeat_count: 3,
+        };
+        let mut events: Vec<Event> = vec![
+            WindowEvent::Created,
+            WindowEvent::Resized {
+                size: PhysicalSize::new(1200, 900),
+                dpi: Dpi(144),
+            },
+            WindowEvent::DpiChanged(Dpi(120)),
+            WindowEvent::Moved { x: -10, y: 20 },
+            WindowEvent::Focused(true),
+            WindowEvent::CloseRequested,
+            WindowEvent::Destroyed,
+            WindowEvent::Paint,
+            WindowEvent::KeyDown(input),
+            WindowEvent::KeyUp(KeyboardInput {
+                key: Key::A,
+                ..input
+            }),
+            WindowEvent::TextInput('é'),
+            WindowEvent::MouseMove { x: 1, y: 2 },
+            WindowEvent::MouseEnter,
+            WindowEvent::MouseLeave,
+            WindowEvent::MouseDown {
+                button: MouseButton::X2,
+                x: 3,
+                y: 4,
+            },
+            WindowEvent::MouseUp {
+                button: MouseButton::Left,
+                x: 5,
+                y: 6,
+            },
+            WindowEvent::MouseDoubleClick {
+                button: MouseButton::Middle,
+                x: 7,
+                y: 8,
+            },
+            WindowEvent::MouseWheel {
+                delta_x: 0,
+                delta_y: -120,
+            },
+        ]
+        .into_iter()
+        .map(window_event)
+        .collect();
+        for event in [
+            DeviceEvent::MouseMotion {
+                delta_x: -1,
+                delta_y: 1,
+            },
+            DeviceEvent::MouseButton {
+                button: MouseButton::Right,
+                pressed: true,
+            },
+            DeviceEvent::MouseWheel {
+                delta_x: 120,
+                delta_y: 0,
+            },
+            DeviceEvent::Key {
+                key: Key::Space,
+                scancode: 0x39,
+                pressed: false,
+            },
+        ] {
+            events.push(Event::DeviceEvent {
+                device_id: DeviceId(7),
+                event,
+            });
+        }
+        events.push(Event::MainEventsCleared);
+        events
+    }
+
+    #[test]
+    fn recording_round_trips() {
+        let recording = Recording {
+            snapshot: MemorySnapshot {
+                base_address: 2 << 40,
+                is_initialized: true,
+                permanent: (0..=255).collect(),
+            },
+            updates: vec![
+                RecordedUpdate {
+                    dt: Duration::from_nanos(16_666_667),
+                    events: every_event(),
+                },
+                RecordedUpdate {
+                    dt: Duration::from_millis(10),
+                    events: Vec::new(),
+                },
+            ],
+        };
+        let mut bytes = Vec::new();
+        recording.write_to(&mut bytes).unwrap();
+        assert_eq!(bytes[..4], RECORDING_MAGIC);
+        assert_eq!(Recording::read_from(&mut &bytes[..]).unwrap(), recording);
+    }
+
+    #[test]
+    fn rejects_damaged_recordings() {
+        let recording = Recording {
+            snapshot: MemorySnapshot {
+                base_address: 0,
+                is_initialized: false,
+                permanent: vec![1, 2, 3],
+            },
+            updates: vec![RecordedUpdate {
+                dt: Duration::from_millis(1),
+                events: every_event(),
+            }],
+        };
+        let mut bytes = Vec::new();
+        recording.write_to(&mut bytes).unwrap();
+
+        assert!(matches!(
+            Recording::read_from(&mut &b"nope"[..]),
+            Err(ReplayError::NotARecording)
+        ));
+        let mut newer = bytes.clone();
+        newer[4..6].copy_from_slice(&(RECORDING_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            Recording::read_from(&mut &newer[..]),
+            Err(ReplayError::UnsupportedVersion(_))
+        ));
+        let mut older_game = bytes.clone();
+        older_game[6..10].copy_from_slice(&(GAME_API_VERSION - 1).to_le_bytes());
+        assert!(matches!(
+            Recording::read_from(&mut &older_game[..]),
+            Err(ReplayError::GameApiVersion(version)) if version == GAME_API_VERSION - 1
+        ));
+        assert!(matches!(
+            Recording::read_from(&mut &bytes[..bytes.len() - 1]),
+            Err(ReplayError::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn recorder_output_reads_back() {
+        let (mut memory, _permanent, _transient) = heap_memory();
+        *unsafe { memory.state(|| 41_u32) } += 1;
+        let mut recorder = Recorder::start(Vec::new(), &memory).unwrap();
+        let events = every_event();
+        recorder.record(&events, Duration::from_millis(16)).unwrap();
+        recorder.record(&[], Duration::from_millis(17)).unwrap();
+        assert_eq!(recorder.update_count(), 2);
+        let bytes = recorder.finish().unwrap();
+
+        let recording = Recording::read_from(&mut &bytes[..]).unwrap();
+        assert_eq!(recording.snapshot, memory.snapshot());
+        assert_eq!(recording.updates.len(), 2);
+        assert_eq!(recording.updates[0].events, events);
+        assert_eq!(recording.updates[1].dt, Duration::from_millis(17));
+    }
+
+    #[test]
+    fn live_loop_records_then_loops() {
+        let path = std::env::temp_dir().join(format!("live_loop_{}.rec", std::process::id()));
+        let (mut memory, _permanent, _transient) = heap_memory();
+        let mut live_loop = LiveLoop::new(&path);
+        let dt = Duration::from_millis(10);
+        let key = |key| {
+            window_event(WindowEvent::KeyDown(KeyboardInput {
+                key,
+                scancode: 0,
+                repeat: false,
+                repeat_count: 1,
+            }))
+        };
+
+        *unsafe { memory.state(|| 0_u32) } = 100;
+        live_loop.toggle(&mut memory).unwrap();
+        assert!(live_loop.is_recording());
+        for recorded in [key(Key::A), key(Key::B)].iter() {
+            let (events, _) = live_loop
+                .process(&mut memory, core::slice::from_ref(recorded), dt)
+                .unwrap();
+            assert_eq!(events, core::slice::from_ref(recorded));
+            *unsafe { memory.state(|| 0_u32) } += 1;
+        }
+
+        live_loop.toggle(&mut memory).unwrap();
+        assert!(live_loop.is_playing());
+        let ignored = [key(Key::Z)];
+        for _ in 0..2 {
+            for expected in [key(Key::A), key(Key::B)].iter() {
+                let (events, played_dt) = live_loop
+                    .process(&mut memory, &ignored, Duration::ZERO)
+                    .unwrap();
+                assert_eq!(events, core::slice::from_ref(expected));
+                assert_eq!(played_dt, dt);
+            }
+            // Back to the memory at the start of the recording, every time round.
+            assert_eq!(*unsafe { memory.state(|| 0_u32) }, 100);
+            *unsafe { memory.state(|| 0_u32) } += 2;
+        }
+
+        live_loop.toggle(&mut memory).unwrap();
+        assert!(!live_loop.is_recording() && !live_loop.is_playing());
+        let idle = [key(Key::Z)];
+        assert_eq!(live_loop.process(&mut memory, &idle, dt).unwrap().0, &idle);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,268 @@
+//! Frame pacing, on top of any monotonic [`Clock`].
+//!
+//! The pacing math only ever talks to the clock through the trait, so it runs the same against the
+//! Win32 performance counter, `clock_gettime` or a fake clock in tests.
+
+use core::time::Duration;
+
+/// A monotonic clock able to put the thread to sleep.
+pub trait Clock {
+    /// The time elapsed since an arbitrary, fixed, point in the past.
+    fn now(&self) -> Duration;
+
+    /// Puts the thread to sleep for about `duration`.
+    ///
+    /// Sleeps are allowed to overshoot, but should rarely undershoot.
+    fn sleep(&self, duration: Duration);
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration)
+    }
+}
+
+/// How a frame went, see [`FrameClock::end_frame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameTiming {
+    /// The time spent between the start of the frame and [`FrameClock::end_frame`].
+    pub work_time: Duration,
+    /// The whole frame, waiting included.
+    pub frame_time: Duration,
+    /// Whether the work took longer than the target frame time.
+    pub missed: bool,
+}
+
+/// Paces frames to a target rate, sleeping the spare time away and spinning for the last bit.
+///
+/// Sleeps are only precise to a millisecond or so (see `timeBeginPeriod` on Windows), so the
+/// clock stops sleeping [`FrameClock::spin_threshold`] before the deadline and busy-waits the
+/// rest.
+pub struct FrameClock<C: Clock> {
+    clock: C,
+    target_frame_time: Duration,
+    /// How long before the deadline to stop sleeping and start spinning.
+    pub spin_threshold: Duration,
+    frame_start: Duration,
+    frame_count: u64,
+    missed_frames: u64,
+}
+
+impl<C: Clock> FrameClock<C> {
+    /// Paces frames to `refresh_rate` frames per second, the first frame starting now.
+    pub fn new(clock: C, refresh_rate: u32) -> Self {
+        let target = Duration::from_nanos(1_000_000_000 / u64::from(refresh_rate.max(1)));
+        Self::with_frame_time(clock, target)
+    }
+
+    /// Paces frames to one every `target_frame_time`, the first frame starting now.
+    pub fn with_frame_time(clock: C, target_frame_time: Duration) -> Self {
+        let frame_start = clock.now();
+        Self {
+            clock,
+            target_frame_time,
+            spin_threshold: Duration::from_millis(2),
+            frame_start,
+            frame_count: 0,
+            missed_frames: 0,
+        }
+    }
+
+    /// The clock the frames are measured with.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// How long each frame should last.
+    pub fn target_frame_time(&self) -> Duration {
+        self.target_frame_time
+    }
+
+    /// How many frames ended so far.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// How many frames took longer than the target frame time so far.
+    pub fn missed_frames(&self) -> u64 {
+        self.missed_frames
+    }
+
+    /// How long the current frame has lasted so far.
+    pub fn elapsed(&self) -> Duration {
+        self.clock.now().saturating_sub(self.frame_start)
+    }
+
+    /// Starts a new frame now, e.g. after a pause, without counting the current one.
+    pub fn reset(&mut self) {
+        self.frame_start = self.clock.now();
+    }
+
+    /// Ends the current frame, waiting until its deadline unless it is already missed, and starts
+    /// the next one.
+    ///
+    /// The next frame starts at the deadline rather than when the wait ends, so oversleeping a
+    /// little doesn't make the rate drift. After a missed frame, the next one starts right away.
+    pub fn end_frame(&mut self) -> FrameTiming {
+        let work_time = self.elapsed();
+        let deadline = self.frame_start + self.target_frame_time;
+        let missed = work_time > self.target_frame_time;
+        if !missed {
+            let remaining = deadline.saturating_sub(self.clock.now());
+            if remaining > self.spin_threshold {
+                self.clock.sleep(remaining - self.spin_threshold);
+            }
+            while self.clock.now() < deadline {
+                core::hint::spin_loop();
+            }
+        }
+        let end = self.clock.now();
+        let frame_time = end.saturating_sub(self.frame_start);
+        self.frame_start = if missed { end } else { deadline };
+        self.frame_count += 1;
+        self.missed_frames += u64::from(missed);
+        FrameTiming {
+            work_time,
+            frame_time,
+            missed,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    /// A clock that only moves when told to, or when slept on.
+    #[derive(Debug, Default)]
+    pub(crate) struct FakeClock {
+        now: Cell<Duration>,
+        /// How much every reading moves the clock, so that spinning on it ends.
+        pub(crate) tick: Duration,
+        /// How much every sleep oversleeps.
+        pub(crate) oversleep: Duration,
+        /// Every sleep so far.
+        pub(crate) sleeps: Cell<u32>,
+    }
+
+    impl FakeClock {
+        pub(crate) fn advance(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> Duration {
+            let now = self.now.get();
+            self.advance(self.tick);
+            now
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.sleeps.set(self.sleeps.get() + 1);
+            self.advance(duration + self.oversleep);
+        }
+    }
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn frames_are_paced_without_drifting() {
+        let clock = FakeClock {
+            oversleep: MS,
+            ..FakeClock::default()
+        };
+        let mut frame_clock = FrameClock::with_frame_time(&clock, 10 * MS);
+        // Without a tick, spinning on the fake clock would never end: sleep all the way instead.
+        frame_clock.spin_threshold = Duration::ZERO;
+        for frame in 1..=10 {
+            clock.advance(3 * MS);
+            let timing = frame_clock.end_frame();
+            // The frames start on the 10ms grid, the oversleeping eats into the next frame.
+            let work_time = if frame == 1 { 3 * MS } else { 4 * MS };
+            assert_eq!(
+                timing,
+                FrameTiming {
+                    work_time,
+                    frame_time: 11 * MS,
+                    missed: false,
+                }
+            );
+        }
+        assert_eq!(clock.now(), 101 * MS);
+        assert_eq!(frame_clock.frame_count(), 10);
+        assert_eq!(frame_clock.missed_frames(), 0);
+        assert_eq!(clock.sleeps.get(), 10);
+    }
+
+    #[test]
+    fn missed_frames_start_the_next_one_right_away() {
+        let clock = FakeClock::default();
+        let mut frame_clock = FrameClock::with_frame_time(&clock, 10 * MS);
+        frame_clock.spin_threshold = Duration::ZERO;
+        clock.advance(15 * MS);
+        let timing = frame_clock.end_frame();
+        assert_eq!(
+            timing,
+            FrameTiming {
+                work_time: 15 * MS,
+                frame_time: 15 * MS,
+                missed: true,
+            }
+        );
+        assert_eq!(clock.sleeps.get(), 0);
+        // The next deadline is 10ms after the missed frame ended, not on the old grid.
+        clock.advance(2 * MS);
+        assert_eq!(frame_clock.elapsed(), 2 * MS);
+        let timing = frame_clock.end_frame();
+        assert!(!timing.missed);
+        assert_eq!(clock.now(), 25 * MS);
+        assert_eq!(frame_clock.frame_count(), 2);
+        assert_eq!(frame_clock.missed_frames(), 1);
+        // Exactly on time isn't late.
+        clock.advance(10 * MS);
+        assert!(!frame_clock.end_frame().missed);
+    }
+
+    #[test]
+    fn the_last_bit_is_spun_away() {
+        let clock = FakeClock {
+            tick: Duration::from_micros(10),
+            ..FakeClock::default()
+        };
+        let mut frame_clock = FrameClock::with_frame_time(&clock, 10 * MS);
+        clock.advance(3 * MS);
+        let timing = frame_clock.end_frame();
+        // Sleeping up to 2ms before the deadline, then spinning until it.
+        assert_eq!(clock.sleeps.get(), 1);
+        assert!(timing.frame_time >= 10 * MS);
+        assert!(timing.frame_time < 10 * MS + Duration::from_micros(50));
+        // Less than the threshold left, no sleep at all.
+        clock.advance(9 * MS);
+        let timing = frame_clock.end_frame();
+        assert_eq!(clock.sleeps.get(), 1);
+        assert!(!timing.missed);
+        assert!(timing.frame_time >= 10 * MS);
+    }
+
+    #[test]
+    fn reset_forgets_the_pause() {
+        let clock = FakeClock::default();
+        let mut frame_clock = FrameClock::new(&clock, 60);
+        frame_clock.spin_threshold = Duration::ZERO;
+        assert_eq!(
+            frame_clock.target_frame_time(),
+            Duration::from_nanos(16_666_666)
+        );
+        clock.advance(Duration::from_secs(1));
+        frame_clock.reset();
+        assert_eq!(frame_clock.elapsed(), Duration::ZERO);
+        assert!(!frame_clock.end_frame().missed);
+        assert_eq!(frame_clock.frame_count(), 1);
+    }
+}
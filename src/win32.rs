@@ -6,6 +6,33 @@
 use core::ffi::c_void;
 use std::os::raw::{c_int, c_uint};
 
+#[cfg(windows)]
+pub mod audio;
+pub mod builder;
+#[cfg(windows)]
+pub mod class;
+#[cfg(windows)]
+pub mod clock;
+#[cfg(windows)]
+pub mod dpi;
+pub mod event;
+#[cfg(windows)]
+pub mod event_loop;
+#[cfg(windows)]
+pub mod library;
+#[cfg(windows)]
+pub mod memory;
+#[cfg(windows)]
+pub mod monitor;
+#[cfg(windows)]
+pub mod safe;
+#[cfg(windows)]
+pub mod wgl;
+#[cfg(windows)]
+pub mod window;
+#[cfg(windows)]
+pub mod xinput;
+
 // See
 // - https://docs.microsoft.com/en-us/cpp/cpp/data-type-ranges?view=msvc-160
 // - https://docs.microsoft.com/en-us/windows/win32/winprog/windows-data-types
@@ -54,13 +81,23 @@ pub type WORD = c_ushort;
 pub type c_ushort = u16;
 
 pub type HMENU = HANDLE;
+pub type USHORT = c_ushort;
+pub type ULONG = c_ulong;
+pub type PUINT = *mut UINT;
+pub type HRAWINPUT = HANDLE;
+pub type SHORT = i16;
+pub type HRESULT = LONG;
+/// A 64 bits signed integer, the C union also allows accessing it as two 32 bits halves.
+pub type LARGE_INTEGER = i64;
+pub type MMRESULT = UINT;
 pub type LPVOID = *mut c_void;
 
 pub type BOOL = c_int;
-pub type LPMSG = *const MSG;
+pub type LPMSG = *mut MSG;
 
 pub type LPWSTR = *mut WCHAR;
 pub type ULONG_PTR = usize;
+pub type SIZE_T = ULONG_PTR;
 
 pub type LPPAINTSTRUCT = *mut PAINTSTRUCT;
 
@@ -70,6 +107,18 @@ pub type c_char = i8;
 
 pub type HLOCAL = HANDLE;
 
+pub type HBITMAP = HANDLE;
+pub type HGDIOBJ = HANDLE;
+pub type LPRECT = *mut RECT;
+
+/// A handle to an OpenGL rendering context.
+pub type HGLRC = HANDLE;
+pub type LPCSTR = *const c_char;
+pub type FLOAT = f32;
+/// A generic function pointer, as returned by `GetProcAddress` and `wglGetProcAddress`.
+pub type PROC = *mut c_void;
+pub type FARPROC = *mut c_void;
+
 ///[`WNDPROC`](https://docs.microsoft.com/en-us/previous-versions/windows/desktop/legacy/ms633573(v=vs.85))
 /// This type defines a pointer to  the application-defined callback function `WindowProc`
 /// that processes messages sent to a window.
@@ -81,6 +130,7 @@ pub type WNDPROC = Option<
 /// Window Messages
 pub const WM_NULL: u32 = 0x0000;
 pub const WM_NCCREATE: u32 = 0x0081;
+pub const WM_NCDESTROY: u32 = 0x0082;
 pub const WM_CREATE: u32 = 0x0001;
 pub const WM_DESTROY: u32 = 0x0002;
 pub const WM_MOVE: u32 = 0x0003;
@@ -113,6 +163,235 @@ pub const WM_CHILDACTIVATE: u32 = 0x0022;
 pub const WM_QUEUESYNC: u32 = 0x0023;
 pub const WM_GETMINMAXINFO: u32 = 0x0024;
 
+pub const WM_KEYDOWN: u32 = 0x0100;
+pub const WM_KEYUP: u32 = 0x0101;
+pub const WM_CHAR: u32 = 0x0102;
+pub const WM_DEADCHAR: u32 = 0x0103;
+pub const WM_SYSKEYDOWN: u32 = 0x0104;
+pub const WM_SYSKEYUP: u32 = 0x0105;
+pub const WM_SYSCHAR: u32 = 0x0106;
+pub const WM_SYSDEADCHAR: u32 = 0x0107;
+
+pub const WM_MOUSEMOVE: u32 = 0x0200;
+pub const WM_LBUTTONDOWN: u32 = 0x0201;
+pub const WM_LBUTTONUP: u32 = 0x0202;
+pub const WM_LBUTTONDBLCLK: u32 = 0x0203;
+pub const WM_RBUTTONDOWN: u32 = 0x0204;
+pub const WM_RBUTTONUP: u32 = 0x0205;
+pub const WM_RBUTTONDBLCLK: u32 = 0x0206;
+pub const WM_MBUTTONDOWN: u32 = 0x0207;
+pub const WM_MBUTTONUP: u32 = 0x0208;
+pub const WM_MBUTTONDBLCLK: u32 = 0x0209;
+pub const WM_MOUSEWHEEL: u32 = 0x020A;
+pub const WM_XBUTTONDOWN: u32 = 0x020B;
+pub const WM_XBUTTONUP: u32 = 0x020C;
+pub const WM_XBUTTONDBLCLK: u32 = 0x020D;
+pub const WM_MOUSEHWHEEL: u32 = 0x020E;
+pub const WM_CAPTURECHANGED: u32 = 0x0215;
+pub const WM_MOUSELEAVE: u32 = 0x02A3;
+/// See [`WM_DPICHANGED`](https://docs.microsoft.com/en-us/windows/win32/hidpi/wm-dpichanged)
+pub const WM_DPICHANGED: u32 = 0x02E0;
+
+/// Mouse Key State, found in the `wParam` of the mouse messages.
+///
+/// See [`WM_MOUSEMOVE`](https://docs.microsoft.com/en-us/windows/win32/inputdev/wm-mousemove)
+pub const MK_LBUTTON: usize = 0x0001;
+pub const MK_RBUTTON: usize = 0x0002;
+pub const MK_SHIFT: usize = 0x0004;
+pub const MK_CONTROL: usize = 0x0008;
+pub const MK_MBUTTON: usize = 0x0010;
+pub const MK_XBUTTON1: usize = 0x0020;
+pub const MK_XBUTTON2: usize = 0x0040;
+
+/// Which X button a `WM_XBUTTON*` message is about, in the high-order word of its `wParam`.
+pub const XBUTTON1: WORD = 0x0001;
+pub const XBUTTON2: WORD = 0x0002;
+
+/// The wheel rotation of one notch, see [`WM_MOUSEWHEEL`](https://docs.microsoft.com/en-us/windows/win32/inputdev/wm-mousewheel)
+pub const WHEEL_DELTA: c_int = 120;
+
+/// See [`TRACKMOUSEEVENT`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-trackmouseevent)
+pub const TME_HOVER: DWORD = 0x00000001;
+pub const TME_LEAVE: DWORD = 0x00000002;
+pub const TME_NONCLIENT: DWORD = 0x00000010;
+pub const TME_QUERY: DWORD = 0x40000000;
+pub const TME_CANCEL: DWORD = 0x80000000;
+pub const HOVER_DEFAULT: DWORD = 0xFFFFFFFF;
+
+/// Sent to the window receiving raw input, see [`WM_INPUT`](https://docs.microsoft.com/en-us/windows/win32/inputdev/wm-input)
+pub const WM_INPUT: u32 = 0x00FF;
+/// The `wParam` of `WM_INPUT`, for input received while the window is in the foreground.
+pub const RIM_INPUT: WPARAM = 0;
+/// The `wParam` of `WM_INPUT`, for input received while the window is in the background.
+pub const RIM_INPUTSINK: WPARAM = 1;
+
+/// What to get out of [`GetRawInputData`].
+pub const RID_INPUT: UINT = 0x10000003;
+pub const RID_HEADER: UINT = 0x10000005;
+
+/// Raw input device types, see [`RAWINPUTHEADER`].
+pub const RIM_TYPEMOUSE: DWORD = 0;
+pub const RIM_TYPEKEYBOARD: DWORD = 1;
+pub const RIM_TYPEHID: DWORD = 2;
+
+/// Raw input device registration flags, see [`RAWINPUTDEVICE`].
+pub const RIDEV_REMOVE: DWORD = 0x00000001;
+pub const RIDEV_NOLEGACY: DWORD = 0x00000030;
+pub const RIDEV_INPUTSINK: DWORD = 0x00000100;
+pub const RIDEV_DEVNOTIFY: DWORD = 0x00002000;
+
+/// HID usages, see [HID Usages](https://docs.microsoft.com/en-us/windows-hardware/drivers/hid/hid-usages)
+pub const HID_USAGE_PAGE_GENERIC: USHORT = 0x01;
+pub const HID_USAGE_GENERIC_MOUSE: USHORT = 0x02;
+pub const HID_USAGE_GENERIC_KEYBOARD: USHORT = 0x06;
+
+/// Raw mouse motion flags, see [`RAWMOUSE`].
+pub const MOUSE_MOVE_RELATIVE: USHORT = 0x00;
+pub const MOUSE_MOVE_ABSOLUTE: USHORT = 0x01;
+pub const MOUSE_VIRTUAL_DESKTOP: USHORT = 0x02;
+
+/// Raw mouse button flags, see [`RAWMOUSE`].
+pub const RI_MOUSE_LEFT_BUTTON_DOWN: USHORT = 0x0001;
+pub const RI_MOUSE_LEFT_BUTTON_UP: USHORT = 0x0002;
+pub const RI_MOUSE_RIGHT_BUTTON_DOWN: USHORT = 0x0004;
+pub const RI_MOUSE_RIGHT_BUTTON_UP: USHORT = 0x0008;
+pub const RI_MOUSE_MIDDLE_BUTTON_DOWN: USHORT = 0x0010;
+pub const RI_MOUSE_MIDDLE_BUTTON_UP: USHORT = 0x0020;
+pub const RI_MOUSE_BUTTON_4_DOWN: USHORT = 0x0040;
+pub const RI_MOUSE_BUTTON_4_UP: USHORT = 0x0080;
+pub const RI_MOUSE_BUTTON_5_DOWN: USHORT = 0x0100;
+pub const RI_MOUSE_BUTTON_5_UP: USHORT = 0x0200;
+pub const RI_MOUSE_WHEEL: USHORT = 0x0400;
+pub const RI_MOUSE_HWHEEL: USHORT = 0x0800;
+
+/// XInput gamepad buttons, see [`XINPUT_GAMEPAD`].
+pub const XINPUT_GAMEPAD_DPAD_UP: WORD = 0x0001;
+pub const XINPUT_GAMEPAD_DPAD_DOWN: WORD = 0x0002;
+pub const XINPUT_GAMEPAD_DPAD_LEFT: WORD = 0x0004;
+pub const XINPUT_GAMEPAD_DPAD_RIGHT: WORD = 0x0008;
+pub const XINPUT_GAMEPAD_START: WORD = 0x0010;
+pub const XINPUT_GAMEPAD_BACK: WORD = 0x0020;
+pub const XINPUT_GAMEPAD_LEFT_THUMB: WORD = 0x0040;
+pub const XINPUT_GAMEPAD_RIGHT_THUMB: WORD = 0x0080;
+pub const XINPUT_GAMEPAD_LEFT_SHOULDER: WORD = 0x0100;
+pub const XINPUT_GAMEPAD_RIGHT_SHOULDER: WORD = 0x0200;
+pub const XINPUT_GAMEPAD_A: WORD = 0x1000;
+pub const XINPUT_GAMEPAD_B: WORD = 0x2000;
+pub const XINPUT_GAMEPAD_X: WORD = 0x4000;
+pub const XINPUT_GAMEPAD_Y: WORD = 0x8000;
+
+/// The recommended XInput deadzones, see [Getting Started With XInput](https://docs.microsoft.com/en-us/windows/win32/xinput/getting-started-with-xinput#dead-zone)
+pub const XINPUT_GAMEPAD_LEFT_THUMB_DEADZONE: SHORT = 7849;
+pub const XINPUT_GAMEPAD_RIGHT_THUMB_DEADZONE: SHORT = 8689;
+pub const XINPUT_GAMEPAD_TRIGGER_THRESHOLD: BYTE = 30;
+
+/// How many controllers XInput supports.
+pub const XUSER_MAX_COUNT: DWORD = 4;
+
+/// See [`timeBeginPeriod`](https://docs.microsoft.com/en-us/windows/win32/api/timeapi/nf-timeapi-timebeginperiod)
+pub const TIMERR_NOERROR: MMRESULT = 0;
+pub const TIMERR_NOCANDO: MMRESULT = 97;
+
+/// See [`VirtualAlloc`](https://docs.microsoft.com/en-us/windows/win32/api/memoryapi/nf-memoryapi-virtualalloc)
+pub const MEM_COMMIT: DWORD = 0x0000_1000;
+pub const MEM_RESERVE: DWORD = 0x0000_2000;
+/// See [`VirtualFree`](https://docs.microsoft.com/en-us/windows/win32/api/memoryapi/nf-memoryapi-virtualfree)
+pub const MEM_RELEASE: DWORD = 0x0000_8000;
+/// See [Memory Protection Constants](https://docs.microsoft.com/en-us/windows/win32/memory/memory-protection-constants)
+pub const PAGE_READWRITE: DWORD = 0x04;
+
+/// See [`WAVEFORMATEX`](https://docs.microsoft.com/en-us/windows/win32/api/mmeapi/ns-mmeapi-waveformatex)
+pub const WAVE_FORMAT_PCM: WORD = 1;
+
+/// DirectSound cooperative levels, see [`IDirectSound::SetCooperativeLevel`](https://docs.microsoft.com/en-us/previous-versions/windows/desktop/mt708925(v=vs.85))
+pub const DSSCL_NORMAL: DWORD = 0x00000001;
+pub const DSSCL_PRIORITY: DWORD = 0x00000002;
+
+/// DirectSound buffer capabilities, see [`DSBUFFERDESC`].
+pub const DSBCAPS_PRIMARYBUFFER: DWORD = 0x00000001;
+pub const DSBCAPS_GLOBALFOCUS: DWORD = 0x00008000;
+pub const DSBCAPS_GETCURRENTPOSITION2: DWORD = 0x00010000;
+
+/// See [`IDirectSoundBuffer::Play`](https://docs.microsoft.com/en-us/previous-versions/windows/desktop/mt708933(v=vs.85))
+pub const DSBPLAY_LOOPING: DWORD = 0x00000001;
+
+/// DirectSound return codes.
+pub const DS_OK: HRESULT = 0;
+pub const DSERR_BUFFERLOST: HRESULT = 0x88780096_u32 as HRESULT;
+
+/// Raw keyboard flags, see [`RAWKEYBOARD`].
+pub const RI_KEY_MAKE: USHORT = 0;
+pub const RI_KEY_BREAK: USHORT = 1;
+pub const RI_KEY_E0: USHORT = 2;
+pub const RI_KEY_E1: USHORT = 4;
+
+/// Virtual-Key Codes
+///
+/// The letters and digits use their ASCII uppercase code, `'A'` to `'Z'` and `'0'` to `'9'`.
+///
+/// See [Virtual-Key Codes](https://docs.microsoft.com/en-us/windows/win32/inputdev/virtual-key-codes)
+pub const VK_BACK: u32 = 0x08;
+pub const VK_TAB: u32 = 0x09;
+pub const VK_RETURN: u32 = 0x0D;
+pub const VK_SHIFT: u32 = 0x10;
+pub const VK_CONTROL: u32 = 0x11;
+pub const VK_MENU: u32 = 0x12;
+pub const VK_PAUSE: u32 = 0x13;
+pub const VK_CAPITAL: u32 = 0x14;
+pub const VK_ESCAPE: u32 = 0x1B;
+pub const VK_SPACE: u32 = 0x20;
+pub const VK_PRIOR: u32 = 0x21;
+pub const VK_NEXT: u32 = 0x22;
+pub const VK_END: u32 = 0x23;
+pub const VK_HOME: u32 = 0x24;
+pub const VK_LEFT: u32 = 0x25;
+pub const VK_UP: u32 = 0x26;
+pub const VK_RIGHT: u32 = 0x27;
+pub const VK_DOWN: u32 = 0x28;
+pub const VK_SNAPSHOT: u32 = 0x2C;
+pub const VK_INSERT: u32 = 0x2D;
+pub const VK_DELETE: u32 = 0x2E;
+pub const VK_LWIN: u32 = 0x5B;
+pub const VK_RWIN: u32 = 0x5C;
+pub const VK_APPS: u32 = 0x5D;
+pub const VK_NUMPAD0: u32 = 0x60;
+pub const VK_NUMPAD9: u32 = 0x69;
+pub const VK_MULTIPLY: u32 = 0x6A;
+pub const VK_ADD: u32 = 0x6B;
+pub const VK_SEPARATOR: u32 = 0x6C;
+pub const VK_SUBTRACT: u32 = 0x6D;
+pub const VK_DECIMAL: u32 = 0x6E;
+pub const VK_DIVIDE: u32 = 0x6F;
+pub const VK_F1: u32 = 0x70;
+pub const VK_F12: u32 = 0x7B;
+pub const VK_F24: u32 = 0x87;
+pub const VK_NUMLOCK: u32 = 0x90;
+pub const VK_SCROLL: u32 = 0x91;
+pub const VK_LSHIFT: u32 = 0xA0;
+pub const VK_RSHIFT: u32 = 0xA1;
+pub const VK_LCONTROL: u32 = 0xA2;
+pub const VK_RCONTROL: u32 = 0xA3;
+pub const VK_LMENU: u32 = 0xA4;
+pub const VK_RMENU: u32 = 0xA5;
+/// `;:` on US keyboards.
+pub const VK_OEM_1: u32 = 0xBA;
+pub const VK_OEM_PLUS: u32 = 0xBB;
+pub const VK_OEM_COMMA: u32 = 0xBC;
+pub const VK_OEM_MINUS: u32 = 0xBD;
+pub const VK_OEM_PERIOD: u32 = 0xBE;
+/// `/?` on US keyboards.
+pub const VK_OEM_2: u32 = 0xBF;
+/// `` `~ `` on US keyboards.
+pub const VK_OEM_3: u32 = 0xC0;
+/// `[{` on US keyboards.
+pub const VK_OEM_4: u32 = 0xDB;
+/// `\|` on US keyboards.
+pub const VK_OEM_5: u32 = 0xDC;
+/// `]}` on US keyboards.
+pub const VK_OEM_6: u32 = 0xDD;
+/// `'"` on US keyboards.
+pub const VK_OEM_7: u32 = 0xDE;
+
 /// Window Styles
 pub const WS_OVERLAPPED: u32 = 0x00000000;
 pub const WS_POPUP: u32 = 0x80000000;
@@ -149,11 +428,26 @@ pub const WS_POPUPWINDOW: u32 = WS_POPUP | WS_BORDER | WS_SYSMENU;
 
 pub const WS_CHILDWINDOW: u32 = WS_CHILD;
 
+/// See [Extended Window Styles](https://docs.microsoft.com/en-us/windows/win32/winmsg/extended-window-styles)
+pub const WS_EX_TOPMOST: u32 = 0x00000008;
+pub const WS_EX_TRANSPARENT: u32 = 0x00000020;
+pub const WS_EX_TOOLWINDOW: u32 = 0x00000080;
+pub const WS_EX_WINDOWEDGE: u32 = 0x00000100;
+pub const WS_EX_CLIENTEDGE: u32 = 0x00000200;
+pub const WS_EX_APPWINDOW: u32 = 0x00040000;
+pub const WS_EX_LAYERED: u32 = 0x00080000;
+pub const WS_EX_NOACTIVATE: u32 = 0x08000000;
+
+/// See [`SetLayeredWindowAttributes`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setlayeredwindowattributes)
+pub const LWA_COLORKEY: DWORD = 0x00000001;
+pub const LWA_ALPHA: DWORD = 0x00000002;
+
 pub const CW_USEDEFAULT: c_int = 0x80000000_u32 as c_int;
 
 pub const SW_SHOW: c_int = 5;
 
 pub const IDC_ARROW: LPCWSTR = MAKEINTRESOURCE(32512);
+pub const IDI_APPLICATION: LPCWSTR = MAKEINTRESOURCE(32512);
 
 /// See [`GetSysColor`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getsyscolor)
 pub enum SysColor {
@@ -197,6 +491,136 @@ pub const MB_OKCANCEL: u32 = 0x00000001;
 pub const IDOK: c_int = 1;
 
 pub const GWLP_USERDATA: c_int = -21;
+pub const GWL_STYLE: c_int = -16;
+pub const GWL_EXSTYLE: c_int = -20;
+
+/// See [`SetWindowPos`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setwindowpos)
+pub const SWP_NOSIZE: UINT = 0x0001;
+pub const SWP_NOMOVE: UINT = 0x0002;
+pub const SWP_NOZORDER: UINT = 0x0004;
+pub const SWP_NOACTIVATE: UINT = 0x0010;
+pub const SWP_FRAMECHANGED: UINT = 0x0020;
+pub const SWP_NOOWNERZORDER: UINT = 0x0200;
+/// Places the window at the top of the Z order, see [`SetWindowPos`].
+pub const HWND_TOP: HWND = core::ptr::null_mut();
+
+/// See [`MonitorFromWindow`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-monitorfromwindow)
+pub const MONITOR_DEFAULTTONULL: DWORD = 0x00000000;
+pub const MONITOR_DEFAULTTOPRIMARY: DWORD = 0x00000001;
+pub const MONITOR_DEFAULTTONEAREST: DWORD = 0x00000002;
+/// See [`EnumDisplayMonitors`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-enumdisplaymonitors)
+pub type MONITORENUMPROC = Option<
+    unsafe extern "system" fn(
+        hMonitor: HMONITOR,
+        hdcMonitor: HDC,
+        lprcMonitor: LPRECT,
+        dwData: LPARAM,
+    ) -> BOOL,
+>;
+/// See [`GetDpiForMonitor`](https://docs.microsoft.com/en-us/windows/win32/api/shellscalingapi/nf-shellscalingapi-getdpiformonitor)
+pub type MONITOR_DPI_TYPE = c_int;
+pub const MDT_EFFECTIVE_DPI: MONITOR_DPI_TYPE = 0;
+/// The primary monitor, in [`MONITORINFO::dwFlags`].
+pub const MONITORINFOF_PRIMARY: DWORD = 0x00000001;
+/// The length of [`MONITORINFOEXW::szDevice`] and [`DEVMODEW::dmDeviceName`].
+pub const CCHDEVICENAME: usize = 32;
+/// The length of [`DEVMODEW::dmFormName`].
+pub const CCHFORMNAME: usize = 32;
+
+/// See [`EnumDisplaySettingsW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-enumdisplaysettingsw)
+pub const ENUM_CURRENT_SETTINGS: DWORD = -1_i32 as DWORD;
+pub const ENUM_REGISTRY_SETTINGS: DWORD = -2_i32 as DWORD;
+
+/// The fields of [`DEVMODEW`] that are set, in `dmFields`.
+pub const DM_POSITION: DWORD = 0x00000020;
+pub const DM_BITSPERPEL: DWORD = 0x00040000;
+pub const DM_PELSWIDTH: DWORD = 0x00080000;
+pub const DM_PELSHEIGHT: DWORD = 0x00100000;
+pub const DM_DISPLAYFREQUENCY: DWORD = 0x00400000;
+
+/// See [`ChangeDisplaySettingsExW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-changedisplaysettingsexw)
+pub const CDS_FULLSCREEN: DWORD = 0x00000004;
+pub const DISP_CHANGE_SUCCESSFUL: LONG = 0;
+pub const DISP_CHANGE_RESTART: LONG = 1;
+pub const DISP_CHANGE_FAILED: LONG = -1;
+pub const DISP_CHANGE_BADMODE: LONG = -2;
+
+/// See [`GetDpiForWindow`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getdpiforwindow)
+pub const USER_DEFAULT_SCREEN_DPI: UINT = 96;
+
+/// See [`DPI_AWARENESS_CONTEXT`](https://docs.microsoft.com/en-us/windows/win32/hidpi/dpi-awareness-context)
+pub type DPI_AWARENESS_CONTEXT = HANDLE;
+pub type HMONITOR = HANDLE;
+/// A `0x00bbggrr` color.
+pub type COLORREF = DWORD;
+pub const DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2: DPI_AWARENESS_CONTEXT =
+    -4_isize as DPI_AWARENESS_CONTEXT;
+
+/// Messages are not removed from the queue after processing by `PeekMessageW`.
+pub const PM_NOREMOVE: u32 = 0x0000;
+/// Messages are removed from the queue after processing by `PeekMessageW`.
+pub const PM_REMOVE: u32 = 0x0001;
+/// Prevents the system from releasing any thread that is waiting for the caller to go idle.
+/// Combine with [`PM_NOREMOVE`] or [`PM_REMOVE`].
+pub const PM_NOYIELD: u32 = 0x0002;
+
+/// An uncompressed bitmap, see [`BITMAPINFOHEADER`].
+pub const BI_RGB: DWORD = 0;
+/// The color table of a [`BITMAPINFO`] contains literal RGB values.
+pub const DIB_RGB_COLORS: UINT = 0;
+/// Copies the source rectangle directly to the destination rectangle.
+pub const SRCCOPY: DWORD = 0x00CC0020;
+
+/// Pixel Format Descriptor Flags
+///
+/// See [`PIXELFORMATDESCRIPTOR`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-pixelformatdescriptor)
+pub const PFD_DOUBLEBUFFER: DWORD = 0x00000001;
+pub const PFD_STEREO: DWORD = 0x00000002;
+pub const PFD_DRAW_TO_WINDOW: DWORD = 0x00000004;
+pub const PFD_DRAW_TO_BITMAP: DWORD = 0x00000008;
+pub const PFD_SUPPORT_GDI: DWORD = 0x00000010;
+pub const PFD_SUPPORT_OPENGL: DWORD = 0x00000020;
+pub const PFD_GENERIC_FORMAT: DWORD = 0x00000040;
+pub const PFD_DEPTH_DONTCARE: DWORD = 0x20000000;
+pub const PFD_DOUBLEBUFFER_DONTCARE: DWORD = 0x40000000;
+pub const PFD_STEREO_DONTCARE: DWORD = 0x80000000;
+
+/// RGBA pixels, see [`PIXELFORMATDESCRIPTOR::iPixelType`].
+pub const PFD_TYPE_RGBA: BYTE = 0;
+/// Color-index pixels, see [`PIXELFORMATDESCRIPTOR::iPixelType`].
+pub const PFD_TYPE_COLORINDEX: BYTE = 1;
+/// The main plane, see [`PIXELFORMATDESCRIPTOR::iLayerType`].
+pub const PFD_MAIN_PLANE: BYTE = 0;
+
+/// WGL_ARB_create_context
+///
+/// See the [extension spec](https://www.khronos.org/registry/OpenGL/extensions/ARB/WGL_ARB_create_context.txt)
+pub const WGL_CONTEXT_MAJOR_VERSION_ARB: c_int = 0x2091;
+pub const WGL_CONTEXT_MINOR_VERSION_ARB: c_int = 0x2092;
+pub const WGL_CONTEXT_LAYER_PLANE_ARB: c_int = 0x2093;
+pub const WGL_CONTEXT_FLAGS_ARB: c_int = 0x2094;
+pub const WGL_CONTEXT_PROFILE_MASK_ARB: c_int = 0x9126;
+pub const WGL_CONTEXT_DEBUG_BIT_ARB: c_int = 0x0001;
+pub const WGL_CONTEXT_FORWARD_COMPATIBLE_BIT_ARB: c_int = 0x0002;
+pub const WGL_CONTEXT_CORE_PROFILE_BIT_ARB: c_int = 0x00000001;
+pub const WGL_CONTEXT_COMPATIBILITY_PROFILE_BIT_ARB: c_int = 0x00000002;
+pub const ERROR_INVALID_VERSION_ARB: DWORD = 0x2095;
+pub const ERROR_INVALID_PROFILE_ARB: DWORD = 0x2096;
+
+/// [`wglCreateContextAttribsARB`](https://www.khronos.org/registry/OpenGL/extensions/ARB/WGL_ARB_create_context.txt),
+/// loaded through `wglGetProcAddress`.
+pub type wglCreateContextAttribsARB_t = Option<
+    unsafe extern "system" fn(hDC: HDC, hShareContext: HGLRC, attribList: *const c_int) -> HGLRC,
+>;
+
+/// See [System Error Codes](https://docs.microsoft.com/en-us/windows/win32/debug/system-error-codes--1300-1699-)
+pub const ERROR_CLASS_ALREADY_EXISTS: DWORD = 1410;
+pub const ERROR_CLASS_DOES_NOT_EXIST: DWORD = 1411;
+pub const ERROR_CLASS_HAS_WINDOWS: DWORD = 1412;
+pub const ERROR_INSUFFICIENT_BUFFER: DWORD = 122;
+pub const ERROR_SUCCESS: DWORD = 0;
+pub const ERROR_DEVICE_NOT_CONNECTED: DWORD = 1167;
+pub const ERROR_INVALID_PARAMETER: DWORD = 87;
 
 pub const FORMAT_MESSAGE_ALLOCATE_BUFFER: u32 = 0x00000100;
 pub const FORMAT_MESSAGE_FROM_SYSTEM: u32 = 0x00001000;
@@ -282,7 +706,32 @@ pub struct WNDCLASSW {
 }
 
 impl Default for WNDCLASSW {
-    #[must_use]
+    fn default() -> Self {
+        unsafe { core::mem::zeroed() }
+    }
+}
+
+/// [`WNDCLASSEXW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-wndclassexw)
+#[repr(C)]
+pub struct WNDCLASSEXW {
+    /// Must be set to the size of the struct.
+    pub cbSize: UINT,
+    /// The class style(s). This member can be any combination of the [Class Styles](https://docs.microsoft.com/en-us/windows/win32/winmsg/about-window-classes).
+    pub style: UINT,
+    pub lpfnWndProc: WNDPROC,
+    pub cbClsExtra: c_int,
+    pub cbWndExtra: c_int,
+    pub hInstance: HINSTANCE,
+    pub hIcon: HICON,
+    pub hCursor: HCURSOR,
+    pub hbrBackground: HBRUSH,
+    pub lpszMenuName: LPCWSTR,
+    pub lpszClassName: LPCWSTR,
+    /// The icon of the title bar and the taskbar, derived from `hIcon` when null.
+    pub hIconSm: HICON,
+}
+
+impl Default for WNDCLASSEXW {
     fn default() -> Self {
         unsafe { core::mem::zeroed() }
     }
@@ -292,7 +741,6 @@ macro_rules! unsafe_impl_default_zeroed {
     ($t:ty) => {
         impl Default for $t {
             #[inline]
-            #[must_use]
             fn default() -> Self {
                 unsafe { core::mem::zeroed() }
             }
@@ -319,6 +767,19 @@ pub struct MSG {
 }
 unsafe_impl_default_zeroed!(MSG);
 
+/// See [`MINMAXINFO`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-minmaxinfo)
+#[repr(C)]
+pub struct MINMAXINFO {
+    pub ptReserved: POINT,
+    pub ptMaxSize: POINT,
+    pub ptMaxPosition: POINT,
+    /// The smallest the window can be resized to, frame included.
+    pub ptMinTrackSize: POINT,
+    /// The largest the window can be resized to, frame included.
+    pub ptMaxTrackSize: POINT,
+}
+unsafe_impl_default_zeroed!(MINMAXINFO);
+
 /// A pointer to a RECT structure that contains the logical coordinates of the rectangle to be filled
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
@@ -330,6 +791,81 @@ pub struct RECT {
 }
 unsafe_impl_default_zeroed!(RECT);
 
+/// See [`WINDOWPLACEMENT`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-windowplacement)
+#[repr(C)]
+pub struct WINDOWPLACEMENT {
+    /// Must be set to the size of the struct.
+    pub length: UINT,
+    pub flags: UINT,
+    pub showCmd: UINT,
+    pub ptMinPosition: POINT,
+    pub ptMaxPosition: POINT,
+    /// Where the window is when restored, in workspace coordinates.
+    pub rcNormalPosition: RECT,
+}
+unsafe_impl_default_zeroed!(WINDOWPLACEMENT);
+
+/// See [`MONITORINFO`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-monitorinfo)
+#[repr(C)]
+pub struct MONITORINFO {
+    /// Must be set to the size of the struct, or of [`MONITORINFOEXW`].
+    pub cbSize: DWORD,
+    /// The monitor, in virtual screen coordinates.
+    pub rcMonitor: RECT,
+    /// The monitor without the taskbar and the docked toolbars.
+    pub rcWork: RECT,
+    pub dwFlags: DWORD,
+}
+unsafe_impl_default_zeroed!(MONITORINFO);
+
+/// See [`MONITORINFOEXW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-monitorinfoexw)
+#[repr(C)]
+pub struct MONITORINFOEXW {
+    pub monitorInfo: MONITORINFO,
+    /// The name of the display device, e.g. `\\.\DISPLAY1`.
+    pub szDevice: [WCHAR; CCHDEVICENAME],
+}
+unsafe_impl_default_zeroed!(MONITORINFOEXW);
+
+/// See [`DEVMODEW`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-devmodew)
+///
+/// The unions are declared as their display variants, the printer ones are never used here.
+#[repr(C)]
+pub struct DEVMODEW {
+    pub dmDeviceName: [WCHAR; CCHDEVICENAME],
+    pub dmSpecVersion: WORD,
+    pub dmDriverVersion: WORD,
+    /// Must be set to the size of the struct.
+    pub dmSize: WORD,
+    pub dmDriverExtra: WORD,
+    /// Which of the fields are set, `DM_*`.
+    pub dmFields: DWORD,
+    pub dmPosition: POINT,
+    pub dmDisplayOrientation: DWORD,
+    pub dmDisplayFixedOutput: DWORD,
+    pub dmColor: SHORT,
+    pub dmDuplex: SHORT,
+    pub dmYResolution: SHORT,
+    pub dmTTOption: SHORT,
+    pub dmCollate: SHORT,
+    pub dmFormName: [WCHAR; CCHFORMNAME],
+    pub dmLogPixels: WORD,
+    pub dmBitsPerPel: DWORD,
+    pub dmPelsWidth: DWORD,
+    pub dmPelsHeight: DWORD,
+    pub dmDisplayFlags: DWORD,
+    pub dmDisplayFrequency: DWORD,
+    pub dmICMMethod: DWORD,
+    pub dmICMIntent: DWORD,
+    pub dmMediaType: DWORD,
+    pub dmDitherType: DWORD,
+    pub dmReserved1: DWORD,
+    pub dmReserved2: DWORD,
+    pub dmPanningWidth: DWORD,
+    pub dmPanningHeight: DWORD,
+}
+unsafe_impl_default_zeroed!(DEVMODEW);
+
 /// See [`PAINTSTRUCT`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-paintstruct)
 #[repr(C)]
 pub struct PAINTSTRUCT {
@@ -367,6 +903,471 @@ pub struct CREATESTRUCTW {
 }
 unsafe_impl_default_zeroed!(CREATESTRUCTW);
 
+/// See [`TRACKMOUSEEVENT`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-trackmouseevent)
+#[repr(C)]
+pub struct TRACKMOUSEEVENT {
+    pub cbSize: DWORD,
+    /// A combination of the `TME_*` flags.
+    pub dwFlags: DWORD,
+    pub hwndTrack: HWND,
+    pub dwHoverTime: DWORD,
+}
+unsafe_impl_default_zeroed!(TRACKMOUSEEVENT);
+pub type LPTRACKMOUSEEVENT = *mut TRACKMOUSEEVENT;
+
+/// See [`RAWINPUTDEVICE`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rawinputdevice)
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RAWINPUTDEVICE {
+    /// One of the `HID_USAGE_PAGE_*` constants.
+    pub usUsagePage: USHORT,
+    /// One of the `HID_USAGE_*` constants, within the usage page.
+    pub usUsage: USHORT,
+    /// A combination of the `RIDEV_*` flags.
+    pub dwFlags: DWORD,
+    /// The window receiving the input, or null for the one with the keyboard focus.
+    pub hwndTarget: HWND,
+}
+unsafe_impl_default_zeroed!(RAWINPUTDEVICE);
+pub type PCRAWINPUTDEVICE = *const RAWINPUTDEVICE;
+
+/// See [`RAWINPUTHEADER`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rawinputheader)
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RAWINPUTHEADER {
+    /// [`RIM_TYPEMOUSE`], [`RIM_TYPEKEYBOARD`] or [`RIM_TYPEHID`].
+    pub dwType: DWORD,
+    /// The size of the whole [`RAWINPUT`], in bytes.
+    pub dwSize: DWORD,
+    pub hDevice: HANDLE,
+    /// The `wParam` of the `WM_INPUT` message.
+    pub wParam: WPARAM,
+}
+unsafe_impl_default_zeroed!(RAWINPUTHEADER);
+
+/// See [`RAWMOUSE`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rawmouse)
+///
+/// The C struct has a `ulButtons` union member overlapping `usButtonFlags` and `usButtonData`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RAWMOUSE {
+    /// A combination of the `MOUSE_MOVE_*` flags.
+    pub usFlags: USHORT,
+    /// Padding, the union that follows is aligned like `ulButtons`.
+    pub _padding: USHORT,
+    /// A combination of the `RI_MOUSE_*` flags.
+    pub usButtonFlags: USHORT,
+    /// The signed wheel rotation, when `usButtonFlags` has [`RI_MOUSE_WHEEL`] or
+    /// [`RI_MOUSE_HWHEEL`].
+    pub usButtonData: USHORT,
+    pub ulRawButtons: ULONG,
+    /// The motion along x, relative unless `usFlags` has [`MOUSE_MOVE_ABSOLUTE`].
+    pub lLastX: LONG,
+    pub lLastY: LONG,
+    pub ulExtraInformation: ULONG,
+}
+unsafe_impl_default_zeroed!(RAWMOUSE);
+
+/// See [`RAWKEYBOARD`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rawkeyboard)
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RAWKEYBOARD {
+    /// The scancode.
+    pub MakeCode: USHORT,
+    /// A combination of the `RI_KEY_*` flags.
+    pub Flags: USHORT,
+    pub Reserved: USHORT,
+    /// The virtual-key code.
+    pub VKey: USHORT,
+    /// The keystroke message it stands for, `WM_KEYDOWN`, ...
+    pub Message: UINT,
+    pub ExtraInformation: ULONG,
+}
+unsafe_impl_default_zeroed!(RAWKEYBOARD);
+
+/// See [`RAWHID`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rawhid)
+///
+/// `bRawData` is a variable length array, `dwSizeHid * dwCount` bytes long.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RAWHID {
+    pub dwSizeHid: DWORD,
+    pub dwCount: DWORD,
+    pub bRawData: [BYTE; 1],
+}
+unsafe_impl_default_zeroed!(RAWHID);
+
+/// The anonymous union of [`RAWINPUT`], read the member matching `header.dwType`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union RAWINPUT_data {
+    pub mouse: RAWMOUSE,
+    pub keyboard: RAWKEYBOARD,
+    pub hid: RAWHID,
+}
+
+/// See [`RAWINPUT`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-rawinput)
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct RAWINPUT {
+    pub header: RAWINPUTHEADER,
+    pub data: RAWINPUT_data,
+}
+unsafe_impl_default_zeroed!(RAWINPUT);
+
+/// See [`XINPUT_GAMEPAD`](https://docs.microsoft.com/en-us/windows/win32/api/xinput/ns-xinput-xinput_gamepad)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XINPUT_GAMEPAD {
+    /// A combination of the `XINPUT_GAMEPAD_*` buttons.
+    pub wButtons: WORD,
+    pub bLeftTrigger: BYTE,
+    pub bRightTrigger: BYTE,
+    pub sThumbLX: SHORT,
+    pub sThumbLY: SHORT,
+    pub sThumbRX: SHORT,
+    pub sThumbRY: SHORT,
+}
+unsafe_impl_default_zeroed!(XINPUT_GAMEPAD);
+
+/// See [`XINPUT_STATE`](https://docs.microsoft.com/en-us/windows/win32/api/xinput/ns-xinput-xinput_state)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XINPUT_STATE {
+    /// Changes whenever the state of the controller does.
+    pub dwPacketNumber: DWORD,
+    pub Gamepad: XINPUT_GAMEPAD,
+}
+unsafe_impl_default_zeroed!(XINPUT_STATE);
+
+/// See [`XINPUT_VIBRATION`](https://docs.microsoft.com/en-us/windows/win32/api/xinput/ns-xinput-xinput_vibration)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XINPUT_VIBRATION {
+    /// The low frequency motor.
+    pub wLeftMotorSpeed: WORD,
+    /// The high frequency motor.
+    pub wRightMotorSpeed: WORD,
+}
+unsafe_impl_default_zeroed!(XINPUT_VIBRATION);
+
+/// [`XInputGetState`](https://docs.microsoft.com/en-us/windows/win32/api/xinput/nf-xinput-xinputgetstate),
+/// loaded at runtime since the DLL it lives in depends on the Windows version.
+pub type XInputGetState_t =
+    unsafe extern "system" fn(dwUserIndex: DWORD, pState: *mut XINPUT_STATE) -> DWORD;
+/// [`XInputSetState`](https://docs.microsoft.com/en-us/windows/win32/api/xinput/nf-xinput-xinputsetstate),
+/// loaded at runtime since the DLL it lives in depends on the Windows version.
+pub type XInputSetState_t =
+    unsafe extern "system" fn(dwUserIndex: DWORD, pVibration: *mut XINPUT_VIBRATION) -> DWORD;
+
+/// See [`GUID`](https://docs.microsoft.com/en-us/windows/win32/api/guiddef/ns-guiddef-guid)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GUID {
+    pub Data1: c_ulong,
+    pub Data2: c_ushort,
+    pub Data3: c_ushort,
+    pub Data4: [BYTE; 8],
+}
+unsafe_impl_default_zeroed!(GUID);
+
+/// See [`WAVEFORMATEX`](https://docs.microsoft.com/en-us/windows/win32/api/mmeapi/ns-mmeapi-waveformatex)
+///
+/// Declared with 1 byte packing in `mmreg.h`.
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct WAVEFORMATEX {
+    /// [`WAVE_FORMAT_PCM`], ...
+    pub wFormatTag: WORD,
+    pub nChannels: WORD,
+    pub nSamplesPerSec: DWORD,
+    pub nAvgBytesPerSec: DWORD,
+    /// The size of a frame, in bytes.
+    pub nBlockAlign: WORD,
+    pub wBitsPerSample: WORD,
+    /// The size of the extra format information following the struct, 0 for PCM.
+    pub cbSize: WORD,
+}
+unsafe_impl_default_zeroed!(WAVEFORMATEX);
+
+/// See [`DSBUFFERDESC`](https://docs.microsoft.com/en-us/previous-versions/windows/desktop/ee416820(v=vs.85))
+#[repr(C)]
+pub struct DSBUFFERDESC {
+    pub dwSize: DWORD,
+    /// A combination of the `DSBCAPS_*` flags.
+    pub dwFlags: DWORD,
+    /// 0 for the primary buffer.
+    pub dwBufferBytes: DWORD,
+    pub dwReserved: DWORD,
+    /// Null for the primary buffer.
+    pub lpwfxFormat: *mut WAVEFORMATEX,
+    pub guid3DAlgorithm: GUID,
+}
+unsafe_impl_default_zeroed!(DSBUFFERDESC);
+
+/// The `IDirectSound` COM interface, only ever used through pointers.
+///
+/// See [`IDirectSound8`](https://docs.microsoft.com/en-us/previous-versions/windows/desktop/ee418035(v=vs.85))
+#[repr(C)]
+pub struct IDirectSound {
+    pub lpVtbl: *const IDirectSoundVtbl,
+}
+
+/// The methods of [`IDirectSound`], in declaration order. The unused ones are left untyped.
+#[repr(C)]
+pub struct IDirectSoundVtbl {
+    pub QueryInterface: usize,
+    pub AddRef: unsafe extern "system" fn(This: *mut IDirectSound) -> ULONG,
+    pub Release: unsafe extern "system" fn(This: *mut IDirectSound) -> ULONG,
+    pub CreateSoundBuffer: unsafe extern "system" fn(
+        This: *mut IDirectSound,
+        pcDSBufferDesc: *const DSBUFFERDESC,
+        ppDSBuffer: *mut *mut IDirectSoundBuffer,
+        pUnkOuter: *mut c_void,
+    ) -> HRESULT,
+    pub GetCaps: usize,
+    pub DuplicateSoundBuffer: usize,
+    pub SetCooperativeLevel:
+        unsafe extern "system" fn(This: *mut IDirectSound, hwnd: HWND, dwLevel: DWORD) -> HRESULT,
+    pub Compact: usize,
+    pub GetSpeakerConfig: usize,
+    pub SetSpeakerConfig: usize,
+    pub Initialize: usize,
+}
+
+/// The `IDirectSoundBuffer` COM interface, only ever used through pointers.
+///
+/// See [`IDirectSoundBuffer8`](https://docs.microsoft.com/en-us/previous-versions/windows/desktop/ee418055(v=vs.85))
+#[repr(C)]
+pub struct IDirectSoundBuffer {
+    pub lpVtbl: *const IDirectSoundBufferVtbl,
+}
+
+/// The methods of [`IDirectSoundBuffer`], in declaration order. The unused ones are left untyped.
+#[repr(C)]
+pub struct IDirectSoundBufferVtbl {
+    pub QueryInterface: usize,
+    pub AddRef: unsafe extern "system" fn(This: *mut IDirectSoundBuffer) -> ULONG,
+    pub Release: unsafe extern "system" fn(This: *mut IDirectSoundBuffer) -> ULONG,
+    pub GetCaps: usize,
+    pub GetCurrentPosition: unsafe extern "system" fn(
+        This: *mut IDirectSoundBuffer,
+        pdwCurrentPlayCursor: *mut DWORD,
+        pdwCurrentWriteCursor: *mut DWORD,
+    ) -> HRESULT,
+    pub GetFormat: usize,
+    pub GetVolume: usize,
+    pub GetPan: usize,
+    pub GetFrequency: usize,
+    pub GetStatus: usize,
+    pub Initialize: usize,
+    pub Lock: unsafe extern "system" fn(
+        This: *mut IDirectSoundBuffer,
+        dwOffset: DWORD,
+        dwBytes: DWORD,
+        ppvAudioPtr1: *mut LPVOID,
+        pdwAudioBytes1: *mut DWORD,
+        ppvAudioPtr2: *mut LPVOID,
+        pdwAudioBytes2: *mut DWORD,
+        dwFlags: DWORD,
+    ) -> HRESULT,
+    pub Play: unsafe extern "system" fn(
+        This: *mut IDirectSoundBuffer,
+        dwReserved1: DWORD,
+        dwPriority: DWORD,
+        dwFlags: DWORD,
+    ) -> HRESULT,
+    pub SetCurrentPosition: usize,
+    pub SetFormat: unsafe extern "system" fn(
+        This: *mut IDirectSoundBuffer,
+        pcfxFormat: *const WAVEFORMATEX,
+    ) -> HRESULT,
+    pub SetVolume: usize,
+    pub SetPan: usize,
+    pub SetFrequency: usize,
+    pub Stop: unsafe extern "system" fn(This: *mut IDirectSoundBuffer) -> HRESULT,
+    pub Unlock: unsafe extern "system" fn(
+        This: *mut IDirectSoundBuffer,
+        pvAudioPtr1: LPVOID,
+        dwAudioBytes1: DWORD,
+        pvAudioPtr2: LPVOID,
+        dwAudioBytes2: DWORD,
+    ) -> HRESULT,
+    pub Restore: unsafe extern "system" fn(This: *mut IDirectSoundBuffer) -> HRESULT,
+}
+
+/// [`SetProcessDpiAwarenessContext`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setprocessdpiawarenesscontext),
+/// loaded at runtime since it only exists from Windows 10 1703 on.
+pub type SetProcessDpiAwarenessContext_t =
+    unsafe extern "system" fn(value: DPI_AWARENESS_CONTEXT) -> BOOL;
+/// [`GetDpiForWindow`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getdpiforwindow),
+/// loaded at runtime since it only exists from Windows 10 1607 on.
+pub type GetDpiForWindow_t = unsafe extern "system" fn(hwnd: HWND) -> UINT;
+/// [`AdjustWindowRectExForDpi`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-adjustwindowrectexfordpi),
+/// loaded at runtime since it only exists from Windows 10 1607 on.
+pub type AdjustWindowRectExForDpi_t = unsafe extern "system" fn(
+    lpRect: *mut RECT,
+    dwStyle: DWORD,
+    bMenu: BOOL,
+    dwExStyle: DWORD,
+    dpi: UINT,
+) -> BOOL;
+
+/// [`GetDpiForMonitor`](https://docs.microsoft.com/en-us/windows/win32/api/shellscalingapi/nf-shellscalingapi-getdpiformonitor),
+/// loaded at runtime from `shcore.dll`, which only exists from Windows 8.1 on.
+pub type GetDpiForMonitor_t = unsafe extern "system" fn(
+    hmonitor: HMONITOR,
+    dpiType: MONITOR_DPI_TYPE,
+    dpiX: *mut UINT,
+    dpiY: *mut UINT,
+) -> HRESULT;
+
+/// [`DirectSoundCreate`](https://docs.microsoft.com/en-us/previous-versions/windows/desktop/mt708921(v=vs.85)),
+/// loaded at runtime from `dsound.dll`.
+pub type DirectSoundCreate_t = unsafe extern "system" fn(
+    pcGuidDevice: *const GUID,
+    ppDS: *mut *mut IDirectSound,
+    pUnkOuter: *mut c_void,
+) -> HRESULT;
+
+/// See [`BITMAPINFOHEADER`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-bitmapinfoheader)
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct BITMAPINFOHEADER {
+    /// The number of bytes required by the structure.
+    pub biSize: DWORD,
+    pub biWidth: LONG,
+    /// Positive for a bottom-up bitmap, negative for a top-down one.
+    pub biHeight: LONG,
+    /// Must be 1.
+    pub biPlanes: WORD,
+    pub biBitCount: WORD,
+    pub biCompression: DWORD,
+    pub biSizeImage: DWORD,
+    pub biXPelsPerMeter: LONG,
+    pub biYPelsPerMeter: LONG,
+    pub biClrUsed: DWORD,
+    pub biClrImportant: DWORD,
+}
+unsafe_impl_default_zeroed!(BITMAPINFOHEADER);
+
+/// See [`RGBQUAD`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-rgbquad)
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RGBQUAD {
+    pub rgbBlue: BYTE,
+    pub rgbGreen: BYTE,
+    pub rgbRed: BYTE,
+    pub rgbReserved: BYTE,
+}
+unsafe_impl_default_zeroed!(RGBQUAD);
+
+/// See [`BITMAPINFO`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-bitmapinfo)
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct BITMAPINFO {
+    pub bmiHeader: BITMAPINFOHEADER,
+    /// Unused for 32 bits per pixel `BI_RGB` bitmaps.
+    pub bmiColors: [RGBQUAD; 1],
+}
+unsafe_impl_default_zeroed!(BITMAPINFO);
+
+/// See [`PIXELFORMATDESCRIPTOR`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-pixelformatdescriptor)
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PIXELFORMATDESCRIPTOR {
+    /// The size of the structure, in bytes.
+    pub nSize: WORD,
+    /// Must be 1.
+    pub nVersion: WORD,
+    /// A combination of the `PFD_*` flags.
+    pub dwFlags: DWORD,
+    /// [`PFD_TYPE_RGBA`] or [`PFD_TYPE_COLORINDEX`].
+    pub iPixelType: BYTE,
+    /// The number of color bitplanes, excluding the alpha ones.
+    pub cColorBits: BYTE,
+    pub cRedBits: BYTE,
+    pub cRedShift: BYTE,
+    pub cGreenBits: BYTE,
+    pub cGreenShift: BYTE,
+    pub cBlueBits: BYTE,
+    pub cBlueShift: BYTE,
+    pub cAlphaBits: BYTE,
+    pub cAlphaShift: BYTE,
+    pub cAccumBits: BYTE,
+    pub cAccumRedBits: BYTE,
+    pub cAccumGreenBits: BYTE,
+    pub cAccumBlueBits: BYTE,
+    pub cAccumAlphaBits: BYTE,
+    pub cDepthBits: BYTE,
+    pub cStencilBits: BYTE,
+    pub cAuxBuffers: BYTE,
+    /// Ignored, kept for compatibility.
+    pub iLayerType: BYTE,
+    pub bReserved: BYTE,
+    pub dwLayerMask: DWORD,
+    pub dwVisibleMask: DWORD,
+    pub dwDamageMask: DWORD,
+}
+unsafe_impl_default_zeroed!(PIXELFORMATDESCRIPTOR);
+
+// The layouts have to match the C headers on every target, check it on every build.
+const _: () = {
+    use core::mem::{offset_of, size_of};
+    assert!(size_of::<BITMAPINFOHEADER>() == 40);
+    assert!(offset_of!(BITMAPINFOHEADER, biWidth) == 4);
+    assert!(offset_of!(BITMAPINFOHEADER, biPlanes) == 12);
+    assert!(offset_of!(BITMAPINFOHEADER, biBitCount) == 14);
+    assert!(offset_of!(BITMAPINFOHEADER, biCompression) == 16);
+    assert!(offset_of!(BITMAPINFOHEADER, biClrImportant) == 36);
+    assert!(size_of::<RGBQUAD>() == 4);
+    assert!(size_of::<BITMAPINFO>() == 44);
+    assert!(offset_of!(BITMAPINFO, bmiColors) == 40);
+    assert!(size_of::<PIXELFORMATDESCRIPTOR>() == 40);
+    assert!(offset_of!(PIXELFORMATDESCRIPTOR, dwFlags) == 4);
+    assert!(offset_of!(PIXELFORMATDESCRIPTOR, cDepthBits) == 23);
+    assert!(offset_of!(PIXELFORMATDESCRIPTOR, dwLayerMask) == 28);
+    assert!(size_of::<RAWMOUSE>() == 24);
+    assert!(offset_of!(RAWMOUSE, usButtonFlags) == 4);
+    assert!(offset_of!(RAWMOUSE, lLastX) == 12);
+    assert!(size_of::<RAWKEYBOARD>() == 16);
+    assert!(size_of::<XINPUT_GAMEPAD>() == 12);
+    assert!(size_of::<XINPUT_STATE>() == 16);
+    assert!(offset_of!(XINPUT_STATE, Gamepad) == 4);
+    assert!(size_of::<GUID>() == 16);
+    assert!(size_of::<WAVEFORMATEX>() == 18);
+    assert!(offset_of!(WAVEFORMATEX, nBlockAlign) == 12);
+    assert!(size_of::<MINMAXINFO>() == 40);
+    assert!(offset_of!(MINMAXINFO, ptMinTrackSize) == 24);
+    assert!(size_of::<WINDOWPLACEMENT>() == 44);
+    assert!(offset_of!(WINDOWPLACEMENT, rcNormalPosition) == 28);
+    assert!(size_of::<MONITORINFO>() == 40);
+    assert!(size_of::<MONITORINFOEXW>() == 104);
+    assert!(size_of::<DEVMODEW>() == 220);
+    assert!(offset_of!(DEVMODEW, dmFields) == 72);
+    assert!(offset_of!(DEVMODEW, dmPosition) == 76);
+    assert!(offset_of!(DEVMODEW, dmBitsPerPel) == 168);
+    assert!(offset_of!(DEVMODEW, dmDisplayFrequency) == 184);
+};
+
+// Same, for the structs holding pointers.
+#[cfg(target_pointer_width = "64")]
+const _: () = {
+    use core::mem::{offset_of, size_of};
+    assert!(size_of::<TRACKMOUSEEVENT>() == 24);
+    assert!(size_of::<RAWINPUTDEVICE>() == 16);
+    assert!(size_of::<RAWINPUTHEADER>() == 24);
+    assert!(offset_of!(RAWINPUT, data) == 24);
+    assert!(size_of::<RAWINPUT>() == 48);
+    assert!(size_of::<DSBUFFERDESC>() == 40);
+    assert!(size_of::<WNDCLASSEXW>() == 80);
+    assert!(offset_of!(WNDCLASSEXW, hIconSm) == 72);
+    assert!(offset_of!(IDirectSoundVtbl, SetCooperativeLevel) == 6 * 8);
+    assert!(offset_of!(IDirectSoundBufferVtbl, Lock) == 11 * 8);
+    assert!(offset_of!(IDirectSoundBufferVtbl, Unlock) == 19 * 8);
+};
+
+#[cfg(windows)]
 #[link(name = "Kernel32")]
 extern "system" {
     /// [`GetModuleHandleW`](https://docs.microsoft.com/en-us/windows/win32/api/libloaderapi/nf-libloaderapi-getmodulehandlew)
@@ -391,18 +1392,154 @@ extern "system" {
 
     /// [`SetLastError`](https://docs.microsoft.com/en-us/windows/win32/api/errhandlingapi/nf-errhandlingapi-setlasterror)
     pub fn SetLastError(dwErrCode: DWORD);
+
+    /// [`LoadLibraryW`](https://docs.microsoft.com/en-us/windows/win32/api/libloaderapi/nf-libloaderapi-loadlibraryw)
+    pub fn LoadLibraryW(lpLibFileName: LPCWSTR) -> HMODULE;
+
+    /// [`GetProcAddress`](https://docs.microsoft.com/en-us/windows/win32/api/libloaderapi/nf-libloaderapi-getprocaddress)
+    pub fn GetProcAddress(hModule: HMODULE, lpProcName: LPCSTR) -> FARPROC;
+
+    /// [`FreeLibrary`](https://docs.microsoft.com/en-us/windows/win32/api/libloaderapi/nf-libloaderapi-freelibrary)
+    pub fn FreeLibrary(hLibModule: HMODULE) -> BOOL;
+
+    /// [`QueryPerformanceCounter`](https://docs.microsoft.com/en-us/windows/win32/api/profileapi/nf-profileapi-queryperformancecounter)
+    pub fn QueryPerformanceCounter(lpPerformanceCount: *mut LARGE_INTEGER) -> BOOL;
+
+    /// [`QueryPerformanceFrequency`](https://docs.microsoft.com/en-us/windows/win32/api/profileapi/nf-profileapi-queryperformancefrequency)
+    pub fn QueryPerformanceFrequency(lpFrequency: *mut LARGE_INTEGER) -> BOOL;
+
+    /// [`Sleep`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-sleep)
+    pub fn Sleep(dwMilliseconds: DWORD);
+
+    /// [`VirtualAlloc`](https://docs.microsoft.com/en-us/windows/win32/api/memoryapi/nf-memoryapi-virtualalloc)
+    pub fn VirtualAlloc(
+        lpAddress: LPVOID,
+        dwSize: SIZE_T,
+        flAllocationType: DWORD,
+        flProtect: DWORD,
+    ) -> LPVOID;
+
+    /// [`VirtualFree`](https://docs.microsoft.com/en-us/windows/win32/api/memoryapi/nf-memoryapi-virtualfree)
+    pub fn VirtualFree(lpAddress: LPVOID, dwSize: SIZE_T, dwFreeType: DWORD) -> BOOL;
 }
 
 pub const fn MAKEINTRESOURCE(i: WORD) -> LPWSTR {
     i as ULONG_PTR as LPWSTR
 }
 
-/// See `C:\Program Files (x86)\Windows Kits\10\Include\10.0.19041.0\um\WinUser.h`
+/// The low-order word of a `WPARAM`/`LPARAM`.
+pub const fn LOWORD(l: usize) -> WORD {
+    (l & 0xffff) as WORD
+}
+
+/// The high-order word of a `WPARAM`/`LPARAM`.
+pub const fn HIWORD(l: usize) -> WORD {
+    ((l >> 16) & 0xffff) as WORD
+}
+
+/// The signed x-coordinate packed in an `LPARAM`, as done by `GET_X_LPARAM` in `windowsx.h`.
+///
+/// Don't use [`LOWORD`] for coordinates, they can be negative on multiple monitor setups.
+pub const fn GET_X_LPARAM(lp: LPARAM) -> c_int {
+    LOWORD(lp) as i16 as c_int
+}
+
+/// The signed y-coordinate packed in an `LPARAM`, as done by `GET_Y_LPARAM` in `windowsx.h`.
+pub const fn GET_Y_LPARAM(lp: LPARAM) -> c_int {
+    HIWORD(lp) as i16 as c_int
+}
+
+// See `C:\Program Files (x86)\Windows Kits\10\Include\10.0.19041.0\um\WinUser.h`
+#[cfg(windows)]
 #[link(name = "User32")]
 extern "system" {
+    /// [`AdjustWindowRectEx`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-adjustwindowrectex)
+    pub fn AdjustWindowRectEx(
+        lpRect: *mut RECT,
+        dwStyle: DWORD,
+        bMenu: BOOL,
+        dwExStyle: DWORD,
+    ) -> BOOL;
+
+    /// [`SetWindowPos`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setwindowpos)
+    pub fn SetWindowPos(
+        hWnd: HWND,
+        hWndInsertAfter: HWND,
+        X: c_int,
+        Y: c_int,
+        cx: c_int,
+        cy: c_int,
+        uFlags: UINT,
+    ) -> BOOL;
+
+    /// [`GetWindowPlacement`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getwindowplacement)
+    pub fn GetWindowPlacement(hWnd: HWND, lpwndpl: *mut WINDOWPLACEMENT) -> BOOL;
+
+    /// [`SetWindowPlacement`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setwindowplacement)
+    pub fn SetWindowPlacement(hWnd: HWND, lpwndpl: *const WINDOWPLACEMENT) -> BOOL;
+
+    /// [`SetLayeredWindowAttributes`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setlayeredwindowattributes)
+    pub fn SetLayeredWindowAttributes(
+        hwnd: HWND,
+        crKey: COLORREF,
+        bAlpha: BYTE,
+        dwFlags: DWORD,
+    ) -> BOOL;
+
+    /// [`MonitorFromWindow`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-monitorfromwindow)
+    pub fn MonitorFromWindow(hwnd: HWND, dwFlags: DWORD) -> HMONITOR;
+
+    /// [`MonitorFromPoint`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-monitorfrompoint)
+    pub fn MonitorFromPoint(pt: POINT, dwFlags: DWORD) -> HMONITOR;
+
+    /// [`EnumDisplayMonitors`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-enumdisplaymonitors)
+    pub fn EnumDisplayMonitors(
+        hdc: HDC,
+        lprcClip: *const RECT,
+        lpfnEnum: MONITORENUMPROC,
+        dwData: LPARAM,
+    ) -> BOOL;
+
+    /// [`EnumDisplaySettingsW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-enumdisplaysettingsw)
+    pub fn EnumDisplaySettingsW(
+        lpszDeviceName: LPCWSTR,
+        iModeNum: DWORD,
+        lpDevMode: *mut DEVMODEW,
+    ) -> BOOL;
+
+    /// [`GetMonitorInfoW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getmonitorinfow),
+    /// `lpmi` may point to a [`MONITORINFOEXW`] with `cbSize` set accordingly.
+    pub fn GetMonitorInfoW(hMonitor: HMONITOR, lpmi: *mut MONITORINFO) -> BOOL;
+
+    /// [`ChangeDisplaySettingsExW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-changedisplaysettingsexw)
+    pub fn ChangeDisplaySettingsExW(
+        lpszDeviceName: LPCWSTR,
+        lpDevMode: *mut DEVMODEW,
+        hwnd: HWND,
+        dwflags: DWORD,
+        lParam: LPVOID,
+    ) -> LONG;
+
+    /// [`SetProcessDPIAware`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setprocessdpiaware),
+    /// system wide DPI awareness, for the Windows versions before per monitor awareness.
+    pub fn SetProcessDPIAware() -> BOOL;
+
     /// [`RegisterClassW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerclassw)
     pub fn RegisterClassW(lpWndClass: *const WNDCLASSW) -> ATOM;
 
+    /// [`RegisterClassExW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerclassexw)
+    pub fn RegisterClassExW(lpWndClass: *const WNDCLASSEXW) -> ATOM;
+
+    /// [`UnregisterClassW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-unregisterclassw)
+    pub fn UnregisterClassW(lpClassName: LPCWSTR, hInstance: HINSTANCE) -> BOOL;
+
+    /// [`GetClassInfoExW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getclassinfoexw)
+    pub fn GetClassInfoExW(
+        hInstance: HINSTANCE,
+        lpszClass: LPCWSTR,
+        lpwcx: *mut WNDCLASSEXW,
+    ) -> BOOL;
+
     /// [`CreateWindowExW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw#syntax)
     pub fn CreateWindowExW(
         dwExStyle: DWORD,
@@ -428,6 +1565,15 @@ extern "system" {
     ///[`GetMessageW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getmessagew)
     pub fn GetMessageW(lpMsg: LPMSG, hWnd: HWND, wMsgFilterMin: UINT, wMsgFilterMax: UINT) -> BOOL;
 
+    /// [`PeekMessageW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-peekmessagew)
+    pub fn PeekMessageW(
+        lpMsg: LPMSG,
+        hWnd: HWND,
+        wMsgFilterMin: UINT,
+        wMsgFilterMax: UINT,
+        wRemoveMsg: UINT,
+    ) -> BOOL;
+
     /// [`TranslateMessage`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-translatemessage)
     pub fn TranslateMessage(lpMsg: *const MSG) -> BOOL;
 
@@ -443,6 +1589,9 @@ extern "system" {
     /// [`LoadCursorW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-loadcursorw)
     pub fn LoadCursorW(hInstance: HINSTANCE, lpCursorName: LPCWSTR) -> HCURSOR;
 
+    /// [`LoadIconW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-loadiconw)
+    pub fn LoadIconW(hInstance: HINSTANCE, lpIconName: LPCWSTR) -> HICON;
+
     /// [`BeginPaint`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-beginpaint)
     pub fn BeginPaint(hWnd: HWND, lpPaint: *mut PAINTSTRUCT) -> HDC;
 
@@ -464,6 +1613,123 @@ extern "system" {
     /// [`GetSysColor `](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getsyscolor)
     pub fn GetSysColor(nIndex: c_int) -> DWORD;
 
+    /// [`GetDC`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getdc)
+    pub fn GetDC(hWnd: HWND) -> HDC;
+
+    /// [`ReleaseDC`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-releasedc)
+    pub fn ReleaseDC(hWnd: HWND, hDC: HDC) -> c_int;
+
+    /// [`GetClientRect`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getclientrect)
+    pub fn GetClientRect(hWnd: HWND, lpRect: LPRECT) -> BOOL;
+
+    /// [`SetCapture`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setcapture)
+    pub fn SetCapture(hWnd: HWND) -> HWND;
+
+    /// [`ReleaseCapture`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-releasecapture)
+    pub fn ReleaseCapture() -> BOOL;
+
+    /// [`GetCapture`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getcapture)
+    pub fn GetCapture() -> HWND;
+
+    /// [`TrackMouseEvent`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-trackmouseevent)
+    pub fn TrackMouseEvent(lpEventTrack: LPTRACKMOUSEEVENT) -> BOOL;
+
+    /// [`RegisterRawInputDevices`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerrawinputdevices)
+    pub fn RegisterRawInputDevices(
+        pRawInputDevices: PCRAWINPUTDEVICE,
+        uiNumDevices: UINT,
+        cbSize: UINT,
+    ) -> BOOL;
+
+    /// [`GetRawInputData`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getrawinputdata)
+    pub fn GetRawInputData(
+        hRawInput: HRAWINPUT,
+        uiCommand: UINT,
+        pData: LPVOID,
+        pcbSize: PUINT,
+        cbSizeHeader: UINT,
+    ) -> UINT;
+}
+
+// See `C:\Program Files (x86)\Windows Kits\10\Include\10.0.19041.0\um\wingdi.h`
+#[cfg(windows)]
+#[link(name = "Gdi32")]
+extern "system" {
+    /// [`StretchDIBits`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-stretchdibits)
+    pub fn StretchDIBits(
+        hdc: HDC,
+        xDest: c_int,
+        yDest: c_int,
+        DestWidth: c_int,
+        DestHeight: c_int,
+        xSrc: c_int,
+        ySrc: c_int,
+        SrcWidth: c_int,
+        SrcHeight: c_int,
+        lpBits: *const c_void,
+        lpbmi: *const BITMAPINFO,
+        iUsage: UINT,
+        rop: DWORD,
+    ) -> c_int;
+
+    /// [`CreateDIBSection`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-createdibsection)
+    pub fn CreateDIBSection(
+        hdc: HDC,
+        pbmi: *const BITMAPINFO,
+        usage: UINT,
+        ppvBits: *mut *mut c_void,
+        hSection: HANDLE,
+        offset: DWORD,
+    ) -> HBITMAP;
+
+    /// [`DeleteObject`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-deleteobject)
+    pub fn DeleteObject(ho: HGDIOBJ) -> BOOL;
+
+    /// [`ChoosePixelFormat`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-choosepixelformat)
+    pub fn ChoosePixelFormat(hdc: HDC, ppfd: *const PIXELFORMATDESCRIPTOR) -> c_int;
+
+    /// [`DescribePixelFormat`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-describepixelformat)
+    pub fn DescribePixelFormat(
+        hdc: HDC,
+        iPixelFormat: c_int,
+        nBytes: UINT,
+        ppfd: *mut PIXELFORMATDESCRIPTOR,
+    ) -> c_int;
+
+    /// [`SetPixelFormat`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-setpixelformat)
+    pub fn SetPixelFormat(hdc: HDC, format: c_int, ppfd: *const PIXELFORMATDESCRIPTOR) -> BOOL;
+
+    /// [`SwapBuffers`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-swapbuffers)
+    pub fn SwapBuffers(Arg1: HDC) -> BOOL;
+}
+
+#[cfg(windows)]
+#[link(name = "Opengl32")]
+extern "system" {
+    /// [`wglCreateContext`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-wglcreatecontext)
+    pub fn wglCreateContext(Arg1: HDC) -> HGLRC;
+
+    /// [`wglDeleteContext`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-wgldeletecontext)
+    pub fn wglDeleteContext(Arg1: HGLRC) -> BOOL;
+
+    /// [`wglMakeCurrent`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-wglmakecurrent)
+    pub fn wglMakeCurrent(hdc: HDC, hglrc: HGLRC) -> BOOL;
+
+    /// [`wglGetCurrentContext`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-wglgetcurrentcontext)
+    pub fn wglGetCurrentContext() -> HGLRC;
+
+    /// [`wglGetProcAddress`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-wglgetprocaddress)
+    pub fn wglGetProcAddress(Arg1: LPCSTR) -> PROC;
+}
+
+#[cfg(windows)]
+#[link(name = "Winmm")]
+extern "system" {
+    /// [`timeBeginPeriod`](https://docs.microsoft.com/en-us/windows/win32/api/timeapi/nf-timeapi-timebeginperiod)
+    pub fn timeBeginPeriod(uPeriod: UINT) -> MMRESULT;
+
+    /// [`timeEndPeriod`](https://docs.microsoft.com/en-us/windows/win32/api/timeapi/nf-timeapi-timeendperiod)
+    pub fn timeEndPeriod(uPeriod: UINT) -> MMRESULT;
 }
 
 /// Turns a Rust string slice into a null-terminated utf-16 vector.
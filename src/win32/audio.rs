@@ -0,0 +1,274 @@
+//! Sound output through DirectSound, fed from an [`audio`](crate::audio) ring buffer.
+//!
+//! Like in Handmade Hero, a looping secondary buffer of a fixed size plays continuously, and
+//! every frame [`AudioOutput::pump`] refills the part between what was last written and a little
+//! past DirectSound's write cursor. The DLL is loaded at runtime so the game still runs, silent,
+//! where there is no DirectSound.
+//!
+//! See [DirectSound](https://docs.microsoft.com/en-us/previous-versions/windows/desktop/ee416960(v=vs.85))
+
+use super::{safe::*, *};
+use crate::audio::{ring_buffer, AudioFormat, Consumer, Producer};
+use core::{ptr::null_mut, time::Duration};
+
+/// Converts a failed `HRESULT` to the error type of the module.
+fn check(hr: HRESULT) -> Result<(), Win32Error> {
+    if hr >= 0 {
+        Ok(())
+    } else {
+        Err(Win32Error(hr as DWORD))
+    }
+}
+
+/// A DirectSound buffer playing what the game writes into a ring buffer.
+pub struct AudioOutput {
+    dsound: HMODULE,
+    direct_sound: *mut IDirectSound,
+    buffer: *mut IDirectSoundBuffer,
+    /// The size of `buffer`, in bytes.
+    buffer_size: DWORD,
+    format: AudioFormat,
+    /// How far past DirectSound's write cursor to fill the buffer, in bytes.
+    latency_bytes: DWORD,
+    consumer: Consumer,
+    /// How many bytes were ever written to `buffer`, `None` until the first pump.
+    written: Option<u64>,
+    /// The play cursor seen by the last pump.
+    play_cursor: DWORD,
+    /// The samples moved from the ring buffer to DirectSound, kept around between pumps.
+    scratch: Vec<i16>,
+}
+
+impl AudioOutput {
+    /// Opens the default output device for `hwnd`, which must stay alive as long as the output.
+    ///
+    /// * `latency` is how far ahead of the sound card samples are written, it has to be longer
+    ///   than a frame for the sound not to skip.
+    /// * The DirectSound buffer and the ring buffer both hold one second of sound.
+    ///
+    /// **Returns:** The output, and the end of the ring buffer the game writes into.
+    // The window handle is only handed over to DirectSound, never dereferenced.
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    pub fn open(
+        hwnd: HWND,
+        format: AudioFormat,
+        latency: Duration,
+    ) -> Result<(Self, Producer), Win32Error> {
+        let dsound_wn = wide_null("dsound.dll");
+        let dsound = unsafe { LoadLibraryW(dsound_wn.as_ptr()) };
+        if dsound.is_null() {
+            return Err(get_last_error());
+        }
+        let bytes_per_frame = format.bytes_per_frame();
+        let buffer_size = format.sample_rate * bytes_per_frame;
+        let latency_frames = format.duration_to_frames(latency) as DWORD;
+        let (producer, consumer) =
+            ring_buffer((format.sample_rate * u32::from(format.channels)) as usize);
+        // From here on, dropping the output cleans up whatever was created.
+        let mut output = Self {
+            dsound,
+            direct_sound: null_mut(),
+            buffer: null_mut(),
+            buffer_size,
+            format,
+            latency_bytes: (latency_frames * bytes_per_frame).min(buffer_size / 2),
+            consumer,
+            written: None,
+            play_cursor: 0,
+            scratch: Vec::new(),
+        };
+
+        let create = unsafe { GetProcAddress(dsound, b"DirectSoundCreate\0".as_ptr().cast()) };
+        if create.is_null() {
+            return Err(get_last_error());
+        }
+        // Safety: the signature is the documented one.
+        let create = unsafe { core::mem::transmute::<FARPROC, DirectSoundCreate_t>(create) };
+        check(unsafe { create(core::ptr::null(), &mut output.direct_sound, null_mut()) })?;
+        let direct_sound = output.direct_sound;
+        let vtbl = unsafe { &*(*direct_sound).lpVtbl };
+        check(unsafe { (vtbl.SetCooperativeLevel)(direct_sound, hwnd, DSSCL_PRIORITY) })?;
+
+        let mut wave_format = WAVEFORMATEX {
+            wFormatTag: WAVE_FORMAT_PCM,
+            nChannels: format.channels,
+            nSamplesPerSec: format.sample_rate,
+            nAvgBytesPerSec: format.sample_rate * bytes_per_frame,
+            nBlockAlign: bytes_per_frame as WORD,
+            wBitsPerSample: 16,
+            cbSize: 0,
+        };
+        // The primary buffer is only there to set the format the sound card runs at.
+        let primary_desc = DSBUFFERDESC {
+            dwSize: core::mem::size_of::<DSBUFFERDESC>() as DWORD,
+            dwFlags: DSBCAPS_PRIMARYBUFFER,
+            ..DSBUFFERDESC::default()
+        };
+        let mut primary = null_mut();
+        check(unsafe {
+            (vtbl.CreateSoundBuffer)(direct_sound, &primary_desc, &mut primary, null_mut())
+        })?;
+        let primary_vtbl = unsafe { &*(*primary).lpVtbl };
+        let set_format = unsafe { (primary_vtbl.SetFormat)(primary, &wave_format) };
+        unsafe { (primary_vtbl.Release)(primary) };
+        check(set_format)?;
+
+        let secondary_desc = DSBUFFERDESC {
+            dwSize: core::mem::size_of::<DSBUFFERDESC>() as DWORD,
+            dwFlags: DSBCAPS_GETCURRENTPOSITION2 | DSBCAPS_GLOBALFOCUS,
+            dwBufferBytes: buffer_size,
+            lpwfxFormat: &mut wave_format,
+            ..DSBUFFERDESC::default()
+        };
+        check(unsafe {
+            (vtbl.CreateSoundBuffer)(
+                direct_sound,
+                &secondary_desc,
+                &mut output.buffer,
+                null_mut(),
+            )
+        })?;
+        output.clear()?;
+        check(unsafe { (output.buffer_vtbl().Play)(output.buffer, 0, 0, DSBPLAY_LOOPING) })?;
+        Ok((output, producer))
+    }
+
+    /// The format of the samples the output expects.
+    pub fn format(&self) -> AudioFormat {
+        self.format
+    }
+
+    /// How long the samples written so far will play before the sound card runs out of them,
+    /// as of the last [`AudioOutput::pump`].
+    pub fn latency(&self) -> Duration {
+        let frames = match self.written {
+            Some(written) => {
+                let end = (written % u64::from(self.buffer_size)) as DWORD;
+                self.distance(self.play_cursor, end) / self.format.bytes_per_frame()
+            }
+            None => 0,
+        };
+        self.format.frames_to_duration(u64::from(frames))
+    }
+
+    /// Moves samples from the ring buffer to DirectSound, filling the buffer up to the latency
+    /// past the write cursor.
+    ///
+    /// Should be called once per frame. When the ring buffer runs dry, silence is written instead.
+    pub fn pump(&mut self) -> Result<(), Win32Error> {
+        let buffer = self.buffer;
+        let vtbl = self.buffer_vtbl();
+        let [mut play_cursor, mut write_cursor] = [0, 0];
+        let hr = unsafe { (vtbl.GetCurrentPosition)(buffer, &mut play_cursor, &mut write_cursor) };
+        if hr == DSERR_BUFFERLOST {
+            check(unsafe { (vtbl.Restore)(buffer) })?;
+            self.written = None;
+            return Ok(());
+        }
+        check(hr)?;
+        self.play_cursor = play_cursor;
+
+        // Start at the write cursor the first time, and when the sound card caught up with us.
+        let bytes_per_frame = self.format.bytes_per_frame();
+        let mut start = self
+            .written
+            .map(|written| (written % u64::from(self.buffer_size)) as DWORD);
+        if let Some(position) = start {
+            if self.distance(play_cursor, position) < self.distance(play_cursor, write_cursor) {
+                start = None;
+            }
+        }
+        let start = match start {
+            Some(start) => start,
+            None => {
+                self.written = Some(u64::from(write_cursor));
+                write_cursor
+            }
+        };
+        let mut target = (write_cursor + self.latency_bytes) % self.buffer_size;
+        target -= target % bytes_per_frame;
+        let bytes = self.distance(start, target);
+        // Also covers what's already written past the target, when the latency shrunk.
+        if bytes == 0 || bytes > self.buffer_size / 2 + self.latency_bytes {
+            return Ok(());
+        }
+
+        let sample_count = (bytes / 2) as usize;
+        self.scratch.clear();
+        self.scratch.resize(sample_count, 0);
+        // Whole frames only, the rest of the frame stays in the ring buffer.
+        let available = self.consumer.len() / usize::from(self.format.channels)
+            * usize::from(self.format.channels);
+        self.consumer
+            .pop(&mut self.scratch[..sample_count.min(available)]);
+        self.write(start, bytes)?;
+        self.written = self.written.map(|written| written + u64::from(bytes));
+        Ok(())
+    }
+
+    /// Copies the first `bytes` bytes of `scratch` into the buffer, starting at `start`.
+    fn write(&mut self, start: DWORD, bytes: DWORD) -> Result<(), Win32Error> {
+        let buffer = self.buffer;
+        let vtbl = self.buffer_vtbl();
+        let [mut region1, mut region2] = [null_mut(), null_mut()];
+        let [mut size1, mut size2] = [0, 0];
+        check(unsafe {
+            (vtbl.Lock)(
+                buffer,
+                start,
+                bytes,
+                &mut region1,
+                &mut size1,
+                &mut region2,
+                &mut size2,
+                0,
+            )
+        })?;
+        // The locked area wraps around the end of the buffer when there is a second region.
+        let samples = self.scratch.as_ptr();
+        unsafe {
+            core::ptr::copy_nonoverlapping(samples.cast::<u8>(), region1.cast(), size1 as usize);
+            if !region2.is_null() {
+                core::ptr::copy_nonoverlapping(
+                    samples.cast::<u8>().add(size1 as usize),
+                    region2.cast(),
+                    size2 as usize,
+                );
+            }
+        }
+        check(unsafe { (vtbl.Unlock)(buffer, region1, size1, region2, size2) })
+    }
+
+    /// Fills the whole buffer with silence.
+    fn clear(&mut self) -> Result<(), Win32Error> {
+        self.scratch.clear();
+        self.scratch.resize((self.buffer_size / 2) as usize, 0);
+        self.write(0, self.buffer_size)
+    }
+
+    /// How many bytes there are from `from` to `to`, going forward around the buffer.
+    fn distance(&self, from: DWORD, to: DWORD) -> DWORD {
+        (to + self.buffer_size - from) % self.buffer_size
+    }
+
+    fn buffer_vtbl(&self) -> &'static IDirectSoundBufferVtbl {
+        // Safety: only called once the buffer is created, vtables are static.
+        unsafe { &*(*self.buffer).lpVtbl }
+    }
+}
+
+impl Drop for AudioOutput {
+    fn drop(&mut self) {
+        unsafe {
+            if !self.buffer.is_null() {
+                let vtbl = self.buffer_vtbl();
+                (vtbl.Stop)(self.buffer);
+                (vtbl.Release)(self.buffer);
+            }
+            if !self.direct_sound.is_null() {
+                ((*(*self.direct_sound).lpVtbl).Release)(self.direct_sound);
+            }
+            FreeLibrary(self.dsound);
+        }
+    }
+}
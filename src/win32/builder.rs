@@ -0,0 +1,307 @@
+//! The settings of a window, before it is created.
+//!
+//! Turning them into window styles is plain data crunching, so it's available (and testable) on
+//! every platform.
+
+use super::*;
+#[cfg(windows)]
+use super::{event_loop::EventLoop, safe::Win32Error, window::Window};
+use core::ptr::null_mut;
+
+/// How a window relates to the other windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowKind {
+    /// A window of its own, with a taskbar button.
+    TopLevel,
+    /// A window owned by another one, which it stays above. The owner may be null.
+    Popup(HWND),
+    /// A window inside the client area of its parent, moving and hiding along with it.
+    Child(HWND),
+}
+
+impl WindowKind {
+    /// The owner of a popup window or the parent of a child window, null otherwise.
+    pub fn parent(self) -> HWND {
+        match self {
+            WindowKind::TopLevel => null_mut(),
+            WindowKind::Popup(owner) => owner,
+            WindowKind::Child(parent) => parent,
+        }
+    }
+}
+
+/// The settings of a window to create, see [`WindowBuilder::build`].
+///
+/// The defaults are those of [`Window::new`](super::window::Window::new): a resizable top-level
+/// window with a frame, at a position and size picked by the system, not initially shown.
+#[derive(Debug, Clone)]
+pub struct WindowBuilder {
+    class_name: Option<String>,
+    title: String,
+    position: Option<[i32; 2]>,
+    size: Option<[u32; 2]>,
+    resizable: bool,
+    decorations: bool,
+    always_on_top: bool,
+    opacity: u8,
+    kind: WindowKind,
+    visible: bool,
+}
+
+impl Default for WindowBuilder {
+    fn default() -> Self {
+        Self {
+            class_name: None,
+            title: String::new(),
+            position: None,
+            size: None,
+            resizable: true,
+            decorations: true,
+            always_on_top: false,
+            opacity: u8::MAX,
+            kind: WindowKind::TopLevel,
+            visible: false,
+        }
+    }
+}
+
+impl WindowBuilder {
+    /// The size of popup and child windows created without one: `CW_USEDEFAULT` only works for
+    /// top-level windows, the others would be zero-sized.
+    pub const DEFAULT_SIZE: [u32; 2] = [320, 240];
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The class of the window, the one of the event loop by default.
+    ///
+    /// See [`Window::with_class`](super::window::Window::with_class) for what the class must do.
+    pub fn with_class(mut self, class_name: &str) -> Self {
+        self.class_name = Some(class_name.to_owned());
+        self
+    }
+
+    /// The text of the title bar.
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_owned();
+        self
+    }
+
+    /// The top left corner of the window, relative to the client area of the parent for a child
+    /// window.
+    pub fn with_position(mut self, position: [i32; 2]) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// The size of the client area, in logical pixels.
+    ///
+    /// Only top-level windows get a size from the system, popup and child windows get
+    /// [`WindowBuilder::DEFAULT_SIZE`] otherwise.
+    pub fn with_size(mut self, size: [u32; 2]) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Whether the user can resize and maximize the window, only possible with decorations.
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Whether the window has a title bar, or a thin border for child windows.
+    pub fn with_decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    /// Whether the window stays above the windows that aren't, child windows never do.
+    pub fn with_always_on_top(mut self, always_on_top: bool) -> Self {
+        self.always_on_top = always_on_top;
+        self
+    }
+
+    /// How opaque the whole window is, from 0 (invisible) to 255 (opaque, the default).
+    ///
+    /// Anything below 255 makes it a layered window.
+    pub fn with_opacity(mut self, opacity: u8) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    /// A top-level, popup or child window.
+    pub fn with_kind(mut self, kind: WindowKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Whether the window is shown right away, see [`Window::show`](super::window::Window::show)
+    /// otherwise.
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    pub fn class_name(&self) -> Option<&str> {
+        self.class_name.as_deref()
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn position(&self) -> Option<[i32; 2]> {
+        self.position
+    }
+
+    /// The size of the client area, `None` to let the system pick it.
+    pub fn size(&self) -> Option<[u32; 2]> {
+        match self.kind {
+            WindowKind::TopLevel => self.size,
+            WindowKind::Popup(_) | WindowKind::Child(_) => {
+                Some(self.size.unwrap_or(Self::DEFAULT_SIZE))
+            }
+        }
+    }
+
+    pub fn opacity(&self) -> u8 {
+        self.opacity
+    }
+
+    pub fn kind(&self) -> WindowKind {
+        self.kind
+    }
+
+    /// The `WS_*` styles of the window.
+    pub fn style(&self) -> DWORD {
+        let frame = match (self.kind, self.decorations) {
+            (WindowKind::TopLevel, true) => {
+                WS_OVERLAPPED | WS_CAPTION | WS_SYSMENU | WS_MINIMIZEBOX
+            }
+            // An overlapped window always gets a caption.
+            (WindowKind::TopLevel, false) => WS_POPUP,
+            (WindowKind::Popup(_), true) => WS_POPUP | WS_CAPTION | WS_SYSMENU,
+            (WindowKind::Popup(_), false) => WS_POPUP,
+            (WindowKind::Child(_), true) => WS_CHILD | WS_CLIPSIBLINGS | WS_BORDER,
+            (WindowKind::Child(_), false) => WS_CHILD | WS_CLIPSIBLINGS,
+        };
+        let resize = match (self.kind, self.resizable && self.decorations) {
+            (_, false) => 0,
+            (WindowKind::TopLevel, true) => WS_THICKFRAME | WS_MAXIMIZEBOX,
+            (_, true) => WS_THICKFRAME,
+        };
+        let visible = if self.visible { WS_VISIBLE } else { 0 };
+        frame | resize | visible
+    }
+
+    /// The `WS_EX_*` styles of the window.
+    pub fn ex_style(&self) -> DWORD {
+        let mut ex_style = 0;
+        if self.always_on_top && !matches!(self.kind, WindowKind::Child(_)) {
+            ex_style |= WS_EX_TOPMOST;
+        }
+        if self.opacity < u8::MAX {
+            ex_style |= WS_EX_LAYERED;
+        }
+        ex_style
+    }
+}
+
+#[cfg(windows)]
+impl WindowBuilder {
+    /// Creates the window, handled by `event_loop`, moving `state` into it.
+    pub fn build<S: 'static>(
+        &self,
+        _event_loop: &EventLoop,
+        state: S,
+    ) -> Result<Window<S>, Win32Error> {
+        Window::from_builder(self, state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_to_an_overlapped_window() {
+        let builder = WindowBuilder::new();
+        assert_eq!(builder.style(), WS_OVERLAPPEDWINDOW);
+        assert_eq!(builder.ex_style(), 0);
+    }
+
+    #[test]
+    fn fixed_size_windows_cannot_be_resized_or_maximized() {
+        let style = WindowBuilder::new().with_resizable(false).style();
+        assert_eq!(
+            style,
+            WS_OVERLAPPEDWINDOW & !(WS_THICKFRAME | WS_MAXIMIZEBOX)
+        );
+        assert_eq!(style & WS_MINIMIZEBOX, WS_MINIMIZEBOX);
+    }
+
+    #[test]
+    fn undecorated_windows_are_borderless_popups() {
+        let builder = WindowBuilder::new().with_decorations(false);
+        assert_eq!(builder.style(), WS_POPUP);
+        // Resizing needs the frame.
+        assert_eq!(builder.with_resizable(true).style() & WS_THICKFRAME, 0);
+    }
+
+    #[test]
+    fn visible_windows_are_shown_on_creation() {
+        let style = WindowBuilder::new().with_visible(true).style();
+        assert_eq!(style, WS_OVERLAPPEDWINDOW | WS_VISIBLE);
+    }
+
+    #[test]
+    fn popup_windows_have_no_minimize_or_maximize_box() {
+        let owner = 0x1234 as HWND;
+        let builder = WindowBuilder::new().with_kind(WindowKind::Popup(owner));
+        assert_eq!(
+            builder.style(),
+            WS_POPUP | WS_CAPTION | WS_SYSMENU | WS_THICKFRAME
+        );
+        assert_eq!(builder.kind().parent(), owner);
+    }
+
+    #[test]
+    fn child_windows_are_clipped_and_never_topmost() {
+        let parent = 0x1234 as HWND;
+        let builder = WindowBuilder::new()
+            .with_kind(WindowKind::Child(parent))
+            .with_decorations(false)
+            .with_always_on_top(true)
+            .with_visible(true);
+        assert_eq!(builder.style(), WS_CHILD | WS_CLIPSIBLINGS | WS_VISIBLE);
+        assert_eq!(builder.ex_style(), 0);
+        assert_eq!(
+            builder.with_decorations(true).style(),
+            WS_CHILD | WS_CLIPSIBLINGS | WS_BORDER | WS_THICKFRAME | WS_VISIBLE
+        );
+    }
+
+    #[test]
+    fn only_top_level_windows_get_their_size_from_the_system() {
+        let builder = WindowBuilder::new();
+        assert_eq!(builder.size(), None);
+        assert_eq!(builder.clone().with_size([64, 48]).size(), Some([64, 48]));
+        let owner = 0x1234 as HWND;
+        for kind in [WindowKind::Popup(owner), WindowKind::Child(owner)] {
+            let builder = builder.clone().with_kind(kind);
+            assert_eq!(builder.size(), Some(WindowBuilder::DEFAULT_SIZE));
+            assert_eq!(builder.with_size([64, 48]).size(), Some([64, 48]));
+        }
+    }
+
+    #[test]
+    fn always_on_top_and_transparency_are_extended_styles() {
+        let builder = WindowBuilder::new().with_always_on_top(true);
+        assert_eq!(builder.ex_style(), WS_EX_TOPMOST);
+        let builder = builder.with_opacity(128);
+        assert_eq!(builder.ex_style(), WS_EX_TOPMOST | WS_EX_LAYERED);
+        assert_eq!(builder.style(), WS_OVERLAPPEDWINDOW);
+        assert_eq!(builder.with_opacity(u8::MAX).ex_style(), WS_EX_TOPMOST);
+    }
+}
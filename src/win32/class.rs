@@ -0,0 +1,251 @@
+//! Window classes, registered once per process and unregistered along with their last window.
+//!
+//! Classes are registered for the whole process, so the registry is shared by every thread.
+
+use super::{safe::*, window::window_procedure, *};
+use core::ptr::null_mut;
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+/// A class registered through [`WindowClass::register`].
+struct Registration {
+    atom: ATOM,
+    /// How many [`RegisteredClass`]es there are for the class.
+    count: usize,
+}
+
+/// The classes registered through [`WindowClass::register`], by name.
+static REGISTRY: Mutex<BTreeMap<String, Registration>> = Mutex::new(BTreeMap::new());
+
+fn registry() -> MutexGuard<'static, BTreeMap<String, Registration>> {
+    // The map is never left half updated.
+    REGISTRY.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The settings of a window class to register, see [`WindowClass::register`].
+///
+/// By default the windows of the class use [`window_procedure`], the arrow cursor and the
+/// application icon, and have no background: they paint all of their client area themselves.
+pub struct WindowClass {
+    name: String,
+    style: UINT,
+    window_procedure: unsafe extern "system" fn(HWND, UINT, WPARAM, LPARAM) -> LRESULT,
+    cursor: Option<HCURSOR>,
+    icon: Option<HICON>,
+    small_icon: HICON,
+    background: HBRUSH,
+}
+
+impl WindowClass {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            style: 0,
+            window_procedure,
+            cursor: None,
+            icon: None,
+            small_icon: null_mut(),
+            background: null_mut(),
+        }
+    }
+
+    /// The `CS_*` class styles, e.g. [`CS_OWNDC`] for OpenGL windows.
+    pub fn with_style(mut self, style: UINT) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// The window procedure of the windows of the class.
+    ///
+    /// For [`Window`](super::window::Window)s of the class to get their state, it must forward
+    /// the messages it doesn't handle to [`window_procedure`].
+    pub fn with_window_procedure(
+        mut self,
+        procedure: unsafe extern "system" fn(HWND, UINT, WPARAM, LPARAM) -> LRESULT,
+    ) -> Self {
+        self.window_procedure = procedure;
+        self
+    }
+
+    /// The cursor over the client area, see [`load_predefined_cursor`]. Null lets the windows
+    /// set the cursor themselves.
+    pub fn with_cursor(mut self, cursor: HCURSOR) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// The icon of the windows, see [`load_predefined_icon`].
+    pub fn with_icon(mut self, icon: HICON) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// The icon of the title bar and the taskbar, scaled down from the icon by default.
+    pub fn with_small_icon(mut self, small_icon: HICON) -> Self {
+        self.small_icon = small_icon;
+        self
+    }
+
+    /// The brush `DefWindowProcW` paints the background with.
+    pub fn with_background(mut self, background: HBRUSH) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Paints the background with a system color.
+    pub fn with_system_color_background(self, color: SysColor) -> Self {
+        // The brush for a system color is the color index plus one.
+        self.with_background((color as u32 + 1) as HBRUSH)
+    }
+
+    /// Registers the class, unless a class of that name was already registered this way.
+    ///
+    /// * The settings are only used by the first registration of a name, later ones get the
+    ///   class as it was first registered.
+    /// * The class is unregistered once every [`RegisteredClass`] for it is dropped, windows
+    ///   created through [`Window`](super::window::Window) keep one.
+    ///
+    /// See [`RegisterClassExW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerclassexw)
+    pub fn register(&self) -> Result<RegisteredClass, Win32Error> {
+        let mut registry = registry();
+        if let Some(registration) = registry.get_mut(&self.name) {
+            registration.count += 1;
+            return Ok(RegisteredClass {
+                name: self.name.clone(),
+                atom: registration.atom,
+            });
+        }
+        let cursor = match self.cursor {
+            Some(cursor) => cursor,
+            None => load_predefined_cursor(EIDCursor::Arrow)?,
+        };
+        let icon = match self.icon {
+            Some(icon) => icon,
+            None => load_predefined_icon(EIDIcon::Application)?,
+        };
+        let name_wn = wide_null(&self.name);
+        let window_class = WNDCLASSEXW {
+            cbSize: core::mem::size_of::<WNDCLASSEXW>() as UINT,
+            style: self.style,
+            lpfnWndProc: Some(self.window_procedure),
+            hInstance: get_process_handle(),
+            hIcon: icon,
+            hCursor: cursor,
+            hbrBackground: self.background,
+            lpszClassName: name_wn.as_ptr(),
+            hIconSm: self.small_icon,
+            ..WNDCLASSEXW::default()
+        };
+        let atom = unsafe { register_class_ex(&window_class) }?;
+        registry.insert(self.name.clone(), Registration { atom, count: 1 });
+        Ok(RegisteredClass {
+            name: self.name.clone(),
+            atom,
+        })
+    }
+}
+
+/// Keeps a class registered through [`WindowClass::register`] alive.
+///
+/// The class is unregistered when the last one for it is dropped, which fails if there are
+/// windows of the class left.
+#[derive(Debug)]
+pub struct RegisteredClass {
+    name: String,
+    atom: ATOM,
+}
+
+impl RegisteredClass {
+    /// Another handle to the class `name`, if it was registered through
+    /// [`WindowClass::register`] and is still registered.
+    pub fn find(name: &str) -> Option<Self> {
+        let mut registry = registry();
+        let registration = registry.get_mut(name)?;
+        registration.count += 1;
+        Some(Self {
+            name: name.to_owned(),
+            atom: registration.atom,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The atom identifying the class, which `CreateWindowExW` accepts in place of its name.
+    pub fn atom(&self) -> ATOM {
+        self.atom
+    }
+}
+
+impl Clone for RegisteredClass {
+    fn clone(&self) -> Self {
+        // Registered for as long as `self` exists.
+        registry()
+            .get_mut(&self.name)
+            .expect("the class was unregistered early")
+            .count += 1;
+        Self {
+            name: self.name.clone(),
+            atom: self.atom,
+        }
+    }
+}
+
+impl Drop for RegisteredClass {
+    /// See [`UnregisterClassW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-unregisterclassw)
+    fn drop(&mut self) {
+        let mut registry = registry();
+        let registration = match registry.get_mut(&self.name) {
+            Some(registration) => registration,
+            None => return,
+        };
+        registration.count -= 1;
+        if registration.count > 0 {
+            return;
+        }
+        registry.remove(&self.name);
+        let name_wn = wide_null(&self.name);
+        // Only fails with windows of the class left, created without a `RegisteredClass`: the
+        // class then stays registered for the rest of the process, which is harmless.
+        unsafe { UnregisterClassW(name_wn.as_ptr(), get_process_handle()) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether the process has a class registered as `name`.
+    fn is_registered(name: &str) -> bool {
+        let name_wn = wide_null(name);
+        let mut window_class = WNDCLASSEXW {
+            cbSize: core::mem::size_of::<WNDCLASSEXW>() as UINT,
+            ..WNDCLASSEXW::default()
+        };
+        0 != unsafe { GetClassInfoExW(get_process_handle(), name_wn.as_ptr(), &mut window_class) }
+    }
+
+    #[test]
+    fn registering_twice_shares_the_class() {
+        let name = "triangle_from_scratch registering twice";
+        let first = WindowClass::new(name).register().unwrap();
+        let second = WindowClass::new(name).register().unwrap();
+        assert_eq!(first.atom(), second.atom());
+        assert_eq!(RegisteredClass::find(name).unwrap().atom(), first.atom());
+    }
+
+    #[test]
+    fn the_last_handle_unregisters_the_class() {
+        let name = "triangle_from_scratch last handle";
+        let class = WindowClass::new(name).register().unwrap();
+        let clone = class.clone();
+        drop(class);
+        assert!(is_registered(name));
+        drop(clone);
+        assert!(!is_registered(name));
+        assert!(RegisteredClass::find(name).is_none());
+    }
+}
@@ -0,0 +1,86 @@
+//! A [`Clock`] on top of the performance counter.
+
+use super::*;
+use crate::timing::Clock;
+use core::time::Duration;
+
+/// The performance counter, with the system timer resolution raised to 1ms for precise sleeps.
+///
+/// The resolution is restored when the clock is dropped.
+///
+/// See [Acquiring high-resolution time stamps](https://docs.microsoft.com/en-us/windows/win32/sysinfo/acquiring-high-resolution-time-stamps)
+pub struct PerformanceClock {
+    /// Counts per second, fixed at boot.
+    frequency: LARGE_INTEGER,
+    /// Whether `timeBeginPeriod` succeeded, and has to be undone.
+    granular_sleep: bool,
+}
+
+impl PerformanceClock {
+    /// The sleep granularity asked for, in milliseconds.
+    const TIMER_PERIOD: UINT = 1;
+
+    /// Reads the counter frequency and raises the timer resolution.
+    pub fn new() -> Self {
+        let mut frequency = 0;
+        // Never fails on Windows XP and later.
+        unsafe { QueryPerformanceFrequency(&mut frequency) };
+        let granular_sleep = unsafe { timeBeginPeriod(Self::TIMER_PERIOD) } == TIMERR_NOERROR;
+        Self {
+            frequency: frequency.max(1),
+            granular_sleep,
+        }
+    }
+
+    /// Whether sleeps are precise to the millisecond, rather than to the default ~15ms.
+    pub fn has_granular_sleep(&self) -> bool {
+        self.granular_sleep
+    }
+
+    /// The raw value of the counter.
+    ///
+    /// See [`QueryPerformanceCounter`](https://docs.microsoft.com/en-us/windows/win32/api/profileapi/nf-profileapi-queryperformancecounter)
+    pub fn counter(&self) -> LARGE_INTEGER {
+        let mut counter = 0;
+        unsafe { QueryPerformanceCounter(&mut counter) };
+        counter
+    }
+
+    /// Counts per second.
+    pub fn frequency(&self) -> LARGE_INTEGER {
+        self.frequency
+    }
+}
+
+impl Default for PerformanceClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for PerformanceClock {
+    fn now(&self) -> Duration {
+        let counter = self.counter() as u64;
+        let frequency = self.frequency as u64;
+        // Split to avoid overflowing when multiplying the whole counter by 10^9.
+        let seconds = counter / frequency;
+        let nanos = (counter % frequency) * 1_000_000_000 / frequency;
+        Duration::new(seconds, nanos as u32)
+    }
+
+    fn sleep(&self, duration: Duration) {
+        // Rounded down, so the sleep doesn't undershoot by a whole timer period.
+        let milliseconds = duration.as_millis().min(u128::from(DWORD::MAX - 1)) as DWORD;
+        if milliseconds > 0 {
+            unsafe { Sleep(milliseconds) };
+        }
+    }
+}
+
+impl Drop for PerformanceClock {
+    fn drop(&mut self) {
+        if self.granular_sleep {
+            unsafe { timeEndPeriod(Self::TIMER_PERIOD) };
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Per monitor DPI awareness.
+//!
+//! The per monitor functions only exist on recent Windows 10 versions, so they are looked up at
+//! runtime in `user32.dll`, with fallbacks to the older system wide awareness.
+//!
+//! See [High DPI Desktop Application Development on Windows](https://docs.microsoft.com/en-us/windows/win32/hidpi/high-dpi-desktop-application-development-on-windows)
+//!
+//! Like in [`safe`](super::safe), window handles are only ever dereferenced by Windows.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::{safe::*, *};
+use crate::dpi::{Dpi, PhysicalSize};
+use core::ptr::null_mut;
+use std::sync::OnceLock;
+
+/// The functions that may be missing from `user32.dll`.
+struct DpiFunctions {
+    set_process_dpi_awareness_context: Option<SetProcessDpiAwarenessContext_t>,
+    get_dpi_for_window: Option<GetDpiForWindow_t>,
+    adjust_window_rect_ex_for_dpi: Option<AdjustWindowRectExForDpi_t>,
+}
+
+/// Looks the functions up the first time.
+fn functions() -> &'static DpiFunctions {
+    static FUNCTIONS: OnceLock<DpiFunctions> = OnceLock::new();
+    FUNCTIONS.get_or_init(|| {
+        let user32_wn = wide_null("user32.dll");
+        // Already loaded, the crate links to it.
+        let user32 = unsafe { GetModuleHandleW(user32_wn.as_ptr()) };
+        let lookup = |name: &[u8]| -> FARPROC {
+            if user32.is_null() {
+                null_mut()
+            } else {
+                unsafe { GetProcAddress(user32, name.as_ptr().cast()) }
+            }
+        };
+        let set_context = lookup(b"SetProcessDpiAwarenessContext\0");
+        let get_dpi = lookup(b"GetDpiForWindow\0");
+        let adjust = lookup(b"AdjustWindowRectExForDpi\0");
+        // Safety: the signatures are the documented ones.
+        unsafe {
+            DpiFunctions {
+                set_process_dpi_awareness_context: (!set_context.is_null()).then(|| {
+                    core::mem::transmute::<FARPROC, SetProcessDpiAwarenessContext_t>(set_context)
+                }),
+                get_dpi_for_window: (!get_dpi.is_null())
+                    .then(|| core::mem::transmute::<FARPROC, GetDpiForWindow_t>(get_dpi)),
+                adjust_window_rect_ex_for_dpi: (!adjust.is_null())
+                    .then(|| core::mem::transmute::<FARPROC, AdjustWindowRectExForDpi_t>(adjust)),
+            }
+        }
+    })
+}
+
+/// Makes the process per monitor DPI aware, or at least system DPI aware on older Windows.
+///
+/// Has to happen before any window is created. Fails if the awareness was already set, e.g. by the
+/// application manifest.
+///
+/// **Returns:** Whether the process is now per monitor DPI aware.
+///
+/// See [`SetProcessDpiAwarenessContext`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setprocessdpiawarenesscontext)
+pub fn enable_per_monitor_dpi_awareness() -> bool {
+    match functions().set_process_dpi_awareness_context {
+        Some(set_process_dpi_awareness_context) => {
+            0 != unsafe {
+                set_process_dpi_awareness_context(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2)
+            }
+        }
+        None => {
+            unsafe { SetProcessDPIAware() };
+            false
+        }
+    }
+}
+
+/// The DPI of the monitor the window is on, [`Dpi::BASE`] when Windows is too old to tell.
+///
+/// See [`GetDpiForWindow`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getdpiforwindow)
+pub fn window_dpi(hwnd: HWND) -> Dpi {
+    match functions().get_dpi_for_window {
+        Some(get_dpi_for_window) => match unsafe { get_dpi_for_window(hwnd) } {
+            0 => Dpi::BASE,
+            dpi => Dpi(dpi),
+        },
+        None => Dpi::BASE,
+    }
+}
+
+/// The styles of the window, `GWL_STYLE` and `GWL_EXSTYLE`.
+pub fn window_styles(hwnd: HWND) -> (DWORD, DWORD) {
+    unsafe {
+        (
+            GetWindowLongPtrW(hwnd, GWL_STYLE) as DWORD,
+            GetWindowLongPtrW(hwnd, GWL_EXSTYLE) as DWORD,
+        )
+    }
+}
+
+/// The size of a window with a client area of `client_size`, frame included.
+///
+/// See [`AdjustWindowRectExForDpi`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-adjustwindowrectexfordpi)
+pub fn window_size_for_client(
+    client_size: PhysicalSize,
+    style: DWORD,
+    ex_style: DWORD,
+    dpi: Dpi,
+) -> Result<PhysicalSize, Win32Error> {
+    let mut rect = RECT {
+        left: 0,
+        top: 0,
+        right: client_size.width as LONG,
+        bottom: client_size.height as LONG,
+    };
+    let success = match functions().adjust_window_rect_ex_for_dpi {
+        Some(adjust_window_rect_ex_for_dpi) => unsafe {
+            adjust_window_rect_ex_for_dpi(&mut rect, style, 0, ex_style, dpi.0)
+        },
+        None => unsafe { AdjustWindowRectEx(&mut rect, style, 0, ex_style) },
+    };
+    if success == 0 {
+        return Err(get_last_error());
+    }
+    Ok(PhysicalSize::new(
+        (rect.right - rect.left) as u32,
+        (rect.bottom - rect.top) as u32,
+    ))
+}
+
+/// `GetDpiForMonitor`, from `shcore.dll` which the crate doesn't link to.
+fn get_dpi_for_monitor() -> Option<GetDpiForMonitor_t> {
+    static FUNCTION: OnceLock<Option<GetDpiForMonitor_t>> = OnceLock::new();
+    *FUNCTION.get_or_init(|| {
+        let shcore_wn = wide_null("shcore.dll");
+        // Never freed, the function is kept for the whole process.
+        let shcore = unsafe { LoadLibraryW(shcore_wn.as_ptr()) };
+        if shcore.is_null() {
+            return None;
+        }
+        let get_dpi = unsafe { GetProcAddress(shcore, b"GetDpiForMonitor\0".as_ptr().cast()) };
+        // Safety: the signature is the documented one.
+        (!get_dpi.is_null())
+            .then(|| unsafe { core::mem::transmute::<FARPROC, GetDpiForMonitor_t>(get_dpi) })
+    })
+}
+
+/// The DPI of a monitor, [`Dpi::BASE`] when Windows is too old to tell.
+///
+/// See [`GetDpiForMonitor`](https://docs.microsoft.com/en-us/windows/win32/api/shellscalingapi/nf-shellscalingapi-getdpiformonitor)
+pub fn monitor_dpi(hmonitor: HMONITOR) -> Dpi {
+    let (mut dpi_x, mut dpi_y) = (0, 0);
+    match get_dpi_for_monitor() {
+        Some(get_dpi_for_monitor)
+            if unsafe {
+                get_dpi_for_monitor(hmonitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y)
+            } == 0
+                && dpi_x != 0 =>
+        {
+            Dpi(dpi_x)
+        }
+        _ => Dpi::BASE,
+    }
+}
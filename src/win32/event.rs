@@ -0,0 +1,806 @@
+//! Decoding of raw window messages into [`WindowEvent`]s.
+//!
+//! This is plain data crunching, so it's available (and testable) on every platform.
+
+use super::*;
+use crate::{
+    dpi::{Dpi, PhysicalSize},
+    event::{DeviceEvent, Key, KeyboardInput, MouseButton, WindowEvent},
+};
+
+/// The scancode of the right shift key, the only way to tell it apart from the left one.
+const SCANCODE_RIGHT_SHIFT: u8 = 0x36;
+
+/// Decodes a window message into a [`WindowEvent`], for a window at `dpi`.
+///
+/// **Returns:** `None` for the messages that have no event counterpart.
+pub fn decode_window_message(
+    msg: UINT,
+    w_param: WPARAM,
+    l_param: LPARAM,
+    dpi: Dpi,
+) -> Option<WindowEvent> {
+    let event = match msg {
+        WM_CREATE => WindowEvent::Created,
+        // The client area size, see https://docs.microsoft.com/en-us/windows/win32/winmsg/wm-size
+        WM_SIZE => WindowEvent::Resized {
+            size: PhysicalSize::new(u32::from(LOWORD(l_param)), u32::from(HIWORD(l_param))),
+            dpi,
+        },
+        // Both words of wParam hold the new DPI, see https://docs.microsoft.com/en-us/windows/win32/hidpi/wm-dpichanged
+        WM_DPICHANGED => WindowEvent::DpiChanged(Dpi(u32::from(LOWORD(w_param)))),
+        // The client area position, see https://docs.microsoft.com/en-us/windows/win32/winmsg/wm-move
+        WM_MOVE => WindowEvent::Moved {
+            x: GET_X_LPARAM(l_param),
+            y: GET_Y_LPARAM(l_param),
+        },
+        WM_SETFOCUS => WindowEvent::Focused(true),
+        WM_KILLFOCUS => WindowEvent::Focused(false),
+        WM_CLOSE => WindowEvent::CloseRequested,
+        WM_DESTROY => WindowEvent::Destroyed,
+        WM_PAINT => WindowEvent::Paint,
+        // The virtual-key code is in wParam, see https://docs.microsoft.com/en-us/windows/win32/inputdev/wm-keydown
+        // The SYS variants are sent instead when Alt is down, or for F10.
+        WM_KEYDOWN | WM_SYSKEYDOWN => WindowEvent::KeyDown(decode_keystroke(w_param, l_param)),
+        WM_KEYUP | WM_SYSKEYUP => WindowEvent::KeyUp(decode_keystroke(w_param, l_param)),
+        WM_MOUSEMOVE => WindowEvent::MouseMove {
+            x: GET_X_LPARAM(l_param),
+            y: GET_Y_LPARAM(l_param),
+        },
+        WM_MOUSELEAVE => WindowEvent::MouseLeave,
+        // The client coordinates are in lParam, see https://docs.microsoft.com/en-us/windows/win32/inputdev/wm-lbuttondown
+        WM_LBUTTONDOWN | WM_RBUTTONDOWN | WM_MBUTTONDOWN | WM_XBUTTONDOWN => {
+            WindowEvent::MouseDown {
+                button: mouse_button(msg, w_param)?,
+                x: GET_X_LPARAM(l_param),
+                y: GET_Y_LPARAM(l_param),
+            }
+        }
+        WM_LBUTTONUP | WM_RBUTTONUP | WM_MBUTTONUP | WM_XBUTTONUP => WindowEvent::MouseUp {
+            button: mouse_button(msg, w_param)?,
+            x: GET_X_LPARAM(l_param),
+            y: GET_Y_LPARAM(l_param),
+        },
+        // Only sent to windows whose class has the CS_DBLCLKS style.
+        WM_LBUTTONDBLCLK | WM_RBUTTONDBLCLK | WM_MBUTTONDBLCLK | WM_XBUTTONDBLCLK => {
+            WindowEvent::MouseDoubleClick {
+                button: mouse_button(msg, w_param)?,
+                x: GET_X_LPARAM(l_param),
+                y: GET_Y_LPARAM(l_param),
+            }
+        }
+        // The signed rotation is the high-order word of wParam, lParam holds screen coordinates.
+        // WHEEL_NOTCH was picked to match WHEEL_DELTA.
+        WM_MOUSEWHEEL => WindowEvent::MouseWheel {
+            delta_x: 0,
+            delta_y: i32::from(HIWORD(w_param) as i16),
+        },
+        WM_MOUSEHWHEEL => WindowEvent::MouseWheel {
+            delta_x: i32::from(HIWORD(w_param) as i16),
+            delta_y: 0,
+        },
+        _ => return None,
+    };
+    Some(event)
+}
+
+/// Which button a mouse button message is about.
+///
+/// **Returns:** `None` for an unknown X button.
+fn mouse_button(msg: UINT, w_param: WPARAM) -> Option<MouseButton> {
+    match msg {
+        WM_LBUTTONDOWN | WM_LBUTTONUP | WM_LBUTTONDBLCLK => Some(MouseButton::Left),
+        WM_RBUTTONDOWN | WM_RBUTTONUP | WM_RBUTTONDBLCLK => Some(MouseButton::Right),
+        WM_MBUTTONDOWN | WM_MBUTTONUP | WM_MBUTTONDBLCLK => Some(MouseButton::Middle),
+        _ => match HIWORD(w_param) {
+            XBUTTON1 => Some(MouseButton::X1),
+            XBUTTON2 => Some(MouseButton::X2),
+            _ => None,
+        },
+    }
+}
+
+/// The keystroke message flags, packed in the `lParam` of the `WM_KEYDOWN` family.
+///
+/// See [Keystroke Message Flags](https://docs.microsoft.com/en-us/windows/win32/inputdev/about-keyboard-input#keystroke-message-flags)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeystrokeFlags {
+    /// How many times the keystroke is auto-repeated, bits 0 to 15.
+    pub repeat_count: u16,
+    /// The scancode, bits 16 to 23.
+    pub scancode: u8,
+    /// Whether the key is an extended key (right Alt and Ctrl, arrows out of the keypad, ...), bit
+    /// 24.
+    pub extended: bool,
+    /// Whether Alt is down, bit 29.
+    pub context_code: bool,
+    /// Whether the key was down before the message was sent, bit 30.
+    pub previous_state: bool,
+    /// Whether the key is being released, bit 31.
+    pub transition_state: bool,
+}
+
+impl KeystrokeFlags {
+    /// Unpacks the flags from the `lParam` of a keystroke message.
+    pub const fn from_l_param(l_param: LPARAM) -> Self {
+        let bits = l_param as u32;
+        Self {
+            repeat_count: bits as u16,
+            scancode: (bits >> 16) as u8,
+            extended: bits & (1 << 24) != 0,
+            context_code: bits & (1 << 29) != 0,
+            previous_state: bits & (1 << 30) != 0,
+            transition_state: bits & (1 << 31) != 0,
+        }
+    }
+}
+
+/// Decodes the `wParam` and `lParam` of a keystroke message.
+fn decode_keystroke(w_param: WPARAM, l_param: LPARAM) -> KeyboardInput {
+    let flags = KeystrokeFlags::from_l_param(l_param);
+    let scancode = u32::from(flags.scancode);
+    KeyboardInput {
+        key: key_from_virtual_key(w_param as u32, flags.scancode, flags.extended),
+        scancode: if flags.extended {
+            0xE000 | scancode
+        } else {
+            scancode
+        },
+        // Releases always have bit 30 set, it only means something for presses.
+        repeat: flags.previous_state && !flags.transition_state,
+        repeat_count: flags.repeat_count.max(1),
+    }
+}
+
+/// Maps a virtual-key code to a [`Key`].
+///
+/// The keystroke messages use the same code for both keys of a left/right pair, the scancode and
+/// the extended key flag are used to tell them apart.
+pub fn key_from_virtual_key(virtual_key: u32, scancode: u8, extended: bool) -> Key {
+    match virtual_key {
+        0x41..=0x5A => Key::LETTERS[(virtual_key - 0x41) as usize],
+        0x30..=0x39 => Key::DIGITS[(virtual_key - 0x30) as usize],
+        VK_NUMPAD0..=VK_NUMPAD9 => Key::NUMPAD_DIGITS[(virtual_key - VK_NUMPAD0) as usize],
+        VK_F1..=VK_F12 => Key::FUNCTION_KEYS[(virtual_key - VK_F1) as usize],
+        VK_ESCAPE => Key::Escape,
+        VK_RETURN if extended => Key::NumpadEnter,
+        VK_RETURN => Key::Enter,
+        VK_TAB => Key::Tab,
+        VK_BACK => Key::Backspace,
+        VK_SPACE => Key::Space,
+        VK_INSERT => Key::Insert,
+        VK_DELETE => Key::Delete,
+        VK_HOME => Key::Home,
+        VK_END => Key::End,
+        VK_PRIOR => Key::PageUp,
+        VK_NEXT => Key::PageDown,
+        VK_LEFT => Key::Left,
+        VK_UP => Key::Up,
+        VK_RIGHT => Key::Right,
+        VK_DOWN => Key::Down,
+        VK_SHIFT if scancode == SCANCODE_RIGHT_SHIFT => Key::RightShift,
+        VK_SHIFT | VK_LSHIFT => Key::LeftShift,
+        VK_RSHIFT => Key::RightShift,
+        VK_CONTROL if extended => Key::RightControl,
+        VK_CONTROL | VK_LCONTROL => Key::LeftControl,
+        VK_RCONTROL => Key::RightControl,
+        VK_MENU if extended => Key::RightAlt,
+        VK_MENU | VK_LMENU => Key::LeftAlt,
+        VK_RMENU => Key::RightAlt,
+        VK_LWIN => Key::LeftSuper,
+        VK_RWIN => Key::RightSuper,
+        VK_APPS => Key::Menu,
+        VK_CAPITAL => Key::CapsLock,
+        VK_NUMLOCK => Key::NumLock,
+        VK_SCROLL => Key::ScrollLock,
+        VK_SNAPSHOT => Key::PrintScreen,
+        VK_PAUSE => Key::Pause,
+        VK_ADD => Key::NumpadAdd,
+        VK_SUBTRACT => Key::NumpadSubtract,
+        VK_MULTIPLY => Key::NumpadMultiply,
+        VK_DIVIDE => Key::NumpadDivide,
+        VK_DECIMAL => Key::NumpadDecimal,
+        VK_OEM_MINUS => Key::Minus,
+        VK_OEM_PLUS => Key::Equals,
+        VK_OEM_4 => Key::LeftBracket,
+        VK_OEM_6 => Key::RightBracket,
+        VK_OEM_5 => Key::Backslash,
+        VK_OEM_1 => Key::Semicolon,
+        VK_OEM_7 => Key::Apostrophe,
+        VK_OEM_3 => Key::Grave,
+        VK_OEM_COMMA => Key::Comma,
+        VK_OEM_PERIOD => Key::Period,
+        VK_OEM_2 => Key::Slash,
+        _ => Key::Unknown,
+    }
+}
+
+/// The button transitions of [`RAWMOUSE::usButtonFlags`], as (flag, button, pressed).
+const RAW_MOUSE_BUTTONS: [(USHORT, MouseButton, bool); 10] = [
+    (RI_MOUSE_LEFT_BUTTON_DOWN, MouseButton::Left, true),
+    (RI_MOUSE_LEFT_BUTTON_UP, MouseButton::Left, false),
+    (RI_MOUSE_RIGHT_BUTTON_DOWN, MouseButton::Right, true),
+    (RI_MOUSE_RIGHT_BUTTON_UP, MouseButton::Right, false),
+    (RI_MOUSE_MIDDLE_BUTTON_DOWN, MouseButton::Middle, true),
+    (RI_MOUSE_MIDDLE_BUTTON_UP, MouseButton::Middle, false),
+    (RI_MOUSE_BUTTON_4_DOWN, MouseButton::X1, true),
+    (RI_MOUSE_BUTTON_4_UP, MouseButton::X1, false),
+    (RI_MOUSE_BUTTON_5_DOWN, MouseButton::X2, true),
+    (RI_MOUSE_BUTTON_5_UP, MouseButton::X2, false),
+];
+
+/// Decodes the raw input of a `WM_INPUT` message into [`DeviceEvent`]s, handing them over to
+/// `emit`.
+///
+/// * A single mouse input can carry a motion, several button transitions and a wheel rotation.
+/// * Absolute mouse motions (tablets, remote desktop sessions, ...) are left out, only relative
+///   ones make sense as deltas.
+/// * HID input is left out.
+pub fn decode_raw_input(raw: &RAWINPUT, mut emit: impl FnMut(DeviceEvent)) {
+    match raw.header.dwType {
+        RIM_TYPEMOUSE => {
+            // Safety: the header says which member of the union is filled.
+            let mouse = unsafe { raw.data.mouse };
+            let relative = mouse.usFlags & MOUSE_MOVE_ABSOLUTE == 0;
+            if relative && (mouse.lLastX != 0 || mouse.lLastY != 0) {
+                emit(DeviceEvent::MouseMotion {
+                    delta_x: mouse.lLastX,
+                    delta_y: mouse.lLastY,
+                });
+            }
+            for &(flag, button, pressed) in RAW_MOUSE_BUTTONS.iter() {
+                if mouse.usButtonFlags & flag != 0 {
+                    emit(DeviceEvent::MouseButton { button, pressed });
+                }
+            }
+            let rotation = i32::from(mouse.usButtonData as i16);
+            if mouse.usButtonFlags & RI_MOUSE_WHEEL != 0 {
+                emit(DeviceEvent::MouseWheel {
+                    delta_x: 0,
+                    delta_y: rotation,
+                });
+            }
+            if mouse.usButtonFlags & RI_MOUSE_HWHEEL != 0 {
+                emit(DeviceEvent::MouseWheel {
+                    delta_x: rotation,
+                    delta_y: 0,
+                });
+            }
+        }
+        RIM_TYPEKEYBOARD => {
+            // Safety: the header says which member of the union is filled.
+            let keyboard = unsafe { raw.data.keyboard };
+            // 0xFF is a fake key, part of an escaped sequence (Pause, ...).
+            if keyboard.VKey == 0xFF {
+                return;
+            }
+            let extended = keyboard.Flags & RI_KEY_E0 != 0;
+            let scancode = u32::from(keyboard.MakeCode);
+            emit(DeviceEvent::Key {
+                key: key_from_virtual_key(
+                    u32::from(keyboard.VKey),
+                    keyboard.MakeCode as u8,
+                    extended,
+                ),
+                scancode: if extended {
+                    0xE000 | scancode
+                } else if keyboard.Flags & RI_KEY_E1 != 0 {
+                    0xE100 | scancode
+                } else {
+                    scancode
+                },
+                pressed: keyboard.Flags & RI_KEY_BREAK == 0,
+            });
+        }
+        _ => {}
+    }
+}
+
+/// Reassembles the UTF-16 code units `WM_CHAR` delivers one at a time into characters.
+///
+/// Characters out of the Basic Multilingual Plane (most emojis, ...) come as a surrogate pair,
+/// in two consecutive messages.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Utf16Decoder {
+    high_surrogate: Option<u16>,
+}
+
+impl Utf16Decoder {
+    /// A decoder in the middle of nothing.
+    pub const fn new() -> Self {
+        Self {
+            high_surrogate: None,
+        }
+    }
+
+    /// Feeds the next code unit.
+    ///
+    /// **Returns:** The character it completes, if any. A low surrogate without a high one, or a
+    /// high surrogate followed by another one, gives [`char::REPLACEMENT_CHARACTER`].
+    pub fn push(&mut self, unit: u16) -> Option<char> {
+        match unit {
+            0xD800..=0xDBFF => {
+                let unpaired = self.high_surrogate.replace(unit);
+                unpaired.map(|_| char::REPLACEMENT_CHARACTER)
+            }
+            0xDC00..=0xDFFF => match self.high_surrogate.take() {
+                Some(high) => {
+                    let code_point =
+                        0x10000 + ((u32::from(high) - 0xD800) << 10) + (u32::from(unit) - 0xDC00);
+                    char::from_u32(code_point)
+                }
+                None => Some(char::REPLACEMENT_CHARACTER),
+            },
+            // A high surrogate followed by anything else is dropped.
+            _ => {
+                self.high_surrogate = None;
+                char::from_u32(u32::from(unit))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Packs two signed words into an `lParam`, the way `MAKELPARAM` does.
+    fn make_l_param(low: i16, high: i16) -> LPARAM {
+        ((high as u16 as LPARAM) << 16) | (low as u16 as LPARAM)
+    }
+
+    #[test]
+    fn coordinates_are_signed() {
+        // Mouse positions left of or above the client area, and windows on a monitor left of or
+        // above the primary one, have negative coordinates.
+        assert_eq!(
+            decode_window_message(WM_MOVE, 0, make_l_param(-1920, -40), Dpi::BASE),
+            Some(WindowEvent::Moved { x: -1920, y: -40 })
+        );
+        assert_eq!(
+            decode_window_message(WM_MOUSEMOVE, 0, make_l_param(-3, 250), Dpi::BASE),
+            Some(WindowEvent::MouseMove { x: -3, y: 250 })
+        );
+        assert_eq!(
+            decode_window_message(WM_LBUTTONDOWN, 0, make_l_param(12, -7), Dpi::BASE),
+            Some(WindowEvent::MouseDown {
+                button: MouseButton::Left,
+                x: 12,
+                y: -7
+            })
+        );
+    }
+
+    /// Packs the high word of a `wParam`, the low one being the held keys and buttons.
+    fn make_w_param(high: i16, low: usize) -> WPARAM {
+        ((high as u16 as WPARAM) << 16) | low
+    }
+
+    #[test]
+    fn wheel_rotations_are_signed() {
+        let w_param = make_w_param(-120, MK_XBUTTON1);
+        assert_eq!(
+            decode_window_message(WM_MOUSEWHEEL, w_param, 0, Dpi::BASE),
+            Some(WindowEvent::MouseWheel {
+                delta_x: 0,
+                delta_y: -120,
+            })
+        );
+        assert_eq!(
+            decode_window_message(WM_MOUSEWHEEL, make_w_param(240, 0), 0, Dpi::BASE),
+            Some(WindowEvent::MouseWheel {
+                delta_x: 0,
+                delta_y: 240,
+            })
+        );
+        assert_eq!(
+            decode_window_message(WM_MOUSEHWHEEL, make_w_param(-30, 0), 0, Dpi::BASE),
+            Some(WindowEvent::MouseWheel {
+                delta_x: -30,
+                delta_y: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn x_buttons_come_from_the_high_word() {
+        let l_param = make_l_param(4, 5);
+        let decode = |msg, button: WORD| {
+            decode_window_message(
+                msg,
+                make_w_param(button as i16, MK_XBUTTON1),
+                l_param,
+                Dpi::BASE,
+            )
+        };
+        assert_eq!(
+            decode(WM_XBUTTONDOWN, XBUTTON1),
+            Some(WindowEvent::MouseDown {
+                button: MouseButton::X1,
+                x: 4,
+                y: 5,
+            })
+        );
+        assert_eq!(
+            decode(WM_XBUTTONUP, XBUTTON2),
+            Some(WindowEvent::MouseUp {
+                button: MouseButton::X2,
+                x: 4,
+                y: 5,
+            })
+        );
+        assert_eq!(decode(WM_XBUTTONDOWN, 0x0004), None);
+        assert_eq!(decode(WM_XBUTTONUP, 0), None);
+    }
+
+    #[test]
+    fn double_clicks_are_decoded() {
+        let l_param = make_l_param(-1, 2);
+        let x_button = make_w_param(XBUTTON2 as i16, 0);
+        for &(msg, w_param, button) in &[
+            (WM_LBUTTONDBLCLK, 0, MouseButton::Left),
+            (WM_RBUTTONDBLCLK, 0, MouseButton::Right),
+            (WM_MBUTTONDBLCLK, 0, MouseButton::Middle),
+            (WM_XBUTTONDBLCLK, x_button, MouseButton::X2),
+        ] {
+            assert_eq!(
+                decode_window_message(msg, w_param, l_param, Dpi::BASE),
+                Some(WindowEvent::MouseDoubleClick {
+                    button,
+                    x: -1,
+                    y: 2
+                })
+            );
+        }
+        assert_eq!(
+            decode_window_message(WM_XBUTTONDBLCLK, 0, l_param, Dpi::BASE),
+            None
+        );
+    }
+
+    #[test]
+    fn mouse_buttons_and_leaving() {
+        let l_param = make_l_param(7, 8);
+        for &(down, up, button) in &[
+            (WM_LBUTTONDOWN, WM_LBUTTONUP, MouseButton::Left),
+            (WM_RBUTTONDOWN, WM_RBUTTONUP, MouseButton::Right),
+            (WM_MBUTTONDOWN, WM_MBUTTONUP, MouseButton::Middle),
+        ] {
+            assert_eq!(
+                decode_window_message(down, 0, l_param, Dpi::BASE),
+                Some(WindowEvent::MouseDown { button, x: 7, y: 8 })
+            );
+            assert_eq!(
+                decode_window_message(up, 0, l_param, Dpi::BASE),
+                Some(WindowEvent::MouseUp { button, x: 7, y: 8 })
+            );
+        }
+        assert_eq!(
+            decode_window_message(WM_MOUSELEAVE, 0, 0, Dpi::BASE),
+            Some(WindowEvent::MouseLeave)
+        );
+    }
+
+    #[test]
+    fn sizes_are_unsigned_and_carry_the_dpi() {
+        let l_param = (1080 << 16) | 1920;
+        assert_eq!(
+            decode_window_message(WM_SIZE, 0, l_param, Dpi(144)),
+            Some(WindowEvent::Resized {
+                size: PhysicalSize::new(1920, 1080),
+                dpi: Dpi(144)
+            })
+        );
+        // Words past 32767 are sizes, not negative numbers.
+        let l_param = (0xFFFF << 16) | 0x8000;
+        assert_eq!(
+            decode_window_message(WM_SIZE, 0, l_param, Dpi::BASE),
+            Some(WindowEvent::Resized {
+                size: PhysicalSize::new(0x8000, 0xFFFF),
+                dpi: Dpi::BASE
+            })
+        );
+    }
+
+    #[test]
+    fn keystroke_flags_are_unpacked() {
+        let flags = KeystrokeFlags::from_l_param(0x001E_0003);
+        assert_eq!(
+            flags,
+            KeystrokeFlags {
+                repeat_count: 3,
+                scancode: 0x1E,
+                extended: false,
+                context_code: false,
+                previous_state: false,
+                transition_state: false,
+            }
+        );
+        let flags = KeystrokeFlags::from_l_param(0xE11D_0001);
+        assert_eq!(
+            flags,
+            KeystrokeFlags {
+                repeat_count: 1,
+                scancode: 0x1D,
+                extended: true,
+                context_code: true,
+                previous_state: true,
+                transition_state: true,
+            }
+        );
+    }
+
+    #[test]
+    fn keystrokes_are_decoded() {
+        // A first press of A.
+        let input = KeyboardInput {
+            key: Key::A,
+            scancode: 0x1E,
+            repeat: false,
+            repeat_count: 1,
+        };
+        assert_eq!(
+            decode_window_message(WM_KEYDOWN, 0x41, 0x001E_0001, Dpi::BASE),
+            Some(WindowEvent::KeyDown(input))
+        );
+        // Auto-repeated presses have the previous state bit set.
+        let input = KeyboardInput {
+            key: Key::A,
+            scancode: 0x1E,
+            repeat: true,
+            repeat_count: 2,
+        };
+        assert_eq!(
+            decode_window_message(WM_KEYDOWN, 0x41, 0x401E_0002, Dpi::BASE),
+            Some(WindowEvent::KeyDown(input))
+        );
+        // Releases have it set too, but they are never repeats.
+        let input = KeyboardInput {
+            key: Key::A,
+            scancode: 0x1E,
+            repeat: false,
+            repeat_count: 1,
+        };
+        assert_eq!(
+            decode_window_message(WM_KEYUP, 0x41, 0xC01E_0001, Dpi::BASE),
+            Some(WindowEvent::KeyUp(input))
+        );
+        // Extended keys get the 0xE0 prefix, right Ctrl here.
+        let input = KeyboardInput {
+            key: Key::RightControl,
+            scancode: 0xE01D,
+            repeat: false,
+            repeat_count: 1,
+        };
+        assert_eq!(
+            decode_window_message(WM_KEYDOWN, VK_CONTROL as WPARAM, 0x011D_0001, Dpi::BASE),
+            Some(WindowEvent::KeyDown(input))
+        );
+    }
+
+    #[test]
+    fn modifiers_are_told_apart() {
+        // Shift only has the scancode to go by.
+        assert_eq!(key_from_virtual_key(VK_SHIFT, 0x2A, false), Key::LeftShift);
+        assert_eq!(key_from_virtual_key(VK_SHIFT, 0x36, false), Key::RightShift);
+        // Ctrl and Alt have the extended key flag.
+        assert_eq!(
+            key_from_virtual_key(VK_CONTROL, 0x1D, false),
+            Key::LeftControl
+        );
+        assert_eq!(
+            key_from_virtual_key(VK_CONTROL, 0x1D, true),
+            Key::RightControl
+        );
+        assert_eq!(key_from_virtual_key(VK_MENU, 0x38, false), Key::LeftAlt);
+        assert_eq!(key_from_virtual_key(VK_MENU, 0x38, true), Key::RightAlt);
+        // The sided codes, as given by raw input or `GetKeyState`, need neither.
+        assert_eq!(key_from_virtual_key(VK_LSHIFT, 0, false), Key::LeftShift);
+        assert_eq!(key_from_virtual_key(VK_RSHIFT, 0, false), Key::RightShift);
+        assert_eq!(
+            key_from_virtual_key(VK_LCONTROL, 0, false),
+            Key::LeftControl
+        );
+        assert_eq!(
+            key_from_virtual_key(VK_RCONTROL, 0, false),
+            Key::RightControl
+        );
+        assert_eq!(key_from_virtual_key(VK_LMENU, 0, false), Key::LeftAlt);
+        assert_eq!(key_from_virtual_key(VK_RMENU, 0, false), Key::RightAlt);
+        // Same thing for the two Enter keys.
+        assert_eq!(key_from_virtual_key(VK_RETURN, 0x1C, false), Key::Enter);
+        assert_eq!(
+            key_from_virtual_key(VK_RETURN, 0x1C, true),
+            Key::NumpadEnter
+        );
+    }
+
+    /// Feeds all of `units`, collecting the characters.
+    fn decode_utf16(decoder: &mut Utf16Decoder, units: &[u16]) -> String {
+        units
+            .iter()
+            .filter_map(|&unit| decoder.push(unit))
+            .collect()
+    }
+
+    #[test]
+    fn surrogate_pairs_are_reassembled() {
+        let mut decoder = Utf16Decoder::new();
+        let units: Vec<u16> = "a\u{e9}\u{1F600}z\u{10FFFF}".encode_utf16().collect();
+        assert_eq!(
+            decode_utf16(&mut decoder, &units),
+            "a\u{e9}\u{1F600}z\u{10FFFF}"
+        );
+        // The high surrogate alone completes nothing.
+        assert_eq!(decoder.push(0xD83D), None);
+        assert_eq!(decoder.push(0xDE00), Some('\u{1F600}'));
+    }
+
+    #[test]
+    fn lone_surrogates_are_replaced_or_dropped() {
+        let mut decoder = Utf16Decoder::new();
+        // A low surrogate without a high one.
+        assert_eq!(decode_utf16(&mut decoder, &[0xDE00, 0x61]), "\u{FFFD}a");
+        // A high surrogate followed by another one, which still pairs with what follows.
+        assert_eq!(
+            decode_utf16(&mut decoder, &[0xD83D, 0xD83D, 0xDE00]),
+            "\u{FFFD}\u{1F600}"
+        );
+        // A high surrogate followed by anything else.
+        assert_eq!(
+            decode_utf16(&mut decoder, &[0xD83D, 0x62, 0xDE00]),
+            "b\u{FFFD}"
+        );
+    }
+
+    fn raw_mouse(mouse: RAWMOUSE) -> RAWINPUT {
+        RAWINPUT {
+            header: RAWINPUTHEADER {
+                dwType: RIM_TYPEMOUSE,
+                ..RAWINPUTHEADER::default()
+            },
+            data: RAWINPUT_data { mouse },
+        }
+    }
+
+    fn raw_keyboard(keyboard: RAWKEYBOARD) -> RAWINPUT {
+        RAWINPUT {
+            header: RAWINPUTHEADER {
+                dwType: RIM_TYPEKEYBOARD,
+                ..RAWINPUTHEADER::default()
+            },
+            data: RAWINPUT_data { keyboard },
+        }
+    }
+
+    fn decode(raw: &RAWINPUT) -> Vec<DeviceEvent> {
+        let mut events = Vec::new();
+        decode_raw_input(raw, |event| events.push(event));
+        events
+    }
+
+    #[test]
+    fn raw_mouse_motions_are_relative() {
+        let mouse = RAWMOUSE {
+            lLastX: -3,
+            lLastY: 5,
+            ..RAWMOUSE::default()
+        };
+        assert_eq!(
+            decode(&raw_mouse(mouse)),
+            [DeviceEvent::MouseMotion {
+                delta_x: -3,
+                delta_y: 5,
+            }]
+        );
+        // Absolute positions, from tablets and remote sessions.
+        let absolute = RAWMOUSE {
+            usFlags: MOUSE_MOVE_ABSOLUTE,
+            ..mouse
+        };
+        assert_eq!(decode(&raw_mouse(absolute)), []);
+        // Only buttons moved.
+        assert_eq!(decode(&raw_mouse(RAWMOUSE::default())), []);
+    }
+
+    #[test]
+    fn raw_mouse_buttons_give_an_event_each() {
+        let mouse = RAWMOUSE {
+            usButtonFlags: RI_MOUSE_LEFT_BUTTON_UP
+                | RI_MOUSE_RIGHT_BUTTON_DOWN
+                | RI_MOUSE_BUTTON_4_DOWN
+                | RI_MOUSE_BUTTON_5_UP,
+            lLastX: 1,
+            ..RAWMOUSE::default()
+        };
+        let button = |button, pressed| DeviceEvent::MouseButton { button, pressed };
+        assert_eq!(
+            decode(&raw_mouse(mouse)),
+            [
+                DeviceEvent::MouseMotion {
+                    delta_x: 1,
+                    delta_y: 0,
+                },
+                button(MouseButton::Left, false),
+                button(MouseButton::Right, true),
+                button(MouseButton::X1, true),
+                button(MouseButton::X2, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn raw_wheel_rotations_are_signed() {
+        let wheel = |flags, data: i16| RAWMOUSE {
+            usButtonFlags: flags,
+            usButtonData: data as USHORT,
+            ..RAWMOUSE::default()
+        };
+        assert_eq!(
+            decode(&raw_mouse(wheel(RI_MOUSE_WHEEL, -120))),
+            [DeviceEvent::MouseWheel {
+                delta_x: 0,
+                delta_y: -120,
+            }]
+        );
+        assert_eq!(
+            decode(&raw_mouse(wheel(RI_MOUSE_WHEEL, 240))),
+            [DeviceEvent::MouseWheel {
+                delta_x: 0,
+                delta_y: 240,
+            }]
+        );
+        assert_eq!(
+            decode(&raw_mouse(wheel(RI_MOUSE_HWHEEL, -30))),
+            [DeviceEvent::MouseWheel {
+                delta_x: -30,
+                delta_y: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn raw_keys_carry_the_scancode_prefixes() {
+        let key = |make_code, flags, virtual_key: u32| {
+            decode(&raw_keyboard(RAWKEYBOARD {
+                MakeCode: make_code,
+                Flags: flags,
+                VKey: virtual_key as USHORT,
+                ..RAWKEYBOARD::default()
+            }))
+        };
+        assert_eq!(
+            key(0x1E, RI_KEY_MAKE, 0x41),
+            [DeviceEvent::Key {
+                key: Key::A,
+                scancode: 0x1E,
+                pressed: true,
+            }]
+        );
+        assert_eq!(
+            key(0x1D, RI_KEY_E0 | RI_KEY_BREAK, VK_CONTROL),
+            [DeviceEvent::Key {
+                key: Key::RightControl,
+                scancode: 0xE01D,
+                pressed: false,
+            }]
+        );
+        assert_eq!(
+            key(0x1D, RI_KEY_E1, VK_PAUSE),
+            [DeviceEvent::Key {
+                key: Key::Pause,
+                scancode: 0xE11D,
+                pressed: true,
+            }]
+        );
+        // The fake keys of escaped sequences.
+        assert_eq!(key(0x2A, RI_KEY_E0, 0xFF), []);
+    }
+
+    #[test]
+    fn raw_hid_input_is_left_out() {
+        let mut raw = RAWINPUT::default();
+        raw.header.dwType = RIM_TYPEHID;
+        assert_eq!(decode(&raw), []);
+    }
+}
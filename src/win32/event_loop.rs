@@ -0,0 +1,232 @@
+//! A closure based event loop, hiding the message retrieval and dispatch plumbing.
+//!
+//! The window procedure of the windows created through the loop only records [`Event`]s, the
+//! loop hands them over to its handler once `DispatchMessageW` returns. This way the handler is
+//! free to call back into Win32 (destroying a window, showing a message box, ...) without ever
+//! being re-entered.
+
+use super::{
+    class::{RegisteredClass, WindowClass},
+    dpi,
+    event::{decode_raw_input, decode_window_message, Utf16Decoder},
+    monitor::{self, Monitor},
+    safe::*,
+    window, *,
+};
+use crate::event::{ControlFlow, DeviceId, Event, PlatformEventLoop, WindowEvent, WindowId};
+use core::{marker::PhantomData, ptr::null_mut};
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+};
+
+thread_local! {
+    /// Events recorded by [`event_loop_procedure`], waiting for the handler.
+    static PENDING_EVENTS: RefCell<VecDeque<Event>> = const { RefCell::new(VecDeque::new()) };
+    /// The `WM_CHAR` code units seen so far, only one window has the keyboard focus at a time.
+    static TEXT_DECODER: Cell<Utf16Decoder> = const { Cell::new(Utf16Decoder::new()) };
+    /// The window the cursor is over, waiting for its `WM_MOUSELEAVE`.
+    static HOVERED_WINDOW: Cell<HWND> = const { Cell::new(null_mut()) };
+}
+
+/// The `MK_*` flags of the mouse buttons.
+const MK_BUTTONS: usize = MK_LBUTTON | MK_RBUTTON | MK_MBUTTON | MK_XBUTTON1 | MK_XBUTTON2;
+
+fn push_event(event: Event) {
+    PENDING_EVENTS.with(|events| events.borrow_mut().push_back(event));
+}
+
+fn pop_event() -> Option<Event> {
+    PENDING_EVENTS.with(|events| events.borrow_mut().pop_front())
+}
+
+/// Runs the windows of the current thread, see [`EventLoop::run`].
+pub struct EventLoop {
+    /// Registered for as long as the loop or one of its windows is around.
+    _class: RegisteredClass,
+    // Window messages are per thread, so is the loop.
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl EventLoop {
+    /// The window class used for the windows created through the loop.
+    pub const CLASS_NAME: &'static str = "triangle_from_scratch window";
+
+    /// Prepares the event loop, registering its window class if needed.
+    ///
+    /// * Makes the process per monitor DPI aware, for the windows to be scaled rather than
+    ///   stretched, see [`dpi::enable_per_monitor_dpi_awareness`].
+    pub fn new() -> Result<Self, Win32Error> {
+        // Fails when already done, by an earlier loop or the manifest: fine either way.
+        let _per_monitor = dpi::enable_per_monitor_dpi_awareness();
+        let class = WindowClass::new(Self::CLASS_NAME)
+            .with_style(CS_OWNDC | CS_HREDRAW | CS_VREDRAW | CS_DBLCLKS)
+            .with_window_procedure(event_loop_procedure)
+            // Painted by DefWindowProcW, which validates the window at the same time.
+            .with_system_color_background(SysColor::WINDOW)
+            .register()?;
+        Ok(Self {
+            _class: class,
+            _not_send: PhantomData,
+        })
+    }
+
+    /// Starts reporting [`Event::DeviceEvent`]s for the mouse and the keyboard, on top of the
+    /// window events.
+    ///
+    /// * They go through the window with the keyboard focus, so they are only reported while one
+    ///   of the windows of the loop has it.
+    ///
+    /// See [Raw Input](https://docs.microsoft.com/en-us/windows/win32/inputdev/raw-input)
+    pub fn enable_device_events(&self) -> Result<(), Win32Error> {
+        let device = |usage| RAWINPUTDEVICE {
+            usUsagePage: HID_USAGE_PAGE_GENERIC,
+            usUsage: usage,
+            dwFlags: 0,
+            hwndTarget: null_mut(),
+        };
+        register_raw_input_devices(&[
+            device(HID_USAGE_GENERIC_MOUSE),
+            device(HID_USAGE_GENERIC_KEYBOARD),
+        ])
+    }
+
+    /// Every monitor of the desktop, see [`monitor::available_monitors`].
+    pub fn available_monitors(&self) -> Result<Vec<Monitor>, Win32Error> {
+        monitor::available_monitors()
+    }
+
+    /// The primary monitor, see [`Monitor::primary`].
+    pub fn primary_monitor(&self) -> Option<Monitor> {
+        Some(Monitor::primary())
+    }
+
+    /// Runs the loop until the handler asks to [`ControlFlow::Exit`], or a `WM_QUIT` message is
+    /// received.
+    ///
+    /// The handler is called for every event, then once with [`Event::MainEventsCleared`] before
+    /// the loop waits or polls again depending on the [`ControlFlow`] it left behind.
+    ///
+    /// * Events are delivered once the message that produced them has been dispatched. While
+    ///   Windows runs a modal loop (the user moving or resizing a window, a message box, ...)
+    ///   they pile up and are delivered afterwards.
+    ///
+    /// **Returns:** The exit code.
+    pub fn run<F>(&mut self, mut handler: F) -> Result<i32, Win32Error>
+    where
+        F: FnMut(Event, &mut ControlFlow),
+    {
+        let mut control_flow = ControlFlow::default();
+        let exit_code = loop {
+            if let Some(exit_code) = self.pump_messages(control_flow)? {
+                break exit_code;
+            }
+            while let Some(event) = pop_event() {
+                handler(event, &mut control_flow);
+            }
+            handler(Event::MainEventsCleared, &mut control_flow);
+            if let ControlFlow::Exit(exit_code) = control_flow {
+                break exit_code;
+            }
+        };
+        // Don't leak events into a later run.
+        PENDING_EVENTS.with(|events| events.borrow_mut().clear());
+        Ok(exit_code)
+    }
+
+    /// Dispatches the messages of the thread's queue, waiting for one first if asked to.
+    ///
+    /// **Returns:** The exit code of the `WM_QUIT` message, if there was one.
+    fn pump_messages(&mut self, control_flow: ControlFlow) -> Result<Option<i32>, Win32Error> {
+        if control_flow == ControlFlow::Wait {
+            let msg = get_any_message()?;
+            if msg.message == WM_QUIT {
+                return Ok(Some(msg.wParam as i32));
+            }
+            translate_message(&msg);
+            dispatch_message(&msg);
+        }
+        Ok(poll_messages())
+    }
+}
+
+impl PlatformEventLoop for EventLoop {
+    type Error = Win32Error;
+
+    fn run<F>(&mut self, handler: F) -> Result<i32, Win32Error>
+    where
+        F: FnMut(Event, &mut ControlFlow),
+    {
+        EventLoop::run(self, handler)
+    }
+}
+
+/// The window procedure of the [`EventLoop`] window class.
+unsafe extern "system" fn event_loop_procedure(
+    hwnd: HWND,
+    msg: UINT,
+    w_param: WPARAM,
+    l_param: LPARAM,
+) -> LRESULT {
+    let window_id = WindowId(hwnd as usize);
+    // Windows has no enter message, the first move after a leave stands for it.
+    if msg == WM_MOUSEMOVE
+        && HOVERED_WINDOW.with(Cell::get) != hwnd
+        && track_mouse_leave(hwnd).is_ok()
+    {
+        HOVERED_WINDOW.with(|hovered| hovered.set(hwnd));
+        push_event(Event::WindowEvent {
+            window_id,
+            event: WindowEvent::MouseEnter,
+        });
+    }
+    if let Some(event) = decode_window_message(msg, w_param, l_param, dpi::window_dpi(hwnd)) {
+        push_event(Event::WindowEvent { window_id, event });
+    }
+    if msg == WM_CHAR {
+        let character = TEXT_DECODER.with(|decoder| {
+            let mut current = decoder.get();
+            let character = current.push(w_param as u16);
+            decoder.set(current);
+            character
+        });
+        if let Some(character) = character.filter(|character| !character.is_control()) {
+            push_event(Event::WindowEvent {
+                window_id,
+                event: WindowEvent::TextInput(character),
+            });
+        }
+    }
+    match msg {
+        // DefWindowProcW still has to see the message, to clean up after it. A packet that can't
+        // be read is lost, like one the system drops under load.
+        WM_INPUT => {
+            if let Ok(raw) = get_raw_input_data(l_param as HRAWINPUT) {
+                let device_id = DeviceId(raw.header.hDevice as usize);
+                decode_raw_input(&raw, |event| {
+                    push_event(Event::DeviceEvent { device_id, event })
+                });
+            }
+        }
+        WM_MOUSELEAVE | WM_NCDESTROY => HOVERED_WINDOW.with(|hovered| {
+            if hovered.get() == hwnd {
+                hovered.set(null_mut());
+            }
+        }),
+        // Keep getting the moves and the release while a button is held, even out of the window.
+        WM_LBUTTONDOWN | WM_RBUTTONDOWN | WM_MBUTTONDOWN | WM_XBUTTONDOWN | WM_LBUTTONDBLCLK
+        | WM_RBUTTONDBLCLK | WM_MBUTTONDBLCLK | WM_XBUTTONDBLCLK => {
+            set_capture(hwnd);
+        }
+        WM_LBUTTONUP | WM_RBUTTONUP | WM_MBUTTONUP | WM_XBUTTONUP if w_param & MK_BUTTONS == 0 => {
+            // Only fails when the capture was already taken away, which is just as good.
+            let _ = release_capture();
+        }
+        _ => {}
+    }
+    match msg {
+        // Closing is up to the handler, see `WindowEvent::CloseRequested`.
+        WM_CLOSE => 0,
+        _ => window::window_procedure(hwnd, msg, w_param, l_param),
+    }
+}
@@ -0,0 +1,43 @@
+//! DLLs loaded at runtime, through `LoadLibraryW`.
+
+use super::{safe::*, *};
+use std::{os::windows::ffi::OsStrExt, path::Path};
+
+/// A DLL, freed on drop.
+pub struct Library {
+    module: HMODULE,
+}
+
+impl Library {
+    /// Loads the DLL at `path`.
+    ///
+    /// See [`LoadLibraryW`](https://docs.microsoft.com/en-us/windows/win32/api/libloaderapi/nf-libloaderapi-loadlibraryw)
+    pub fn load(path: &Path) -> Result<Self, Win32Error> {
+        let path_wn: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+        let module = unsafe { LoadLibraryW(path_wn.as_ptr()) };
+        if module.is_null() {
+            Err(get_last_error())
+        } else {
+            Ok(Self { module })
+        }
+    }
+
+    /// Looks up the address of the exported function `name`.
+    ///
+    /// See [`GetProcAddress`](https://docs.microsoft.com/en-us/windows/win32/api/libloaderapi/nf-libloaderapi-getprocaddress)
+    pub fn symbol(&self, name: &str) -> Result<FARPROC, Win32Error> {
+        let name_c: Vec<u8> = name.bytes().chain(Some(0)).collect();
+        let address = unsafe { GetProcAddress(self.module, name_c.as_ptr().cast()) };
+        if address.is_null() {
+            Err(get_last_error())
+        } else {
+            Ok(address)
+        }
+    }
+}
+
+impl Drop for Library {
+    fn drop(&mut self) {
+        unsafe { FreeLibrary(self.module) };
+    }
+}
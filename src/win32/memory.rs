@@ -0,0 +1,60 @@
+//! Pages straight from the virtual memory manager.
+
+use super::{safe::*, *};
+use core::ptr::null_mut;
+
+/// `ERROR_INVALID_ADDRESS`, when the pages can't be placed where asked.
+const ERROR_INVALID_ADDRESS: DWORD = 487;
+
+/// A block of committed pages, released on drop.
+pub struct VirtualMemory {
+    base: *mut u8,
+    size: usize,
+}
+
+impl VirtualMemory {
+    /// Reserves and commits `size` zeroed bytes, at `base_address` if there is one.
+    ///
+    /// `base_address` has to be a multiple of the allocation granularity, 64KiB.
+    ///
+    /// See [`VirtualAlloc`](https://docs.microsoft.com/en-us/windows/win32/api/memoryapi/nf-memoryapi-virtualalloc)
+    pub fn allocate(base_address: Option<usize>, size: usize) -> Result<Self, Win32Error> {
+        let requested = base_address.map_or(null_mut(), |address| address as LPVOID);
+        let base =
+            unsafe { VirtualAlloc(requested, size, MEM_RESERVE | MEM_COMMIT, PAGE_READWRITE) };
+        if base.is_null() {
+            return Err(get_last_error());
+        }
+        let memory = Self {
+            base: base.cast(),
+            size,
+        };
+        // Dropped on the way out when misplaced.
+        if !requested.is_null() && base != requested {
+            return Err(Win32Error(ERROR_INVALID_ADDRESS));
+        }
+        Ok(memory)
+    }
+
+    /// The first byte.
+    pub fn as_ptr(&self) -> *mut u8 {
+        self.base
+    }
+
+    /// The size in bytes, as asked for.
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the block is empty, which it never is.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+}
+
+impl Drop for VirtualMemory {
+    /// See [`VirtualFree`](https://docs.microsoft.com/en-us/windows/win32/api/memoryapi/nf-memoryapi-virtualfree)
+    fn drop(&mut self) {
+        unsafe { VirtualFree(self.base.cast(), 0, MEM_RELEASE) };
+    }
+}
@@ -0,0 +1,283 @@
+//! Monitors, their display modes, and switching between them.
+//!
+//! Like in [`safe`](super::safe), window handles are only ever dereferenced by Windows.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::{dpi, safe::*, *};
+use crate::{
+    dpi::{Dpi, PhysicalSize},
+    monitor::{MonitorInfo, VideoMode},
+};
+use core::ptr::{null, null_mut};
+
+/// `ERROR_NOT_SUPPORTED`, when the display mode can't be set.
+///
+/// `ChangeDisplaySettingsExW` returns a `DISP_CHANGE_*` code rather than setting the last error.
+const ERROR_NOT_SUPPORTED: DWORD = 50;
+
+/// Every monitor of the desktop, in no particular order.
+///
+/// See [`EnumDisplayMonitors`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-enumdisplaymonitors)
+pub fn available_monitors() -> Result<Vec<Monitor>, Win32Error> {
+    unsafe extern "system" fn push_monitor(
+        hmonitor: HMONITOR,
+        _hdc: HDC,
+        _rect: LPRECT,
+        data: LPARAM,
+    ) -> BOOL {
+        let monitors = &mut *(data as *mut Vec<Monitor>);
+        monitors.push(Monitor { hmonitor });
+        // Keep going.
+        1
+    }
+    let mut monitors = Vec::new();
+    let data = &mut monitors as *mut Vec<Monitor> as LPARAM;
+    if 0 == unsafe { EnumDisplayMonitors(null_mut(), null(), Some(push_monitor), data) } {
+        return Err(get_last_error());
+    }
+    Ok(monitors)
+}
+
+/// A monitor, valid for as long as it stays connected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Monitor {
+    hmonitor: HMONITOR,
+}
+
+impl Monitor {
+    /// The monitor the window is on, or the nearest one.
+    ///
+    /// See [`MonitorFromWindow`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-monitorfromwindow)
+    pub fn from_window(hwnd: HWND) -> Self {
+        Self {
+            hmonitor: unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST) },
+        }
+    }
+
+    /// The primary monitor, the one with the taskbar at `[0, 0]`.
+    ///
+    /// See [`MonitorFromPoint`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-monitorfrompoint)
+    pub fn primary() -> Self {
+        let origin = POINT { x: 0, y: 0 };
+        Self {
+            hmonitor: unsafe { MonitorFromPoint(origin, MONITOR_DEFAULTTOPRIMARY) },
+        }
+    }
+
+    /// The handle of the monitor.
+    pub fn hmonitor(self) -> HMONITOR {
+        self.hmonitor
+    }
+
+    /// Everything the monitor reports about itself.
+    pub fn info(self) -> Result<MonitorInfo, Win32Error> {
+        let info = self.monitor_info_ex()?;
+        let rect = info.monitorInfo.rcMonitor;
+        let current = display_settings(&info.szDevice, ENUM_CURRENT_SETTINGS);
+        Ok(MonitorInfo {
+            name: device_name(&info.szDevice),
+            position: [rect.left, rect.top],
+            size: rect_size(&rect),
+            refresh_rate: current.map_or(0, |mode| mode.refresh_rate),
+            dpi: self.dpi(),
+            is_primary: info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+            video_modes: video_modes(&info.szDevice),
+        })
+    }
+
+    /// The name of the display device, e.g. `\\.\DISPLAY1`.
+    pub fn name(self) -> Result<String, Win32Error> {
+        Ok(device_name(&self.monitor_info_ex()?.szDevice))
+    }
+
+    /// The top left corner, in virtual screen coordinates.
+    pub fn position(self) -> Result<[i32; 2], Win32Error> {
+        let rect = self.rect()?;
+        Ok([rect.left, rect.top])
+    }
+
+    /// The resolution, in physical pixels.
+    pub fn size(self) -> Result<PhysicalSize, Win32Error> {
+        Ok(rect_size(&self.rect()?))
+    }
+
+    /// Whether this is the primary monitor.
+    pub fn is_primary(self) -> Result<bool, Win32Error> {
+        Ok(self.monitor_info_ex()?.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0)
+    }
+
+    /// The DPI of the monitor, see [`dpi::monitor_dpi`].
+    pub fn dpi(self) -> Dpi {
+        dpi::monitor_dpi(self.hmonitor)
+    }
+
+    /// How many physical pixels make a logical pixel on this monitor.
+    pub fn scale_factor(self) -> f64 {
+        self.dpi().scale_factor()
+    }
+
+    /// The refresh rate of the current display mode in hertz, 0 when unknown.
+    pub fn refresh_rate(self) -> Result<u32, Win32Error> {
+        Ok(self
+            .current_video_mode()?
+            .map_or(0, |mode| mode.refresh_rate))
+    }
+
+    /// The display mode the monitor is in.
+    ///
+    /// See [`EnumDisplaySettingsW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-enumdisplaysettingsw)
+    pub fn current_video_mode(self) -> Result<Option<VideoMode>, Win32Error> {
+        let info = self.monitor_info_ex()?;
+        Ok(display_settings(&info.szDevice, ENUM_CURRENT_SETTINGS))
+    }
+
+    /// Every display mode the monitor can be switched to, without duplicates.
+    ///
+    /// See [`EnumDisplaySettingsW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-enumdisplaysettingsw)
+    pub fn video_modes(self) -> Result<Vec<VideoMode>, Win32Error> {
+        Ok(video_modes(&self.monitor_info_ex()?.szDevice))
+    }
+
+    /// The bounds and the device name of the monitor.
+    ///
+    /// See [`GetMonitorInfoW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getmonitorinfow)
+    fn monitor_info_ex(self) -> Result<MONITORINFOEXW, Win32Error> {
+        let mut info = MONITORINFOEXW::default();
+        info.monitorInfo.cbSize = core::mem::size_of::<MONITORINFOEXW>() as DWORD;
+        if 0 == unsafe { GetMonitorInfoW(self.hmonitor, &mut info.monitorInfo) } {
+            return Err(get_last_error());
+        }
+        Ok(info)
+    }
+
+    /// The monitor, in virtual screen coordinates.
+    pub fn rect(self) -> Result<RECT, Win32Error> {
+        Ok(self.monitor_info_ex()?.monitorInfo.rcMonitor)
+    }
+
+    /// Switches the monitor to `mode` until the returned value is dropped.
+    ///
+    /// The change is temporary: the mode in the registry is left alone, and Windows goes back to
+    /// it if the process dies before then.
+    ///
+    /// See [`ChangeDisplaySettingsExW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-changedisplaysettingsexw)
+    pub fn set_video_mode(self, mode: &VideoMode) -> Result<DisplayModeChange, Win32Error> {
+        let info = self.monitor_info_ex()?;
+        let mut fields = DM_PELSWIDTH | DM_PELSHEIGHT;
+        if mode.bits_per_pixel != 0 {
+            fields |= DM_BITSPERPEL;
+        }
+        if mode.refresh_rate != 0 {
+            fields |= DM_DISPLAYFREQUENCY;
+        }
+        let mut dev_mode = DEVMODEW {
+            dmSize: core::mem::size_of::<DEVMODEW>() as WORD,
+            dmFields: fields,
+            dmPelsWidth: mode.size.width,
+            dmPelsHeight: mode.size.height,
+            dmBitsPerPel: mode.bits_per_pixel,
+            dmDisplayFrequency: mode.refresh_rate,
+            ..DEVMODEW::default()
+        };
+        let result = unsafe {
+            ChangeDisplaySettingsExW(
+                info.szDevice.as_ptr(),
+                &mut dev_mode,
+                null_mut(),
+                CDS_FULLSCREEN,
+                null_mut(),
+            )
+        };
+        if result != DISP_CHANGE_SUCCESSFUL {
+            return Err(Win32Error(ERROR_NOT_SUPPORTED));
+        }
+        Ok(DisplayModeChange {
+            device_name: info.szDevice,
+        })
+    }
+}
+
+/// The name in a `szDevice`, up to the first null.
+fn device_name(device: &[WCHAR]) -> String {
+    let len = device.iter().position(|&c| c == 0).unwrap_or(device.len());
+    String::from_utf16_lossy(&device[..len])
+}
+
+fn rect_size(rect: &RECT) -> PhysicalSize {
+    PhysicalSize::new(
+        (rect.right - rect.left) as u32,
+        (rect.bottom - rect.top) as u32,
+    )
+}
+
+/// The display mode `mode_num` of the display device `device`, or [`ENUM_CURRENT_SETTINGS`].
+fn display_settings(device: &[WCHAR; CCHDEVICENAME], mode_num: DWORD) -> Option<VideoMode> {
+    let mut dev_mode = DEVMODEW {
+        dmSize: core::mem::size_of::<DEVMODEW>() as WORD,
+        ..DEVMODEW::default()
+    };
+    if 0 == unsafe { EnumDisplaySettingsW(device.as_ptr(), mode_num, &mut dev_mode) } {
+        return None;
+    }
+    Some(VideoMode {
+        size: PhysicalSize::new(dev_mode.dmPelsWidth, dev_mode.dmPelsHeight),
+        bits_per_pixel: dev_mode.dmBitsPerPel,
+        // 0 and 1 both stand for the default of the hardware.
+        refresh_rate: if dev_mode.dmDisplayFrequency > 1 {
+            dev_mode.dmDisplayFrequency
+        } else {
+            0
+        },
+    })
+}
+
+/// Every display mode of the display device `device`, in the order Windows lists them.
+fn video_modes(device: &[WCHAR; CCHDEVICENAME]) -> Vec<VideoMode> {
+    let mut modes = Vec::new();
+    // The same mode is listed once per orientation and scaling option.
+    for mode in (0..).map_while(|mode_num| display_settings(device, mode_num)) {
+        if !modes.contains(&mode) {
+            modes.push(mode);
+        }
+    }
+    modes
+}
+
+/// A display mode set by [`Monitor::set_video_mode`], the registry one is put back on drop.
+#[derive(Debug)]
+pub struct DisplayModeChange {
+    device_name: [WCHAR; CCHDEVICENAME],
+}
+
+impl DisplayModeChange {
+    /// Puts the registry display mode back now, unlike dropping this which can't report failures.
+    pub fn restore(self) -> Result<(), Win32Error> {
+        let result = self.restore_registry_mode();
+        core::mem::forget(self);
+        result
+    }
+
+    /// See [`ChangeDisplaySettingsExW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-changedisplaysettingsexw)
+    fn restore_registry_mode(&self) -> Result<(), Win32Error> {
+        let result = unsafe {
+            ChangeDisplaySettingsExW(
+                self.device_name.as_ptr(),
+                null_mut(),
+                null_mut(),
+                0,
+                null_mut(),
+            )
+        };
+        if result != DISP_CHANGE_SUCCESSFUL {
+            return Err(Win32Error(ERROR_NOT_SUPPORTED));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for DisplayModeChange {
+    fn drop(&mut self) {
+        // Call `restore` first to find out whether it failed.
+        let _ = self.restore_registry_mode();
+    }
+}
@@ -0,0 +1,625 @@
+//! Safe(r) wrappers around the raw bindings of the parent module.
+//!
+//! Each wrapper turns the usual Win32 error signalling (null handles, zero return values,
+//! `GetLastError`) into a `Result` so callers can use `?` instead of checking by hand.
+//!
+//! Window and device context handles are opaque values that only Windows dereferences, hence
+//! taking them doesn't make a wrapper `unsafe`.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::*;
+use core::ptr::null_mut;
+use std::os::raw::c_int;
+
+#[derive(Debug)] // trait used for when you want to display info to a programmer
+#[repr(transparent)]
+pub struct Win32Error(pub DWORD);
+impl core::fmt::Display for Win32Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // If the 29th bit is set, it's an error.
+        if self.0 & (1 << 29) > 0 {
+            return write!(f, "Win32ApplicationError({})", self.0);
+        }
+
+        let dwFlags = FORMAT_MESSAGE_ALLOCATE_BUFFER
+            | FORMAT_MESSAGE_FROM_SYSTEM
+            | FORMAT_MESSAGE_IGNORE_INSERTS;
+        let lpSource = null_mut();
+        let dwMessageId = self.0; // NOTE is this equivalent to *this* ?
+        let dwLanguageId = 0;
+
+        // The buffer that is going to be alocated by FormatMessageW.
+        let mut buffer: *mut u16 = null_mut();
+        // Address where the pointer is located as LPTSTR (Long Pointer TCHAR string, in our case TCHAR is WCHAR, *mut u16)
+        // In C : (LPTSTR)&buffer
+        let lpBuffer = &mut buffer as *mut LPWSTR as *mut u16;
+        let nSize = 0; // Minumum size of the buffer allocated.
+        let Arguments = null_mut();
+        let tchar_count_excluding_null = unsafe {
+            FormatMessageW(
+                dwFlags,
+                lpSource,
+                dwMessageId,
+                dwLanguageId,
+                lpBuffer,
+                nSize,
+                Arguments,
+            )
+        };
+
+        if tchar_count_excluding_null == 0 || buffer.is_null() {
+            Err(core::fmt::Error)
+        } else {
+            // Wrap the buffer in the OnDropLocalFree struct so that when it goes
+            // out of scope it gets dropped via a LocalFree call.
+            let _on_drop = OnDropLocalFree(buffer as HLOCAL);
+            // Binding it to a _ variable (ignored result) would drop it immediately.
+            // There's been no error, let's access the buffer.
+            let buffer_slice: &[u16] = unsafe {
+                core::slice::from_raw_parts_mut(buffer, tchar_count_excluding_null as usize)
+            };
+
+            for decode_result in core::char::decode_utf16(buffer_slice.iter().copied()) {
+                match decode_result {
+                    Ok('\r') | Ok('\n') => write!(f, " ")?,
+                    Ok(ch) => write!(f, "{}", ch)?,
+                    Err(_) => write!(f, "�")?,
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
+impl std::error::Error for Win32Error {}
+
+struct OnDropLocalFree(HLOCAL);
+impl Drop for OnDropLocalFree {
+    fn drop(&mut self) {
+        unsafe { LocalFree(self.0) };
+    }
+}
+
+/// Gets the thread-local last-error code value.
+///
+/// See [`GetLastError`](https://docs.microsoft.com/en-us/windows/win32/api/errhandlingapi/nf-errhandlingapi-getlasterror)
+pub fn get_last_error() -> Win32Error {
+    Win32Error(unsafe { GetLastError() })
+}
+
+/// Sets the thread-local last-error code value.
+///
+/// See [`SetLastError`](https://docs.microsoft.com/en-us/windows/win32/api/errhandlingapi/nf-errhandlingapi-setlasterror)
+pub fn set_last_error(e: Win32Error) {
+    unsafe { SetLastError(e.0) }
+}
+
+/// Returns a handle to the file (executable file) used to create the calling process.
+///
+/// See : [`GetModuleHandleW`](https://docs.microsoft.com/en-us/windows/win32/api/libloaderapi/nf-libloaderapi-getmodulehandlew)
+pub fn get_process_handle() -> HMODULE {
+    // Safety : Null provides the executable handle that created the calling process.
+    // See [MSDN - `GetModuleHandleW` Parameters}(https://docs.microsoft.com/en-us/windows/win32/api/libloaderapi/nf-libloaderapi-getmodulehandlew#parameters)
+    unsafe { GetModuleHandleW(core::ptr::null()) }
+}
+
+/// Predefined cursor styles.
+pub enum EIDCursor {
+    /// Standard arrow and small hourglass
+    AppStarting = 32650,
+    /// Standard arrow
+    Arrow = 32512,
+    /// Crosshair
+    Cross = 32515,
+    /// Hand
+    Hand = 32649,
+    /// Arrow and question mark
+    Help = 32651,
+    /// I-beam
+    IBeam = 32513,
+    /// Slashed circle
+    No = 32648,
+    /// Four-pointed arrow pointing north, south, east, and west
+    SizeAll = 32646,
+    /// Double-pointed arrow pointing northeast and southwest
+    SizeNeSw = 32643,
+    /// Double-pointed arrow pointing north and south
+    SizeNS = 32645,
+    /// Double-pointed arrow pointing northwest and southeast
+    SizeNwSe = 32642,
+    /// Double-pointed arrow pointing west and east
+    SizeWE = 32644,
+    /// Vertical arrow
+    UpArrow = 32516,
+    /// Hourglass
+    Wait = 32514,
+}
+
+/// Loads the specified predefined cursors.
+///
+/// See : [`LoadCursorW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-loadcursorw)
+pub fn load_predefined_cursor(cursor: EIDCursor) -> Result<HCURSOR, Win32Error> {
+    let resource = MAKEINTRESOURCE(cursor as WORD);
+    // Safety : The enum only allows values from the the approved cursors list.
+    let hcursor = unsafe { LoadCursorW(null_mut(), resource) };
+    if hcursor.is_null() {
+        Err(get_last_error())
+    } else {
+        Ok(hcursor)
+    }
+}
+
+/// Predefined icons.
+pub enum EIDIcon {
+    /// Default application icon
+    Application = 32512,
+    /// Stop sign
+    Error = 32513,
+    /// Question mark
+    Question = 32514,
+    /// Exclamation point
+    Warning = 32515,
+    /// Lowercase i in a circle
+    Information = 32516,
+    /// Security shield
+    Shield = 32518,
+}
+
+/// Loads the specified predefined icon.
+///
+/// See : [`LoadIconW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-loadiconw)
+pub fn load_predefined_icon(icon: EIDIcon) -> Result<HICON, Win32Error> {
+    let resource = MAKEINTRESOURCE(icon as WORD);
+    // Safety : The enum only allows values from the the approved icons list.
+    let hicon = unsafe { LoadIconW(null_mut(), resource) };
+    if hicon.is_null() {
+        Err(get_last_error())
+    } else {
+        Ok(hicon)
+    }
+}
+
+/// Registers a window class struct.
+///
+/// # ! Partially wrapped !
+/// ## Safety
+///
+/// All pointer fields of the struct must be valid.
+///
+/// See [`RegisterClassW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerclassw)
+pub unsafe fn register_class(window_class: &WNDCLASSW) -> Result<ATOM, Win32Error> {
+    let atom = RegisterClassW(window_class);
+    if atom == 0 {
+        Err(get_last_error())
+    } else {
+        Ok(atom)
+    }
+}
+
+/// Registers an extended window class struct, see [`WindowClass`](super::class::WindowClass)
+/// for classes that get unregistered.
+///
+/// ## Safety
+///
+/// All pointer fields of the struct must be valid, and `cbSize` set.
+///
+/// See [`RegisterClassExW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerclassexw)
+pub unsafe fn register_class_ex(window_class: &WNDCLASSEXW) -> Result<ATOM, Win32Error> {
+    let atom = RegisterClassExW(window_class);
+    if atom == 0 {
+        Err(get_last_error())
+    } else {
+        Ok(atom)
+    }
+}
+
+/// Creates a `WS_OVERLAPPEDWINDOW` window, see [`create_window`].
+///
+/// * The window is not initially shown, you must call [`ShowWindow`] yourself.
+/// * `size` is the size of the client area, or `CW_USEDEFAULT` to let the system pick.
+///
+/// ## Safety
+///
+/// `param` is handed to the window procedure as-is, through `CREATESTRUCTW::lpCreateParams`.
+pub unsafe fn create_app_window(
+    class_name: &str,
+    window_name: &str,
+    coordinates: Option<[i32; 2]>,
+    size: [i32; 2],
+    param: LPVOID,
+) -> Result<HWND, Win32Error> {
+    create_window(
+        class_name,
+        window_name,
+        WS_OVERLAPPEDWINDOW,
+        0,
+        coordinates,
+        size,
+        null_mut(),
+        param,
+    )
+}
+
+/// Creates a window with the styles `style` and `ex_style`.
+///
+/// * `size` is the size of the client area, or `CW_USEDEFAULT` to let the system pick. Only
+///   overlapped windows get a default size, others are then created empty.
+/// * `coordinates` are relative to the client area of the parent for a `WS_CHILD` window, and
+///   `None` puts it at the top left corner.
+/// * `parent` is the parent of a `WS_CHILD` window, or the owner of any other window: owned
+///   windows stay above their owner and are destroyed along with it. It may be null, except for
+///   `WS_CHILD` windows.
+///
+/// ## Safety
+///
+/// `param` is handed to the window procedure as-is, through `CREATESTRUCTW::lpCreateParams`.
+///
+/// See [`CreateWindowExW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-createwindowexw)
+#[allow(clippy::too_many_arguments)]
+pub unsafe fn create_window(
+    class_name: &str,
+    window_name: &str,
+    style: DWORD,
+    ex_style: DWORD,
+    coordinates: Option<[i32; 2]>,
+    [width, height]: [i32; 2],
+    parent: HWND,
+    param: LPVOID,
+) -> Result<HWND, Win32Error> {
+    // Keep the wide strings alive until CreateWindowExW returns.
+    let class_name_wn = wide_null(class_name);
+    let window_name_wn = wide_null(window_name);
+    let position = match coordinates {
+        Some([x, y]) => (x, y),
+        None if style & WS_CHILD != 0 => (0, 0),
+        None => (CW_USEDEFAULT, CW_USEDEFAULT),
+    };
+    // CreateWindowExW wants the size of the whole window, frame included.
+    let (width, height) = if width == CW_USEDEFAULT || height == CW_USEDEFAULT {
+        (width, height)
+    } else {
+        let mut rect = RECT {
+            left: 0,
+            top: 0,
+            right: width,
+            bottom: height,
+        };
+        if 0 == AdjustWindowRectEx(&mut rect, style, 0, ex_style) {
+            return Err(get_last_error());
+        }
+        (rect.right - rect.left, rect.bottom - rect.top)
+    };
+
+    let handle: HWND = CreateWindowExW(
+        ex_style,
+        class_name_wn.as_ptr(),
+        window_name_wn.as_ptr(),
+        style,
+        position.0,
+        position.1,
+        width,
+        height,
+        parent,
+        null_mut(),
+        get_process_handle(),
+        param,
+    );
+    if handle.is_null() {
+        Err(get_last_error())
+    } else {
+        Ok(handle)
+    }
+}
+
+/// Sets the "userdata" pointer of the window (`GWLP_USERDATA`).
+///
+/// **Returns:** The previous userdata pointer.
+///
+/// [`SetWindowLongPtrW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setwindowlongptrw)
+pub fn set_window_userdata<T>(hwnd: HWND, ptr: *mut T) -> Result<*mut T, Win32Error> {
+    set_last_error(Win32Error(0));
+    let out = unsafe { SetWindowLongPtrW(hwnd, GWLP_USERDATA, ptr as LONG_PTR) };
+    if out == 0 {
+        // Check the last error, if it's also 0 then this is not a "real" error.
+        let last_error = get_last_error();
+        if last_error.0 == 0 {
+            Ok(out as *mut T)
+        } else {
+            Err(last_error)
+        }
+    } else {
+        Ok(out as *mut T)
+    }
+}
+
+/// Gets the "userdata" pointer of the window (`GWLP_USERDATA`).
+///
+/// **Returns:** The userdata pointer.
+///
+/// [`GetWindowLongPtrW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getwindowlongptrw)
+pub fn get_window_userdata<T>(hwnd: HWND) -> Result<*mut T, Win32Error> {
+    set_last_error(Win32Error(0));
+    let out = unsafe { GetWindowLongPtrW(hwnd, GWLP_USERDATA) };
+    if out == 0 {
+        // if output is 0, it's only a "real" error if the last_error is non-zero
+        let last_error = get_last_error();
+        if last_error.0 != 0 {
+            Err(last_error)
+        } else {
+            Ok(out as *mut T)
+        }
+    } else {
+        Ok(out as *mut T)
+    }
+}
+
+/// Gets a message from the thread's message queue.
+///
+/// The message can be for any window in this thread,
+/// or it can be a non-window message as well.
+///
+/// See [`GetMessageW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getmessagew)
+#[inline(always)]
+pub fn get_any_message() -> Result<MSG, Win32Error> {
+    let mut msg = MSG::default();
+    let output = unsafe { GetMessageW(&mut msg, null_mut(), 0, 0) };
+    if output == -1 {
+        // We got an error.
+        Err(get_last_error())
+    } else {
+        Ok(msg)
+    }
+}
+
+/// Gets a message from the thread's message queue, without waiting for one.
+///
+/// **Returns:** `None` when the queue is empty.
+///
+/// See [`PeekMessageW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-peekmessagew)
+#[inline(always)]
+pub fn peek_any_message() -> Option<MSG> {
+    let mut msg = MSG::default();
+    let output = unsafe { PeekMessageW(&mut msg, null_mut(), 0, 0, PM_REMOVE) };
+    if output == 0 {
+        None
+    } else {
+        Some(msg)
+    }
+}
+
+/// Translates and dispatches every message waiting in the thread's queue, then returns.
+///
+/// This is the non-blocking counterpart of a [`get_any_message`] loop, meant to be called
+/// once per frame by a real-time loop that keeps rendering between input bursts.
+///
+/// **Returns:** The exit code of the `WM_QUIT` message, if one was received. The messages behind
+/// it are left in the queue.
+pub fn poll_messages() -> Option<c_int> {
+    while let Some(msg) = peek_any_message() {
+        if msg.message == WM_QUIT {
+            return Some(msg.wParam as c_int);
+        }
+        translate_message(&msg);
+        dispatch_message(&msg);
+    }
+    None
+}
+
+/// Translates virtual-key messages into character messages.
+///
+/// The character messages are posted to the calling thread's message queue, to be read
+/// the next time the thread calls the [`GetMessageW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getmessagew)
+/// or [PeekMessageW](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-peekmessagew) function.
+///
+/// See [`TranslateMessage`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-translatemessage)
+pub fn translate_message(msg: &MSG) -> bool {
+    0 != unsafe { TranslateMessage(msg) }
+}
+
+/// Dispatches a message to the procedure of the window it's for.
+///
+/// **Returns:** What the window procedure returned.
+///
+/// See [`DispatchMessageW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-dispatchmessagew)
+pub fn dispatch_message(msg: &MSG) -> LRESULT {
+    unsafe { DispatchMessageW(msg) }
+}
+
+/// Indicates to the system that a thread has made a request to terminate (quit).
+/// It is typically used in response to a [WM_DESTROY](https://docs.microsoft.com/en-us/windows/win32/winmsg/wm-destroy)
+/// message.
+///
+/// The PostQuitMessage function posts a [WM_QUIT](https://docs.microsoft.com/en-us/windows/win32/winmsg/wm-quit) message
+/// to the thread's message queue and returns immediately; the function simply indicates to the system that the thread
+/// is requesting to quit at some time in the future.
+///
+/// When the thread retrieves the [WM_QUIT](https://docs.microsoft.com/en-us/windows/win32/winmsg/wm-quit) message from
+/// its message queue, it should exit its message loop and return control to the system.
+/// The exit value returned to the system **must** be the wParam parameter of the [WM_QUIT](https://docs.microsoft.com/en-us/windows/win32/winmsg/wm-quit)
+/// message.
+///
+/// See [`PostQuitMessage`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-postquitmessage)
+pub fn post_quit_message(exit_code: c_int) {
+    unsafe { PostQuitMessage(exit_code) }
+}
+
+/// See [`BeginPaint`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-beginpaint)
+pub fn begin_paint(hwnd: HWND) -> Result<(HDC, PAINTSTRUCT), Win32Error> {
+    let mut ps: PAINTSTRUCT = PAINTSTRUCT::default();
+    let hdc: HDC = unsafe { BeginPaint(hwnd, &mut ps) };
+    if hdc.is_null() {
+        Err(get_last_error())
+    } else {
+        Ok((hdc, ps))
+    }
+}
+
+/// See [`EndPaint`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-endpaint)
+pub fn end_paint(hwnd: HWND, ps: &PAINTSTRUCT) {
+    // We do not care about the return value of EndPaint as it's always non-zero.
+    unsafe { EndPaint(hwnd, ps) };
+}
+
+/// Runs `f` between a [`begin_paint`] and [`end_paint`] pair.
+///
+/// The closure receives the device context, whether the background must be erased,
+/// and the rectangle that needs painting.
+///
+/// ## Safety
+///
+/// Must only be called while handling `WM_PAINT` for `hwnd`.
+pub unsafe fn do_some_painting<F>(hwnd: HWND, f: F) -> Result<(), Win32Error>
+where
+    F: FnOnce(HDC, bool, RECT) -> Result<(), Win32Error>,
+{
+    let (hdc, ps) = begin_paint(hwnd)?;
+    let output = f(hdc, ps.fErase != 0, ps.rcPaint);
+    end_paint(hwnd, &ps);
+    output
+}
+
+/// Fills a rectangle area with a specific color.
+///
+/// ## Safety
+///
+/// `hdc` must be a valid device context.
+#[allow(clippy::result_unit_err)]
+pub unsafe fn fill_rect_with_system_color(
+    hdc: HDC,
+    rect: &RECT,
+    color: SysColor,
+) -> Result<(), ()> {
+    let _success = FillRect(hdc, rect, (color as u32 + 1) as HBRUSH);
+    if _success != 0 {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+/// Gets the client area of a window, its top-left corner is always `(0, 0)`.
+///
+/// See [`GetClientRect`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getclientrect)
+pub fn get_client_rect(hwnd: HWND) -> Result<RECT, Win32Error> {
+    let mut rect = RECT::default();
+    if 0 != unsafe { GetClientRect(hwnd, &mut rect) } {
+        Ok(rect)
+    } else {
+        Err(get_last_error())
+    }
+}
+
+/// Shows a modal `OK`/`Cancel` message box owned by `hwnd`.
+///
+/// **Returns:** The button the user picked (`IDOK`, ...).
+///
+/// See [`MessageBoxW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-messageboxw)
+pub fn show_message_box(hwnd: HWND, caption: &str, text: &str) -> Result<i32, Win32Error> {
+    let message_box_text = wide_null(text);
+    let message_box_caption = wide_null(caption);
+    let message_result = unsafe {
+        MessageBoxW(
+            hwnd,
+            message_box_text.as_ptr(),
+            message_box_caption.as_ptr(),
+            MB_OKCANCEL,
+        )
+    };
+    if message_result == 0 {
+        Err(get_last_error())
+    } else {
+        Ok(message_result)
+    }
+}
+
+/// Sends all the mouse input to `hwnd`, even once the cursor leaves it, until
+/// [`release_capture`] is called or another window takes the capture.
+///
+/// **Returns:** The window that had the capture before, or null.
+///
+/// See [`SetCapture`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setcapture)
+pub fn set_capture(hwnd: HWND) -> HWND {
+    unsafe { SetCapture(hwnd) }
+}
+
+/// Releases the mouse capture of the current thread, see [`set_capture`].
+///
+/// See [`ReleaseCapture`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-releasecapture)
+pub fn release_capture() -> Result<(), Win32Error> {
+    if 0 != unsafe { ReleaseCapture() } {
+        Ok(())
+    } else {
+        Err(get_last_error())
+    }
+}
+
+/// Asks for a `WM_MOUSELEAVE` message once the cursor leaves the client area of `hwnd`.
+///
+/// The request only lasts until that message is posted, it has to be made again afterwards.
+///
+/// See [`TrackMouseEvent`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-trackmouseevent)
+pub fn track_mouse_leave(hwnd: HWND) -> Result<(), Win32Error> {
+    let mut track = TRACKMOUSEEVENT {
+        cbSize: core::mem::size_of::<TRACKMOUSEEVENT>() as DWORD,
+        dwFlags: TME_LEAVE,
+        hwndTrack: hwnd,
+        dwHoverTime: HOVER_DEFAULT,
+    };
+    if 0 != unsafe { TrackMouseEvent(&mut track) } {
+        Ok(())
+    } else {
+        Err(get_last_error())
+    }
+}
+
+/// Asks for the raw input of `devices`, delivered through `WM_INPUT` messages.
+///
+/// See [`RegisterRawInputDevices`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerrawinputdevices)
+pub fn register_raw_input_devices(devices: &[RAWINPUTDEVICE]) -> Result<(), Win32Error> {
+    let registered = unsafe {
+        RegisterRawInputDevices(
+            devices.as_ptr(),
+            devices.len() as UINT,
+            core::mem::size_of::<RAWINPUTDEVICE>() as UINT,
+        )
+    };
+    if registered != 0 {
+        Ok(())
+    } else {
+        Err(get_last_error())
+    }
+}
+
+/// Gets the raw input of a `WM_INPUT` message, from its `lParam`.
+///
+/// Only mouse and keyboard input fit in a [`RAWINPUT`], HID input fails with
+/// `ERROR_INSUFFICIENT_BUFFER`.
+///
+/// See [`GetRawInputData`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getrawinputdata)
+pub fn get_raw_input_data(h_raw_input: HRAWINPUT) -> Result<RAWINPUT, Win32Error> {
+    let header_size = core::mem::size_of::<RAWINPUTHEADER>() as UINT;
+    let mut size: UINT = 0;
+    let failed =
+        unsafe { GetRawInputData(h_raw_input, RID_INPUT, null_mut(), &mut size, header_size) };
+    if failed != 0 {
+        return Err(get_last_error());
+    }
+    if size as usize > core::mem::size_of::<RAWINPUT>() {
+        return Err(Win32Error(ERROR_INSUFFICIENT_BUFFER));
+    }
+    let mut raw = RAWINPUT::default();
+    let copied = unsafe {
+        GetRawInputData(
+            h_raw_input,
+            RID_INPUT,
+            (&mut raw as *mut RAWINPUT).cast(),
+            &mut size,
+            header_size,
+        )
+    };
+    if copied == UINT::MAX {
+        Err(get_last_error())
+    } else {
+        Ok(raw)
+    }
+}
@@ -0,0 +1,231 @@
+//! OpenGL contexts for windows, through WGL.
+//!
+//! See the tutorial's [OpenGL with Win32](https://rust-tutorials.github.io/triangle-from-scratch/opening_a_window/win32.html)
+//! chapter, and [Creating an OpenGL Context (WGL)](https://www.khronos.org/opengl/wiki/Creating_an_OpenGL_Context_(WGL)).
+
+use super::{safe::*, window::Window, *};
+use crate::gl::Gl;
+use core::ffi::c_void;
+use core::ptr::null_mut;
+use std::os::raw::c_int;
+
+/// What kind of context [`GlContext::new`] should create.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlConfig {
+    /// The OpenGL version, as `[major, minor]`.
+    pub version: [c_int; 2],
+    /// Core profile if `true`, compatibility profile otherwise.
+    pub core_profile: bool,
+    /// Asks for a debug context.
+    pub debug: bool,
+}
+
+impl Default for GlConfig {
+    fn default() -> Self {
+        Self {
+            version: [3, 3],
+            core_profile: true,
+            debug: cfg!(debug_assertions),
+        }
+    }
+}
+
+/// An OpenGL rendering context, drawing to a window.
+///
+/// * The window must come from a class with `CS_OWNDC`, like the [`EventLoop`](super::event_loop::EventLoop)
+///   one: the context keeps using the device context of the window for its whole life.
+/// * A window's pixel format can only be set once, so there can only be one context per window.
+pub struct GlContext {
+    hwnd: HWND,
+    hdc: HDC,
+    hglrc: HGLRC,
+    /// `opengl32.dll`, where the GL 1.1 functions have to be looked up.
+    opengl32: HMODULE,
+}
+
+impl GlContext {
+    /// Creates a context drawing to `window`, and makes it current.
+    ///
+    /// The window gets a double buffered 32 bits RGBA pixel format, with a 24 bits depth buffer and
+    /// an 8 bits stencil buffer. The context is created through `WGL_ARB_create_context`, which
+    /// needs a legacy context to be current to be loaded in the first place.
+    pub fn new<S: 'static>(window: &Window<S>, config: GlConfig) -> Result<Self, Win32Error> {
+        let hwnd = window.hwnd();
+        let hdc = unsafe { GetDC(hwnd) };
+        if hdc.is_null() {
+            return Err(get_last_error());
+        }
+        set_pixel_format(hdc)?;
+
+        let legacy = unsafe { wglCreateContext(hdc) };
+        if legacy.is_null() {
+            return Err(get_last_error());
+        }
+        let context = unsafe { create_context_with_attributes(hdc, legacy, config) };
+        unsafe {
+            wglMakeCurrent(null_mut(), null_mut());
+            wglDeleteContext(legacy);
+        }
+        let hglrc = context?;
+
+        let opengl32_wn = wide_null("opengl32.dll");
+        let opengl32 = unsafe { LoadLibraryW(opengl32_wn.as_ptr()) };
+        let gl_context = Self {
+            hwnd,
+            hdc,
+            hglrc,
+            opengl32,
+        };
+        if opengl32.is_null() {
+            return Err(get_last_error());
+        }
+        gl_context.make_current()?;
+        Ok(gl_context)
+    }
+
+    /// Makes this context the current one of the calling thread.
+    ///
+    /// See [`wglMakeCurrent`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-wglmakecurrent)
+    pub fn make_current(&self) -> Result<(), Win32Error> {
+        if 0 != unsafe { wglMakeCurrent(self.hdc, self.hglrc) } {
+            Ok(())
+        } else {
+            Err(get_last_error())
+        }
+    }
+
+    /// Whether this context is the current one of the calling thread.
+    pub fn is_current(&self) -> bool {
+        unsafe { wglGetCurrentContext() == self.hglrc }
+    }
+
+    /// Shows what was drawn since the last swap.
+    ///
+    /// See [`SwapBuffers`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-swapbuffers)
+    pub fn swap_buffers(&self) -> Result<(), Win32Error> {
+        if 0 != unsafe { SwapBuffers(self.hdc) } {
+            Ok(())
+        } else {
+            Err(get_last_error())
+        }
+    }
+
+    /// The address of the GL function `name`, null if it's not available.
+    ///
+    /// The context must be current.
+    pub fn get_proc_address(&self, name: &str) -> *const c_void {
+        let name_cstr: Vec<u8> = name.bytes().chain(Some(0)).collect();
+        let name_ptr = name_cstr.as_ptr().cast();
+        let address = unsafe { wglGetProcAddress(name_ptr) };
+        // Some drivers return small values instead of null on failure, and GL 1.1 functions are
+        // only ever found in `opengl32.dll` itself.
+        match address as isize {
+            -1..=3 => unsafe { GetProcAddress(self.opengl32, name_ptr) },
+            _ => address,
+        }
+    }
+
+    /// Loads the [`Gl`] function table for this context.
+    ///
+    /// The context must be current.
+    pub fn load_gl(&self) -> Gl {
+        // Safety: the addresses come from WGL, for the very names we ask for.
+        unsafe { Gl::load(|name| self.get_proc_address(name)) }
+    }
+
+    /// The window this context draws to.
+    pub fn hwnd(&self) -> HWND {
+        self.hwnd
+    }
+}
+
+impl Drop for GlContext {
+    fn drop(&mut self) {
+        unsafe {
+            if self.is_current() {
+                wglMakeCurrent(null_mut(), null_mut());
+            }
+            wglDeleteContext(self.hglrc);
+            if !self.opengl32.is_null() {
+                FreeLibrary(self.opengl32);
+            }
+        }
+    }
+}
+
+/// Picks and sets the pixel format of the device context of a window.
+///
+/// See [`ChoosePixelFormat`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-choosepixelformat)
+fn set_pixel_format(hdc: HDC) -> Result<(), Win32Error> {
+    let pfd = PIXELFORMATDESCRIPTOR {
+        nSize: core::mem::size_of::<PIXELFORMATDESCRIPTOR>() as WORD,
+        nVersion: 1,
+        dwFlags: PFD_DRAW_TO_WINDOW | PFD_SUPPORT_OPENGL | PFD_DOUBLEBUFFER,
+        iPixelType: PFD_TYPE_RGBA,
+        cColorBits: 32,
+        cDepthBits: 24,
+        cStencilBits: 8,
+        iLayerType: PFD_MAIN_PLANE,
+        ..PIXELFORMATDESCRIPTOR::default()
+    };
+    let format = unsafe { ChoosePixelFormat(hdc, &pfd) };
+    if format == 0 {
+        return Err(get_last_error());
+    }
+    if 0 == unsafe { SetPixelFormat(hdc, format, &pfd) } {
+        return Err(get_last_error());
+    }
+    Ok(())
+}
+
+/// Creates a context through `wglCreateContextAttribsARB`.
+///
+/// ## Safety
+///
+/// `legacy` must be a valid context for `hdc`, it's made current.
+unsafe fn create_context_with_attributes(
+    hdc: HDC,
+    legacy: HGLRC,
+    config: GlConfig,
+) -> Result<HGLRC, Win32Error> {
+    if 0 == wglMakeCurrent(hdc, legacy) {
+        return Err(get_last_error());
+    }
+    let name = b"wglCreateContextAttribsARB\0";
+    let address = wglGetProcAddress(name.as_ptr().cast());
+    if address.is_null() {
+        // The driver doesn't know WGL_ARB_create_context.
+        return Err(get_last_error());
+    }
+    let wglCreateContextAttribsARB: wglCreateContextAttribsARB_t =
+        core::mem::transmute::<PROC, wglCreateContextAttribsARB_t>(address);
+    let wglCreateContextAttribsARB = wglCreateContextAttribsARB.unwrap();
+
+    let profile = if config.core_profile {
+        WGL_CONTEXT_CORE_PROFILE_BIT_ARB
+    } else {
+        WGL_CONTEXT_COMPATIBILITY_PROFILE_BIT_ARB
+    };
+    let flags = if config.debug {
+        WGL_CONTEXT_DEBUG_BIT_ARB
+    } else {
+        0
+    };
+    let attributes = [
+        WGL_CONTEXT_MAJOR_VERSION_ARB,
+        config.version[0],
+        WGL_CONTEXT_MINOR_VERSION_ARB,
+        config.version[1],
+        WGL_CONTEXT_PROFILE_MASK_ARB,
+        profile,
+        WGL_CONTEXT_FLAGS_ARB,
+        flags,
+        0,
+    ];
+    let hglrc = wglCreateContextAttribsARB(hdc, null_mut(), attributes.as_ptr());
+    if hglrc.is_null() {
+        Err(get_last_error())
+    } else {
+        Ok(hglrc)
+    }
+}
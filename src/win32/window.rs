@@ -0,0 +1,605 @@
+//! An owned window with typed per-window state.
+//!
+//! The state is boxed and stored behind `GWLP_USERDATA` by [`window_procedure`] while the
+//! window is being created, and freed again when the window receives `WM_NCDESTROY`.
+
+use super::{
+    builder::WindowBuilder,
+    class::RegisteredClass,
+    dpi,
+    event_loop::EventLoop,
+    monitor::{DisplayModeChange, Monitor},
+    safe::*,
+    *,
+};
+use crate::{
+    dpi::{Dpi, LogicalSize, PhysicalSize, SizeConstraints},
+    event::WindowId,
+    monitor::VideoMode,
+    raster::Framebuffer,
+};
+use core::any::Any;
+use core::marker::PhantomData;
+use core::ptr::null_mut;
+use std::os::raw::c_int;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+/// What lives behind `GWLP_USERDATA` for windows created through [`Window`].
+struct WindowData {
+    state: Box<dyn Any>,
+    /// Shared with the owning [`Window`], nulled once the window is destroyed.
+    hwnd: Rc<Cell<HWND>>,
+    /// Enforced on `WM_GETMINMAXINFO`, unless fullscreen.
+    size_constraints: Cell<SizeConstraints>,
+    fullscreen: Cell<Fullscreen>,
+    /// What to go back to when leaving fullscreen, while fullscreen.
+    windowed: RefCell<Option<WindowedState>>,
+}
+
+/// How a window covers its monitor, see [`Window::set_fullscreen`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fullscreen {
+    /// A regular window, with a frame.
+    Windowed,
+    /// A window without a frame, covering the monitor.
+    Borderless(Monitor),
+    /// A window without a frame, covering the monitor it is on, after switching that monitor to
+    /// another display mode.
+    Exclusive(VideoMode),
+}
+
+/// The window as it was before going fullscreen.
+struct WindowedState {
+    placement: WINDOWPLACEMENT,
+    style: DWORD,
+    /// Puts the display mode back when dropped, set for [`Fullscreen::Exclusive`].
+    display_mode: Option<DisplayModeChange>,
+}
+
+impl WindowedState {
+    /// Saves where the window is, and its frame.
+    ///
+    /// See [`GetWindowPlacement`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getwindowplacement)
+    fn save(hwnd: HWND) -> Result<Self, Win32Error> {
+        let mut placement = WINDOWPLACEMENT {
+            length: core::mem::size_of::<WINDOWPLACEMENT>() as UINT,
+            ..WINDOWPLACEMENT::default()
+        };
+        if 0 == unsafe { GetWindowPlacement(hwnd, &mut placement) } {
+            return Err(get_last_error());
+        }
+        Ok(Self {
+            placement,
+            style: dpi::window_styles(hwnd).0,
+            display_mode: None,
+        })
+    }
+
+    /// Puts the window back where it was, with its frame.
+    ///
+    /// See [`SetWindowPlacement`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setwindowplacement)
+    fn restore(&self, hwnd: HWND) -> Result<(), Win32Error> {
+        unsafe { SetWindowLongPtrW(hwnd, GWL_STYLE, self.style as LONG_PTR) };
+        if 0 == unsafe { SetWindowPlacement(hwnd, &self.placement) } {
+            return Err(get_last_error());
+        }
+        // For the frame to be drawn again.
+        let flags = SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOOWNERZORDER | SWP_FRAMECHANGED;
+        if 0 == unsafe { SetWindowPos(hwnd, null_mut(), 0, 0, 0, 0, flags) } {
+            return Err(get_last_error());
+        }
+        Ok(())
+    }
+}
+
+/// A window that owns its `HWND` and a state of type `S`.
+///
+/// * The state lives as long as the window does, and is dropped exactly once when the window is
+///   destroyed, whoever destroys it.
+/// * Dropping the `Window` destroys the window if that didn't happen already.
+pub struct Window<S: 'static> {
+    id: WindowId,
+    hwnd: Rc<Cell<HWND>>,
+    /// The pixels handed over to GDI by [`Window::present`], kept around between frames.
+    backbuffer: RefCell<Vec<u8>>,
+    /// Dropped after the window is destroyed, the class can only be unregistered then.
+    _class: Option<RegisteredClass>,
+    _state: PhantomData<S>,
+}
+
+impl<S: 'static> Window<S> {
+    /// Creates a window handled by `event_loop`, moving `state` into it.
+    ///
+    /// * `size` is the size of the client area, in logical pixels.
+    /// * The window is not initially shown, see [`Window::show`].
+    pub fn new(
+        _event_loop: &EventLoop,
+        window_name: &str,
+        coordinates: Option<[i32; 2]>,
+        size: [i32; 2],
+        state: S,
+    ) -> Result<Self, Win32Error> {
+        Self::with_class(EventLoop::CLASS_NAME, window_name, coordinates, size, state)
+    }
+
+    /// Creates a window of the class `class_name`, moving `state` into it.
+    ///
+    /// The class must have been registered with [`window_procedure`], or with a procedure that
+    /// forwards the messages it doesn't handle to it. Otherwise the window is destroyed right
+    /// away, and this fails with `ERROR_INVALID_PARAMETER`.
+    ///
+    /// A class registered through [`WindowClass`](super::class::WindowClass) stays registered
+    /// for as long as the window is around.
+    ///
+    /// * `size` is the size of the client area, in logical pixels. The system picks one when
+    ///   either side isn't positive.
+    /// * The window is not initially shown, see [`Window::show`].
+    ///
+    /// See [`WindowBuilder`] for other kinds of windows.
+    pub fn with_class(
+        class_name: &str,
+        window_name: &str,
+        coordinates: Option<[i32; 2]>,
+        size: [i32; 2],
+        state: S,
+    ) -> Result<Self, Win32Error> {
+        let mut builder = WindowBuilder::new()
+            .with_class(class_name)
+            .with_title(window_name);
+        if let Some(coordinates) = coordinates {
+            builder = builder.with_position(coordinates);
+        }
+        if size[0] > 0 && size[1] > 0 {
+            builder = builder.with_size([size[0] as u32, size[1] as u32]);
+        }
+        Self::from_builder(&builder, state)
+    }
+
+    /// Creates a window as described by `builder`, moving `state` into it.
+    pub(crate) fn from_builder(builder: &WindowBuilder, state: S) -> Result<Self, Win32Error> {
+        let hwnd = Rc::new(Cell::new(null_mut()));
+        // Taken by the window procedure on WM_NCCREATE.
+        let mut pending = Some(WindowData {
+            state: Box::new(state),
+            hwnd: Rc::clone(&hwnd),
+            size_constraints: Cell::new(SizeConstraints::default()),
+            fullscreen: Cell::new(Fullscreen::Windowed),
+            windowed: RefCell::new(None),
+        });
+        let param: *mut Option<WindowData> = &mut pending;
+        let size = builder
+            .size()
+            .map_or([CW_USEDEFAULT; 2], |[width, height]| {
+                [width as c_int, height as c_int]
+            });
+        let class_name = builder.class_name().unwrap_or(EventLoop::CLASS_NAME);
+        // Taken first, the class can't go away while the window is being created.
+        let class = RegisteredClass::find(class_name);
+        let handle = unsafe {
+            create_window(
+                class_name,
+                builder.title(),
+                builder.style(),
+                builder.ex_style(),
+                builder.position(),
+                size,
+                builder.kind().parent(),
+                param.cast(),
+            )
+        }?;
+        // Still there if the window procedure never saw WM_NCCREATE, nothing would ever free the
+        // window nor tell when it is gone.
+        if pending.is_some() {
+            unsafe { DestroyWindow(handle) };
+            return Err(Win32Error(ERROR_INVALID_PARAMETER));
+        }
+        let window = Self {
+            id: WindowId(handle as usize),
+            hwnd,
+            backbuffer: RefCell::new(Vec::new()),
+            _class: class,
+            _state: PhantomData,
+        };
+        // Layered windows stay invisible until they are given an opacity.
+        if builder.ex_style() & WS_EX_LAYERED != 0
+            && 0 == unsafe { SetLayeredWindowAttributes(handle, 0, builder.opacity(), LWA_ALPHA) }
+        {
+            return Err(get_last_error());
+        }
+        // Created at the system DPI, the monitor the window ended up on may have another.
+        if let Some(size) = builder.size() {
+            let logical = LogicalSize::from(size);
+            window.set_client_size(logical.to_physical(window.dpi()))?;
+        }
+        Ok(window)
+    }
+
+    /// What lives behind `GWLP_USERDATA`, if the window still exists.
+    fn data(&self) -> Option<&WindowData> {
+        if !self.is_alive() {
+            return None;
+        }
+        match get_window_userdata::<WindowData>(self.hwnd()) {
+            // Safety: set on WM_NCCREATE, freed when the handle is nulled on WM_NCDESTROY.
+            Ok(data) => unsafe { data.as_ref() },
+            Err(_) => None,
+        }
+    }
+
+    /// The identifier used for this window in the events.
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
+    /// The handle of the window, null once the window has been destroyed.
+    pub fn hwnd(&self) -> HWND {
+        self.hwnd.get()
+    }
+
+    /// Whether the window still exists.
+    pub fn is_alive(&self) -> bool {
+        !self.hwnd.get().is_null()
+    }
+
+    /// Shows the window.
+    ///
+    /// **Returns:** Whether the window was previously visible.
+    ///
+    /// See [`ShowWindow`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-showwindow)
+    pub fn show(&self) -> bool {
+        self.is_alive() && 0 != unsafe { ShowWindow(self.hwnd(), SW_SHOW) }
+    }
+
+    /// The size of the client area of the window, in physical pixels.
+    pub fn client_size(&self) -> Result<[u32; 2], Win32Error> {
+        let rect = get_client_rect(self.hwnd())?;
+        Ok([
+            (rect.right - rect.left) as u32,
+            (rect.bottom - rect.top) as u32,
+        ])
+    }
+
+    /// Resizes the window for its client area to be `size`, within the size constraints.
+    ///
+    /// See [`SetWindowPos`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setwindowpos)
+    pub fn set_client_size(&self, size: PhysicalSize) -> Result<(), Win32Error> {
+        let hwnd = self.hwnd();
+        let dpi = self.dpi();
+        let size = self.size_constraints().clamp(size, dpi);
+        let (style, ex_style) = dpi::window_styles(hwnd);
+        let outer = dpi::window_size_for_client(size, style, ex_style, dpi)?;
+        let flags = SWP_NOMOVE | SWP_NOZORDER | SWP_NOACTIVATE;
+        let (width, height) = (outer.width as c_int, outer.height as c_int);
+        if 0 != unsafe { SetWindowPos(hwnd, null_mut(), 0, 0, width, height, flags) } {
+            Ok(())
+        } else {
+            Err(get_last_error())
+        }
+    }
+
+    /// The DPI of the monitor the window is on.
+    pub fn dpi(&self) -> Dpi {
+        if self.is_alive() {
+            dpi::window_dpi(self.hwnd())
+        } else {
+            Dpi::BASE
+        }
+    }
+
+    /// The monitor the window is on, or the nearest one.
+    pub fn current_monitor(&self) -> Option<Monitor> {
+        self.is_alive().then(|| Monitor::from_window(self.hwnd()))
+    }
+
+    /// How the window covers its monitor, [`Fullscreen::Windowed`] by default.
+    pub fn fullscreen(&self) -> Fullscreen {
+        self.data()
+            .map_or(Fullscreen::Windowed, |data| data.fullscreen.get())
+    }
+
+    /// Makes the window fullscreen, or windowed again.
+    ///
+    /// * Going fullscreen removes the frame and covers the whole monitor, switching its display
+    ///   mode first for [`Fullscreen::Exclusive`].
+    /// * Going back to [`Fullscreen::Windowed`] puts the display mode, the frame, the size and the
+    ///   position back as they were before the window went fullscreen.
+    pub fn set_fullscreen(&self, fullscreen: Fullscreen) -> Result<(), Win32Error> {
+        let data = match self.data() {
+            Some(data) => data,
+            None => return Ok(()),
+        };
+        let hwnd = self.hwnd();
+        let mut windowed = data.windowed.borrow_mut();
+        // Back to the usual display mode, whatever comes next.
+        if let Some(change) = windowed
+            .as_mut()
+            .and_then(|state| state.display_mode.take())
+        {
+            change.restore()?;
+        }
+        let monitor = match fullscreen {
+            Fullscreen::Windowed => {
+                let state = match windowed.take() {
+                    Some(state) => state,
+                    None => return Ok(()),
+                };
+                data.fullscreen.set(Fullscreen::Windowed);
+                return state.restore(hwnd);
+            }
+            Fullscreen::Borderless(monitor) => monitor,
+            Fullscreen::Exclusive(_) => Monitor::from_window(hwnd),
+        };
+        if windowed.is_none() {
+            *windowed = Some(WindowedState::save(hwnd)?);
+        }
+        let state = windowed.as_mut().unwrap();
+        if let Fullscreen::Exclusive(mode) = fullscreen {
+            state.display_mode = Some(monitor.set_video_mode(&mode)?);
+        }
+        // Set before resizing, for the window procedure to leave the size alone.
+        data.fullscreen.set(fullscreen);
+        // Queried after the display mode change, which resizes the monitor.
+        let rect = monitor.rect()?;
+        let style = (state.style & !WS_OVERLAPPEDWINDOW) | WS_POPUP;
+        unsafe { SetWindowLongPtrW(hwnd, GWL_STYLE, style as LONG_PTR) };
+        let success = unsafe {
+            SetWindowPos(
+                hwnd,
+                HWND_TOP,
+                rect.left,
+                rect.top,
+                rect.right - rect.left,
+                rect.bottom - rect.top,
+                SWP_NOOWNERZORDER | SWP_FRAMECHANGED,
+            )
+        };
+        if success == 0 {
+            return Err(get_last_error());
+        }
+        Ok(())
+    }
+
+    /// The bounds on the client area size, none by default.
+    pub fn size_constraints(&self) -> SizeConstraints {
+        self.data()
+            .map(|data| data.size_constraints.get())
+            .unwrap_or_default()
+    }
+
+    /// Sets bounds on the client area size, in logical pixels, resizing the window to fit them.
+    pub fn set_size_constraints(&self, constraints: SizeConstraints) -> Result<(), Win32Error> {
+        let data = match self.data() {
+            Some(data) => data,
+            None => return Ok(()),
+        };
+        data.size_constraints.set(constraints);
+        let [width, height] = self.client_size()?;
+        let size = PhysicalSize::new(width, height);
+        if constraints.clamp(size, self.dpi()) != size {
+            self.set_client_size(size)?;
+        }
+        Ok(())
+    }
+
+    /// Copies `framebuffer` to the client area of the window, stretching it to fill the area.
+    ///
+    /// Can be called at any time, not only while handling a paint event. The device context is
+    /// fetched and released on every call, so the class doesn't need `CS_OWNDC`.
+    ///
+    /// See [`StretchDIBits`](https://docs.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-stretchdibits)
+    pub fn present(&self, framebuffer: &Framebuffer) -> Result<(), Win32Error> {
+        let hwnd = self.hwnd();
+        let [client_width, client_height] = self.client_size()?;
+
+        // 32 bits per pixel DIBs are laid out as BGRX.
+        let mut backbuffer = self.backbuffer.borrow_mut();
+        backbuffer.clear();
+        backbuffer.extend(
+            framebuffer
+                .pixels()
+                .iter()
+                .flat_map(|pixel| [pixel.b, pixel.g, pixel.r, 0]),
+        );
+
+        let bitmap_info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: core::mem::size_of::<BITMAPINFOHEADER>() as DWORD,
+                biWidth: framebuffer.width() as LONG,
+                // Negative for a top-down bitmap, like the framebuffer.
+                biHeight: -(framebuffer.height() as LONG),
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB,
+                ..BITMAPINFOHEADER::default()
+            },
+            ..BITMAPINFO::default()
+        };
+
+        let hdc = unsafe { GetDC(hwnd) };
+        if hdc.is_null() {
+            return Err(get_last_error());
+        }
+        let scan_lines = unsafe {
+            StretchDIBits(
+                hdc,
+                0,
+                0,
+                client_width as c_int,
+                client_height as c_int,
+                0,
+                0,
+                framebuffer.width() as c_int,
+                framebuffer.height() as c_int,
+                backbuffer.as_ptr().cast(),
+                &bitmap_info,
+                DIB_RGB_COLORS,
+                SRCCOPY,
+            )
+        };
+        // Whatever the class styles: a common DC goes back to the cache, releasing a private
+        // (CS_OWNDC) or class (CS_CLASSDC) one does nothing.
+        unsafe { ReleaseDC(hwnd, hdc) };
+        if scan_lines == 0 && framebuffer.height() != 0 {
+            Err(get_last_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The state of the window, if it still exists.
+    pub fn state(&self) -> Option<&S> {
+        self.data()
+            .and_then(|data| (*data.state).downcast_ref::<S>())
+    }
+
+    /// The state of the window, if it still exists.
+    pub fn state_mut(&mut self) -> Option<&mut S> {
+        // Safety: the handle is only non-null while the window, and thus its state, is alive.
+        unsafe { window_state::<S>(self.hwnd()) }
+    }
+
+    /// Destroys the window, dropping its state.
+    ///
+    /// Does nothing if the window is already gone.
+    ///
+    /// See [`DestroyWindow`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-destroywindow)
+    pub fn destroy(&mut self) -> Result<(), Win32Error> {
+        if !self.is_alive() || 0 != unsafe { DestroyWindow(self.hwnd()) } {
+            Ok(())
+        } else {
+            Err(get_last_error())
+        }
+    }
+}
+
+impl<S: 'static> Drop for Window<S> {
+    fn drop(&mut self) {
+        // Call `destroy` first to find out whether it failed.
+        let _ = self.destroy();
+    }
+}
+
+/// Gets the state of a window created through [`Window::new`] or [`Window::with_class`].
+///
+/// **Returns:** `None` if the window has no state, or if its state isn't an `S`.
+///
+/// ## Safety
+///
+/// * `hwnd` must be a window created through [`Window`], or null.
+/// * The returned reference must not outlive the window, nor coexist with another reference to
+///   the same state.
+pub unsafe fn window_state<'a, S: 'static>(hwnd: HWND) -> Option<&'a mut S> {
+    if hwnd.is_null() {
+        return None;
+    }
+    match get_window_userdata::<WindowData>(hwnd) {
+        Ok(data) if !data.is_null() => (*data).state.downcast_mut::<S>(),
+        _ => None,
+    }
+}
+
+/// The window procedure taking care of the state of windows created through [`Window`].
+///
+/// It handles `WM_NCCREATE` and `WM_NCDESTROY`, and leaves everything to
+/// [`DefWindowProcW`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-defwindowprocw)
+/// otherwise. Custom window procedures should forward the messages they don't handle here.
+///
+/// ## Safety
+///
+/// Must only be used as (or called from) the window procedure of windows created through
+/// [`Window`].
+pub unsafe extern "system" fn window_procedure(
+    hwnd: HWND,
+    msg: UINT,
+    w_param: WPARAM,
+    l_param: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_NCCREATE => {
+            let create_struct = l_param as *const CREATESTRUCTW;
+            let pending = (*create_struct).lpCreateParams as *mut Option<WindowData>;
+            if let Some(data) = pending.as_mut().and_then(Option::take) {
+                data.hwnd.set(hwnd);
+                let ptr = Box::into_raw(Box::new(data));
+                if set_window_userdata(hwnd, ptr).is_err() {
+                    let data = Box::from_raw(ptr);
+                    data.hwnd.set(null_mut());
+                    // Returning 0 aborts the window creation.
+                    return 0;
+                }
+            }
+        }
+        // Sent once before WM_NCCREATE, when there are no constraints yet.
+        WM_GETMINMAXINFO => {
+            if let Ok(data) = get_window_userdata::<WindowData>(hwnd) {
+                if let Some(data) = data.as_ref() {
+                    if data.fullscreen.get() != Fullscreen::Windowed {
+                        return 0;
+                    }
+                    let info = &mut *(l_param as *mut MINMAXINFO);
+                    apply_size_constraints(hwnd, data.size_constraints.get(), info);
+                    return 0;
+                }
+            }
+        }
+        // Resize as suggested, for the window to keep its logical size on the new monitor.
+        WM_DPICHANGED => {
+            if let Ok(data) = get_window_userdata::<WindowData>(hwnd) {
+                // Fullscreen windows cover the monitor at any DPI.
+                if data
+                    .as_ref()
+                    .is_some_and(|data| data.fullscreen.get() != Fullscreen::Windowed)
+                {
+                    return 0;
+                }
+            }
+            let suggested = &*(l_param as *const RECT);
+            SetWindowPos(
+                hwnd,
+                null_mut(),
+                suggested.left,
+                suggested.top,
+                suggested.right - suggested.left,
+                suggested.bottom - suggested.top,
+                SWP_NOZORDER | SWP_NOACTIVATE,
+            );
+            return 0;
+        }
+        WM_NCDESTROY => match get_window_userdata::<WindowData>(hwnd) {
+            Ok(ptr) if !ptr.is_null() => {
+                // Clear the pointer first so nothing can observe the freed state.
+                let _ = set_window_userdata::<WindowData>(hwnd, null_mut());
+                // Dropping it also puts back the display mode of an exclusive fullscreen window.
+                let data = Box::from_raw(ptr);
+                data.hwnd.set(null_mut());
+            }
+            // Nothing to free.
+            _ => {}
+        },
+        _ => {}
+    }
+    DefWindowProcW(hwnd, msg, w_param, l_param)
+}
+
+/// Turns client area constraints into the window size limits of `WM_GETMINMAXINFO`.
+fn apply_size_constraints(hwnd: HWND, constraints: SizeConstraints, info: &mut MINMAXINFO) {
+    let dpi = dpi::window_dpi(hwnd);
+    let (style, ex_style) = dpi::window_styles(hwnd);
+    let to_point = |client_size| {
+        dpi::window_size_for_client(client_size, style, ex_style, dpi)
+            .ok()
+            .map(|outer| POINT {
+                x: outer.width as LONG,
+                y: outer.height as LONG,
+            })
+    };
+    if let Some(min) = constraints.min_physical(dpi).and_then(to_point) {
+        info.ptMinTrackSize = min;
+    }
+    if let Some(max) = constraints.max_physical(dpi).and_then(to_point) {
+        info.ptMaxTrackSize = max;
+    }
+}
@@ -0,0 +1,137 @@
+//! XInput controllers, with the DLL loaded at runtime.
+//!
+//! Which XInput DLL is available depends on the Windows version (and on the DirectX runtime being
+//! installed), so rather than linking to one the functions are looked up at runtime. When none can
+//! be found, stubs reporting every controller as disconnected are used instead.
+//!
+//! See [XInput](https://docs.microsoft.com/en-us/windows/win32/xinput/getting-started-with-xinput)
+
+use super::{safe::*, *};
+use crate::gamepad::{Deadzones, GamepadState};
+use core::ptr::null_mut;
+
+/// The DLLs to try, most recent first.
+const XINPUT_DLLS: [&str; 3] = ["xinput1_4.dll", "xinput9_1_0.dll", "xinput1_3.dll"];
+
+unsafe extern "system" fn xinput_get_state_stub(
+    _dwUserIndex: DWORD,
+    _pState: *mut XINPUT_STATE,
+) -> DWORD {
+    ERROR_DEVICE_NOT_CONNECTED
+}
+
+unsafe extern "system" fn xinput_set_state_stub(
+    _dwUserIndex: DWORD,
+    _pVibration: *mut XINPUT_VIBRATION,
+) -> DWORD {
+    ERROR_DEVICE_NOT_CONNECTED
+}
+
+/// The XInput functions, from whichever DLL could be loaded.
+pub struct XInput {
+    /// Null when the stubs are used.
+    module: HMODULE,
+    get_state: XInputGetState_t,
+    set_state: XInputSetState_t,
+    /// Applied by [`XInput::gamepad`].
+    pub deadzones: Deadzones,
+}
+
+impl XInput {
+    /// Loads the first XInput DLL available, or falls back to the stubs.
+    pub fn load() -> Self {
+        for dll in XINPUT_DLLS.iter() {
+            let dll_wn = wide_null(dll);
+            let module = unsafe { LoadLibraryW(dll_wn.as_ptr()) };
+            if module.is_null() {
+                continue;
+            }
+            let get_state = unsafe { GetProcAddress(module, b"XInputGetState\0".as_ptr().cast()) };
+            let set_state = unsafe { GetProcAddress(module, b"XInputSetState\0".as_ptr().cast()) };
+            if get_state.is_null() || set_state.is_null() {
+                unsafe { FreeLibrary(module) };
+                continue;
+            }
+            return Self {
+                module,
+                // Safety: the signatures are the documented ones.
+                get_state: unsafe { core::mem::transmute::<FARPROC, XInputGetState_t>(get_state) },
+                set_state: unsafe { core::mem::transmute::<FARPROC, XInputSetState_t>(set_state) },
+                deadzones: Deadzones::default(),
+            };
+        }
+        Self::stubs()
+    }
+
+    /// The stubs, which report every controller as disconnected.
+    pub fn stubs() -> Self {
+        Self {
+            module: null_mut(),
+            get_state: xinput_get_state_stub,
+            set_state: xinput_set_state_stub,
+            deadzones: Deadzones::default(),
+        }
+    }
+
+    /// Whether an XInput DLL was loaded, rather than the stubs.
+    pub fn is_loaded(&self) -> bool {
+        !self.module.is_null()
+    }
+
+    /// The raw state of the controller `user_index`, from 0 to `XUSER_MAX_COUNT - 1`.
+    ///
+    /// **Returns:** `None` if the controller is not connected.
+    ///
+    /// See [`XInputGetState`](https://docs.microsoft.com/en-us/windows/win32/api/xinput/nf-xinput-xinputgetstate)
+    pub fn state(&self, user_index: DWORD) -> Result<Option<XINPUT_STATE>, Win32Error> {
+        let mut state = XINPUT_STATE::default();
+        match unsafe { (self.get_state)(user_index, &mut state) } {
+            ERROR_SUCCESS => Ok(Some(state)),
+            ERROR_DEVICE_NOT_CONNECTED => Ok(None),
+            e => Err(Win32Error(e)),
+        }
+    }
+
+    /// The normalised state of the controller `user_index`, from 0 to `XUSER_MAX_COUNT - 1`.
+    ///
+    /// **Returns:** `None` if the controller is not connected.
+    pub fn gamepad(&self, user_index: DWORD) -> Result<Option<GamepadState>, Win32Error> {
+        Ok(self.state(user_index)?.map(|state| {
+            let gamepad = state.Gamepad;
+            GamepadState::from_raw(
+                gamepad.wButtons,
+                [gamepad.bLeftTrigger, gamepad.bRightTrigger],
+                [gamepad.sThumbLX, gamepad.sThumbLY],
+                [gamepad.sThumbRX, gamepad.sThumbRY],
+                &self.deadzones,
+            )
+        }))
+    }
+
+    /// Sets the speed of the vibration motors of the controller `user_index`, from 0 to 65535.
+    ///
+    /// See [`XInputSetState`](https://docs.microsoft.com/en-us/windows/win32/api/xinput/nf-xinput-xinputsetstate)
+    pub fn set_vibration(
+        &self,
+        user_index: DWORD,
+        low_frequency: WORD,
+        high_frequency: WORD,
+    ) -> Result<(), Win32Error> {
+        let mut vibration = XINPUT_VIBRATION {
+            wLeftMotorSpeed: low_frequency,
+            wRightMotorSpeed: high_frequency,
+        };
+        match unsafe { (self.set_state)(user_index, &mut vibration) } {
+            ERROR_SUCCESS => Ok(()),
+            e => Err(Win32Error(e)),
+        }
+    }
+}
+
+impl Drop for XInput {
+    fn drop(&mut self) {
+        if self.is_loaded() {
+            unsafe { FreeLibrary(self.module) };
+        }
+    }
+}
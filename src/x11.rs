@@ -0,0 +1,517 @@
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+
+/// Xlib bindings, written by hand in the same spirit as the `win32` module.
+///
+/// See the [Xlib manual](https://www.x.org/releases/current/doc/libX11/libX11/libX11.html)
+use core::ffi::c_void;
+use std::os::raw::{c_char, c_int, c_long, c_uint, c_ulong};
+
+pub mod event;
+#[cfg(all(unix, not(target_os = "macos")))]
+pub mod event_loop;
+#[cfg(all(unix, not(target_os = "macos")))]
+pub mod window;
+
+// Headers potential location: /usr/include/X11/Xlib.h and /usr/include/X11/X.h
+
+/// An opaque connection to an X server.
+pub enum Display {}
+/// An opaque visual.
+pub enum Visual {}
+/// An opaque graphics context.
+pub enum _XGC {}
+pub type GC = *mut _XGC;
+
+pub type XID = c_ulong;
+pub type Window = XID;
+pub type Drawable = XID;
+pub type Atom = c_ulong;
+pub type Time = c_ulong;
+pub type KeySym = XID;
+pub type Bool = c_int;
+pub type Status = c_int;
+pub type XPointer = *mut c_char;
+
+pub const False: Bool = 0;
+pub const True: Bool = 1;
+
+/// Event Types
+pub const KeyPress: c_int = 2;
+pub const KeyRelease: c_int = 3;
+pub const ButtonPress: c_int = 4;
+pub const ButtonRelease: c_int = 5;
+pub const MotionNotify: c_int = 6;
+pub const EnterNotify: c_int = 7;
+pub const LeaveNotify: c_int = 8;
+pub const FocusIn: c_int = 9;
+pub const FocusOut: c_int = 10;
+pub const Expose: c_int = 12;
+pub const DestroyNotify: c_int = 17;
+pub const UnmapNotify: c_int = 18;
+pub const MapNotify: c_int = 19;
+pub const ConfigureNotify: c_int = 22;
+pub const ClientMessage: c_int = 33;
+
+/// Event Masks, see [`XSelectInput`].
+pub const NoEventMask: c_long = 0;
+pub const KeyPressMask: c_long = 1 << 0;
+pub const KeyReleaseMask: c_long = 1 << 1;
+pub const ButtonPressMask: c_long = 1 << 2;
+pub const ButtonReleaseMask: c_long = 1 << 3;
+pub const EnterWindowMask: c_long = 1 << 4;
+pub const LeaveWindowMask: c_long = 1 << 5;
+pub const PointerMotionMask: c_long = 1 << 6;
+pub const ExposureMask: c_long = 1 << 15;
+pub const StructureNotifyMask: c_long = 1 << 17;
+pub const FocusChangeMask: c_long = 1 << 21;
+
+/// Keysyms, the symbols engraved on the keys.
+///
+/// The Latin-1 ones use their Latin-1 code, `XK_a` to `XK_z` are `'a'` to `'z'` and `XK_0` to
+/// `XK_9` are `'0'` to `'9'`.
+///
+/// See `/usr/include/X11/keysymdef.h`
+pub const XK_space: KeySym = 0x0020;
+pub const XK_apostrophe: KeySym = 0x0027;
+pub const XK_comma: KeySym = 0x002c;
+pub const XK_minus: KeySym = 0x002d;
+pub const XK_period: KeySym = 0x002e;
+pub const XK_slash: KeySym = 0x002f;
+pub const XK_semicolon: KeySym = 0x003b;
+pub const XK_equal: KeySym = 0x003d;
+pub const XK_bracketleft: KeySym = 0x005b;
+pub const XK_backslash: KeySym = 0x005c;
+pub const XK_bracketright: KeySym = 0x005d;
+pub const XK_grave: KeySym = 0x0060;
+pub const XK_BackSpace: KeySym = 0xff08;
+pub const XK_Tab: KeySym = 0xff09;
+pub const XK_Return: KeySym = 0xff0d;
+pub const XK_Pause: KeySym = 0xff13;
+pub const XK_Scroll_Lock: KeySym = 0xff14;
+pub const XK_Escape: KeySym = 0xff1b;
+pub const XK_Home: KeySym = 0xff50;
+pub const XK_Left: KeySym = 0xff51;
+pub const XK_Up: KeySym = 0xff52;
+pub const XK_Right: KeySym = 0xff53;
+pub const XK_Down: KeySym = 0xff54;
+pub const XK_Page_Up: KeySym = 0xff55;
+pub const XK_Page_Down: KeySym = 0xff56;
+pub const XK_End: KeySym = 0xff57;
+pub const XK_Print: KeySym = 0xff61;
+pub const XK_Insert: KeySym = 0xff63;
+pub const XK_Menu: KeySym = 0xff67;
+pub const XK_Num_Lock: KeySym = 0xff7f;
+pub const XK_KP_Enter: KeySym = 0xff8d;
+pub const XK_KP_Home: KeySym = 0xff95;
+pub const XK_KP_Left: KeySym = 0xff96;
+pub const XK_KP_Up: KeySym = 0xff97;
+pub const XK_KP_Right: KeySym = 0xff98;
+pub const XK_KP_Down: KeySym = 0xff99;
+pub const XK_KP_Page_Up: KeySym = 0xff9a;
+pub const XK_KP_Page_Down: KeySym = 0xff9b;
+pub const XK_KP_End: KeySym = 0xff9c;
+pub const XK_KP_Begin: KeySym = 0xff9d;
+pub const XK_KP_Insert: KeySym = 0xff9e;
+pub const XK_KP_Delete: KeySym = 0xff9f;
+pub const XK_KP_Multiply: KeySym = 0xffaa;
+pub const XK_KP_Add: KeySym = 0xffab;
+pub const XK_KP_Subtract: KeySym = 0xffad;
+pub const XK_KP_Decimal: KeySym = 0xffae;
+pub const XK_KP_Divide: KeySym = 0xffaf;
+pub const XK_KP_0: KeySym = 0xffb0;
+pub const XK_KP_9: KeySym = 0xffb9;
+pub const XK_F1: KeySym = 0xffbe;
+pub const XK_F12: KeySym = 0xffc9;
+pub const XK_Shift_L: KeySym = 0xffe1;
+pub const XK_Shift_R: KeySym = 0xffe2;
+pub const XK_Control_L: KeySym = 0xffe3;
+pub const XK_Control_R: KeySym = 0xffe4;
+pub const XK_Caps_Lock: KeySym = 0xffe5;
+pub const XK_Alt_L: KeySym = 0xffe9;
+pub const XK_Alt_R: KeySym = 0xffea;
+pub const XK_Super_L: KeySym = 0xffeb;
+pub const XK_Super_R: KeySym = 0xffec;
+pub const XK_Delete: KeySym = 0xffff;
+
+/// Image formats, see [`XImage`].
+pub const XYBitmap: c_int = 0;
+pub const XYPixmap: c_int = 1;
+pub const ZPixmap: c_int = 2;
+
+/// Every plane, for [`XGetImage`].
+pub const AllPlanes: c_ulong = !0;
+
+/// Byte and bit orders, see [`XImage`].
+pub const LSBFirst: c_int = 0;
+pub const MSBFirst: c_int = 1;
+
+/// The fields every event starts with.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct XAnyEvent {
+    pub type_: c_int,
+    /// The number of the last request processed by the server.
+    pub serial: c_ulong,
+    /// Whether the event came from a `SendEvent` request.
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub window: Window,
+}
+
+/// `KeyPress` and `KeyRelease` events.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct XKeyEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub window: Window,
+    pub root: Window,
+    pub subwindow: Window,
+    pub time: Time,
+    pub x: c_int,
+    pub y: c_int,
+    pub x_root: c_int,
+    pub y_root: c_int,
+    /// The state of the modifier keys and pointer buttons.
+    pub state: c_uint,
+    pub keycode: c_uint,
+    pub same_screen: Bool,
+}
+
+/// `ButtonPress` and `ButtonRelease` events.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct XButtonEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub window: Window,
+    pub root: Window,
+    pub subwindow: Window,
+    pub time: Time,
+    pub x: c_int,
+    pub y: c_int,
+    pub x_root: c_int,
+    pub y_root: c_int,
+    pub state: c_uint,
+    pub button: c_uint,
+    pub same_screen: Bool,
+}
+
+/// `MotionNotify` events.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct XMotionEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub window: Window,
+    pub root: Window,
+    pub subwindow: Window,
+    pub time: Time,
+    pub x: c_int,
+    pub y: c_int,
+    pub x_root: c_int,
+    pub y_root: c_int,
+    pub state: c_uint,
+    pub is_hint: c_char,
+    pub same_screen: Bool,
+}
+
+/// `EnterNotify` and `LeaveNotify` events.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct XCrossingEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub window: Window,
+    pub root: Window,
+    pub subwindow: Window,
+    pub time: Time,
+    pub x: c_int,
+    pub y: c_int,
+    pub x_root: c_int,
+    pub y_root: c_int,
+    pub mode: c_int,
+    pub detail: c_int,
+    pub same_screen: Bool,
+    pub focus: Bool,
+    pub state: c_uint,
+}
+
+/// `FocusIn` and `FocusOut` events.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct XFocusChangeEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub window: Window,
+    pub mode: c_int,
+    pub detail: c_int,
+}
+
+/// `Expose` events.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct XExposeEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub window: Window,
+    pub x: c_int,
+    pub y: c_int,
+    pub width: c_int,
+    pub height: c_int,
+    /// How many `Expose` events follow this one.
+    pub count: c_int,
+}
+
+/// `DestroyNotify` events.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct XDestroyWindowEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub event: Window,
+    pub window: Window,
+}
+
+/// `ConfigureNotify` events.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct XConfigureEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub event: Window,
+    pub window: Window,
+    pub x: c_int,
+    pub y: c_int,
+    pub width: c_int,
+    pub height: c_int,
+    pub border_width: c_int,
+    pub above: Window,
+    pub override_redirect: Bool,
+}
+
+/// `ClientMessage` events.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct XClientMessageEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub window: Window,
+    pub message_type: Atom,
+    /// How `data` should be read: as 8, 16 or 32 bits values.
+    pub format: c_int,
+    /// The `l` member of the C union, the one used with a `format` of 32.
+    pub data: [c_long; 5],
+}
+
+/// An event, whatever its type.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union XEvent {
+    pub type_: c_int,
+    pub xany: XAnyEvent,
+    pub xkey: XKeyEvent,
+    pub xbutton: XButtonEvent,
+    pub xmotion: XMotionEvent,
+    pub xcrossing: XCrossingEvent,
+    pub xfocus: XFocusChangeEvent,
+    pub xexpose: XExposeEvent,
+    pub xdestroywindow: XDestroyWindowEvent,
+    pub xconfigure: XConfigureEvent,
+    pub xclient: XClientMessageEvent,
+    pub pad: [c_long; 24],
+}
+
+impl Default for XEvent {
+    fn default() -> Self {
+        Self { pad: [0; 24] }
+    }
+}
+
+/// An image in client memory, see [`XPutImage`].
+///
+/// The function table is filled in by [`XInitImage`].
+#[repr(C)]
+pub struct XImage {
+    pub width: c_int,
+    pub height: c_int,
+    pub xoffset: c_int,
+    /// [`XYBitmap`], [`XYPixmap`] or [`ZPixmap`].
+    pub format: c_int,
+    pub data: *mut c_char,
+    pub byte_order: c_int,
+    pub bitmap_unit: c_int,
+    pub bitmap_bit_order: c_int,
+    pub bitmap_pad: c_int,
+    pub depth: c_int,
+    pub bytes_per_line: c_int,
+    pub bits_per_pixel: c_int,
+    pub red_mask: c_ulong,
+    pub green_mask: c_ulong,
+    pub blue_mask: c_ulong,
+    pub obdata: XPointer,
+    /// `create_image`, `destroy_image`, `get_pixel`, `put_pixel`, `sub_image` and `add_pixel`.
+    pub f: [*mut c_void; 6],
+}
+
+impl Default for XImage {
+    fn default() -> Self {
+        unsafe { core::mem::zeroed() }
+    }
+}
+
+// The layouts have to match the C headers of 64 bits Linux, check it on every build.
+#[cfg(all(unix, target_pointer_width = "64"))]
+const _: () = {
+    use core::mem::{offset_of, size_of};
+    assert!(size_of::<XEvent>() == 192);
+    assert!(offset_of!(XKeyEvent, keycode) == 84);
+    assert!(offset_of!(XConfigureEvent, width) == 56);
+    assert!(offset_of!(XClientMessageEvent, data) == 56);
+    assert!(size_of::<XImage>() == 136);
+};
+
+#[cfg(all(unix, not(target_os = "macos")))]
+#[link(name = "X11")]
+extern "C" {
+    pub fn XOpenDisplay(display_name: *const c_char) -> *mut Display;
+    pub fn XCloseDisplay(display: *mut Display) -> c_int;
+    pub fn XDefaultScreen(display: *mut Display) -> c_int;
+    pub fn XRootWindow(display: *mut Display, screen_number: c_int) -> Window;
+    pub fn XDefaultVisual(display: *mut Display, screen_number: c_int) -> *mut Visual;
+    pub fn XDefaultDepth(display: *mut Display, screen_number: c_int) -> c_int;
+    pub fn XDefaultGC(display: *mut Display, screen_number: c_int) -> GC;
+    pub fn XBlackPixel(display: *mut Display, screen_number: c_int) -> c_ulong;
+    pub fn XWhitePixel(display: *mut Display, screen_number: c_int) -> c_ulong;
+
+    pub fn XCreateSimpleWindow(
+        display: *mut Display,
+        parent: Window,
+        x: c_int,
+        y: c_int,
+        width: c_uint,
+        height: c_uint,
+        border_width: c_uint,
+        border: c_ulong,
+        background: c_ulong,
+    ) -> Window;
+    pub fn XDestroyWindow(display: *mut Display, w: Window) -> c_int;
+    pub fn XMapWindow(display: *mut Display, w: Window) -> c_int;
+    pub fn XMoveWindow(display: *mut Display, w: Window, x: c_int, y: c_int) -> c_int;
+    pub fn XStoreName(display: *mut Display, w: Window, window_name: *const c_char) -> c_int;
+    pub fn XSelectInput(display: *mut Display, w: Window, event_mask: c_long) -> c_int;
+    pub fn XGetGeometry(
+        display: *mut Display,
+        d: Drawable,
+        root_return: *mut Window,
+        x_return: *mut c_int,
+        y_return: *mut c_int,
+        width_return: *mut c_uint,
+        height_return: *mut c_uint,
+        border_width_return: *mut c_uint,
+        depth_return: *mut c_uint,
+    ) -> Status;
+
+    pub fn XTranslateCoordinates(
+        display: *mut Display,
+        src_w: Window,
+        dest_w: Window,
+        src_x: c_int,
+        src_y: c_int,
+        dest_x_return: *mut c_int,
+        dest_y_return: *mut c_int,
+        child_return: *mut Window,
+    ) -> Bool;
+
+    pub fn XInternAtom(
+        display: *mut Display,
+        atom_name: *const c_char,
+        only_if_exists: Bool,
+    ) -> Atom;
+    pub fn XSetWMProtocols(
+        display: *mut Display,
+        w: Window,
+        protocols: *mut Atom,
+        count: c_int,
+    ) -> Status;
+
+    pub fn XPending(display: *mut Display) -> c_int;
+    pub fn XNextEvent(display: *mut Display, event_return: *mut XEvent) -> c_int;
+    pub fn XFlush(display: *mut Display) -> c_int;
+    pub fn XSync(display: *mut Display, discard: Bool) -> c_int;
+
+    pub fn XInitImage(image: *mut XImage) -> Status;
+    /// The image is allocated by Xlib, to free with [`XDestroyImage`].
+    pub fn XGetImage(
+        display: *mut Display,
+        d: Drawable,
+        x: c_int,
+        y: c_int,
+        width: c_uint,
+        height: c_uint,
+        plane_mask: c_ulong,
+        format: c_int,
+    ) -> *mut XImage;
+    pub fn XGetPixel(image: *mut XImage, x: c_int, y: c_int) -> c_ulong;
+    pub fn XDestroyImage(image: *mut XImage) -> c_int;
+    pub fn XPutImage(
+        display: *mut Display,
+        d: Drawable,
+        gc: GC,
+        image: *mut XImage,
+        src_x: c_int,
+        src_y: c_int,
+        dest_x: c_int,
+        dest_y: c_int,
+        width: c_uint,
+        height: c_uint,
+    ) -> c_int;
+
+    pub fn XLookupKeysym(key_event: *mut XKeyEvent, index: c_int) -> KeySym;
+    /// `status_in_out` is an `XComposeStatus*`, which Xlib doesn't use anymore.
+    pub fn XLookupString(
+        event_struct: *mut XKeyEvent,
+        buffer_return: *mut c_char,
+        bytes_buffer: c_int,
+        keysym_return: *mut KeySym,
+        status_in_out: *mut c_void,
+    ) -> c_int;
+}
+
+/// An error reported by the X11 layer.
+///
+/// Xlib reports most errors asynchronously, through its error handler, so this only covers the
+/// calls that return a failure directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct X11Error(pub &'static str);
+impl core::fmt::Display for X11Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "X11Error({})", self.0)
+    }
+}
+impl std::error::Error for X11Error {}
+
+/// Turns a Rust string slice into a null-terminated byte vector, for Xlib's `char*` strings.
+pub fn c_string(s: &str) -> Vec<c_char> {
+    s.bytes().map(|b| b as c_char).chain(Some(0)).collect()
+}
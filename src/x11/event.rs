@@ -0,0 +1,281 @@
+//! Decoding of raw X events into [`WindowEvent`]s.
+//!
+//! This is plain data crunching, so it's available (and testable) on every platform.
+
+use super::*;
+use crate::{
+    dpi::{Dpi, PhysicalSize},
+    event::{Key, KeyboardInput, MouseButton, WindowEvent, WHEEL_NOTCH},
+};
+
+/// Decodes an X event into the [`WindowEvent`]s it stands for, handing them over to `emit`.
+///
+/// * `wm_delete_window` is the `WM_DELETE_WINDOW` atom of the connection, the window manager
+///   sends it in a `ClientMessage` when the user asks for a window to be closed.
+/// * A `ConfigureNotify` always produces a [`WindowEvent::Resized`], X doesn't tell whether the
+///   size or the position changed. Only the synthetic ones, sent by the window manager, produce a
+///   [`WindowEvent::Moved`]: the position of the real ones is relative to the parent window,
+///   which is the frame of the window manager once it reparented the window. See
+///   [ICCCM 4.1.5](https://tronche.com/gui/x/icccm/sec-4.html#s-4.1.5).
+/// * Key events need Xlib to be made sense of, they go through [`decode_key_event`] instead.
+///
+/// **Returns:** The window the event is about.
+pub fn decode_event(
+    event: &XEvent,
+    wm_delete_window: Atom,
+    mut emit: impl FnMut(WindowEvent),
+) -> Window {
+    // Safety: every event starts with the `XAnyEvent` fields, the other members are only read
+    // once the type is known.
+    let window = unsafe { event.xany.window };
+    match unsafe { event.type_ } {
+        ConfigureNotify => {
+            let configure = unsafe { &event.xconfigure };
+            // No DPI scaling on X11, a logical pixel is a physical one.
+            emit(WindowEvent::Resized {
+                size: PhysicalSize::new(configure.width as u32, configure.height as u32),
+                dpi: Dpi::BASE,
+            });
+            if configure.send_event != False {
+                emit(WindowEvent::Moved {
+                    x: configure.x,
+                    y: configure.y,
+                });
+            }
+        }
+        FocusIn => emit(WindowEvent::Focused(true)),
+        FocusOut => emit(WindowEvent::Focused(false)),
+        ClientMessage => {
+            let client = unsafe { &event.xclient };
+            if client.format == 32 && client.data[0] as Atom == wm_delete_window {
+                emit(WindowEvent::CloseRequested);
+            }
+        }
+        DestroyNotify => emit(WindowEvent::Destroyed),
+        // Only the last one of a series, the whole window gets repainted anyway.
+        Expose if unsafe { event.xexpose.count } == 0 => emit(WindowEvent::Paint),
+        EnterNotify => emit(WindowEvent::MouseEnter),
+        LeaveNotify => emit(WindowEvent::MouseLeave),
+        // X grabs the pointer on presses, like Win32's capture, so coordinates can be negative.
+        ButtonPress | ButtonRelease => {
+            let button = unsafe { &event.xbutton };
+            let pressed = button.type_ == ButtonPress;
+            let mouse_button = match button.button {
+                1 => MouseButton::Left,
+                2 => MouseButton::Middle,
+                3 => MouseButton::Right,
+                // The wheel comes as presses (and releases) of the buttons 4 to 7, one per notch.
+                4..=7 if pressed => {
+                    let [delta_x, delta_y] = match button.button {
+                        4 => [0, WHEEL_NOTCH],
+                        5 => [0, -WHEEL_NOTCH],
+                        6 => [-WHEEL_NOTCH, 0],
+                        _ => [WHEEL_NOTCH, 0],
+                    };
+                    emit(WindowEvent::MouseWheel { delta_x, delta_y });
+                    return window;
+                }
+                8 => MouseButton::X1,
+                9 => MouseButton::X2,
+                _ => return window,
+            };
+            let [x, y] = [button.x, button.y];
+            emit(if pressed {
+                WindowEvent::MouseDown {
+                    button: mouse_button,
+                    x,
+                    y,
+                }
+            } else {
+                WindowEvent::MouseUp {
+                    button: mouse_button,
+                    x,
+                    y,
+                }
+            });
+        }
+        MotionNotify => {
+            let motion = unsafe { &event.xmotion };
+            emit(WindowEvent::MouseMove {
+                x: motion.x,
+                y: motion.y,
+            });
+        }
+        _ => {}
+    }
+    window
+}
+
+/// Decodes a `KeyPress` or `KeyRelease` event, handing the [`WindowEvent`]s over to `emit`.
+///
+/// * `keysym` is the unshifted keysym of the key, as given by `XLookupKeysym` with an index of 0.
+/// * `text` is what `XLookupString` wrote for the event, Latin-1 encoded. Its non-control
+///   characters become [`WindowEvent::TextInput`]s, after the key press.
+/// * X sends a release before each auto-repeated press, so presses are never flagged as repeats.
+pub fn decode_key_event(
+    event: &XKeyEvent,
+    keysym: KeySym,
+    text: &[u8],
+    mut emit: impl FnMut(WindowEvent),
+) {
+    let input = KeyboardInput {
+        key: key_from_keysym(keysym),
+        scancode: event.keycode,
+        repeat: false,
+        repeat_count: 1,
+    };
+    match event.type_ {
+        KeyPress => {
+            emit(WindowEvent::KeyDown(input));
+            // Latin-1 is the first 256 code points of Unicode.
+            text.iter()
+                .map(|&byte| char::from(byte))
+                .filter(|character| !character.is_control())
+                .for_each(|character| emit(WindowEvent::TextInput(character)));
+        }
+        KeyRelease => emit(WindowEvent::KeyUp(input)),
+        _ => {}
+    }
+}
+
+/// Maps a keysym to a [`Key`].
+///
+/// The keypad keys map to the same [`Key`] whatever the NumLock state.
+pub fn key_from_keysym(keysym: KeySym) -> Key {
+    match keysym {
+        // Lowercase is what index 0 gives, uppercase is accepted all the same.
+        0x61..=0x7a => Key::LETTERS[(keysym - 0x61) as usize],
+        0x41..=0x5a => Key::LETTERS[(keysym - 0x41) as usize],
+        0x30..=0x39 => Key::DIGITS[(keysym - 0x30) as usize],
+        XK_KP_0..=XK_KP_9 => Key::NUMPAD_DIGITS[(keysym - XK_KP_0) as usize],
+        XK_F1..=XK_F12 => Key::FUNCTION_KEYS[(keysym - XK_F1) as usize],
+        XK_Escape => Key::Escape,
+        XK_Return => Key::Enter,
+        XK_KP_Enter => Key::NumpadEnter,
+        // Index 0 is the NumLock off meaning of the keypad, these are still the digit keys.
+        XK_KP_Insert => Key::Numpad0,
+        XK_KP_End => Key::Numpad1,
+        XK_KP_Down => Key::Numpad2,
+        XK_KP_Page_Down => Key::Numpad3,
+        XK_KP_Left => Key::Numpad4,
+        XK_KP_Begin => Key::Numpad5,
+        XK_KP_Right => Key::Numpad6,
+        XK_KP_Home => Key::Numpad7,
+        XK_KP_Up => Key::Numpad8,
+        XK_KP_Page_Up => Key::Numpad9,
+        // As on Win32, where NumLock off turns it into VK_DELETE like the Delete key.
+        XK_KP_Delete => Key::Delete,
+        XK_Tab => Key::Tab,
+        XK_BackSpace => Key::Backspace,
+        XK_space => Key::Space,
+        XK_Insert => Key::Insert,
+        XK_Delete => Key::Delete,
+        XK_Home => Key::Home,
+        XK_End => Key::End,
+        XK_Page_Up => Key::PageUp,
+        XK_Page_Down => Key::PageDown,
+        XK_Left => Key::Left,
+        XK_Up => Key::Up,
+        XK_Right => Key::Right,
+        XK_Down => Key::Down,
+        XK_Shift_L => Key::LeftShift,
+        XK_Shift_R => Key::RightShift,
+        XK_Control_L => Key::LeftControl,
+        XK_Control_R => Key::RightControl,
+        XK_Alt_L => Key::LeftAlt,
+        XK_Alt_R => Key::RightAlt,
+        XK_Super_L => Key::LeftSuper,
+        XK_Super_R => Key::RightSuper,
+        XK_Menu => Key::Menu,
+        XK_Caps_Lock => Key::CapsLock,
+        XK_Num_Lock => Key::NumLock,
+        XK_Scroll_Lock => Key::ScrollLock,
+        XK_Print => Key::PrintScreen,
+        XK_Pause => Key::Pause,
+        XK_KP_Add => Key::NumpadAdd,
+        XK_KP_Subtract => Key::NumpadSubtract,
+        XK_KP_Multiply => Key::NumpadMultiply,
+        XK_KP_Divide => Key::NumpadDivide,
+        XK_KP_Decimal => Key::NumpadDecimal,
+        XK_minus => Key::Minus,
+        XK_equal => Key::Equals,
+        XK_bracketleft => Key::LeftBracket,
+        XK_bracketright => Key::RightBracket,
+        XK_backslash => Key::Backslash,
+        XK_semicolon => Key::Semicolon,
+        XK_apostrophe => Key::Apostrophe,
+        XK_grave => Key::Grave,
+        XK_comma => Key::Comma,
+        XK_period => Key::Period,
+        XK_slash => Key::Slash,
+        _ => Key::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keypad_keys_ignore_num_lock() {
+        // With NumLock off, index 0 of the keypad digits gives the navigation keysyms.
+        let off = [
+            XK_KP_Insert,
+            XK_KP_End,
+            XK_KP_Down,
+            XK_KP_Page_Down,
+            XK_KP_Left,
+            XK_KP_Begin,
+            XK_KP_Right,
+            XK_KP_Home,
+            XK_KP_Up,
+            XK_KP_Page_Up,
+        ];
+        let on = XK_KP_0..=XK_KP_9;
+        for ((digit, off), on) in Key::NUMPAD_DIGITS.iter().zip(off).zip(on) {
+            assert_eq!(key_from_keysym(off), *digit);
+            assert_eq!(key_from_keysym(on), *digit);
+        }
+        assert_eq!(key_from_keysym(XK_KP_Delete), Key::Delete);
+        assert_eq!(key_from_keysym(XK_KP_Decimal), Key::NumpadDecimal);
+        // The navigation keys out of the keypad are left alone.
+        assert_eq!(key_from_keysym(XK_Home), Key::Home);
+        assert_eq!(key_from_keysym(XK_End), Key::End);
+    }
+
+    fn configure_notify(send_event: Bool) -> XEvent {
+        XEvent {
+            xconfigure: XConfigureEvent {
+                type_: ConfigureNotify,
+                serial: 0,
+                send_event,
+                display: core::ptr::null_mut(),
+                event: 42,
+                window: 42,
+                x: 10,
+                y: 20,
+                width: 640,
+                height: 480,
+                border_width: 0,
+                above: 0,
+                override_redirect: False,
+            },
+        }
+    }
+
+    #[test]
+    fn only_synthetic_configure_notifies_move() {
+        let resized = WindowEvent::Resized {
+            size: PhysicalSize::new(640, 480),
+            dpi: Dpi::BASE,
+        };
+        let mut events = Vec::new();
+        let window = decode_event(&configure_notify(True), 0, |event| events.push(event));
+        assert_eq!(window, 42);
+        assert_eq!(events, [resized, WindowEvent::Moved { x: 10, y: 20 }]);
+        // Relative to the frame of the window manager.
+        events.clear();
+        decode_event(&configure_notify(False), 0, |event| events.push(event));
+        assert_eq!(events, [resized]);
+    }
+}
@@ -0,0 +1,183 @@
+//! A closure based event loop, on top of an Xlib connection.
+//!
+//! Xlib has no window procedure, events are read from the connection and decoded by the loop
+//! itself, which hands them over to its handler exactly like the Win32 loop does.
+
+use super::{
+    event::{decode_event, decode_key_event},
+    *,
+};
+use crate::event::{ControlFlow, Event, PlatformEventLoop, WindowEvent, WindowId};
+use core::ptr::{null, null_mut};
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+/// The connection to the X server, shared by the event loop and its windows.
+pub(crate) struct Connection {
+    pub(crate) display: *mut Display,
+    pub(crate) screen: c_int,
+    /// The protocol used to be told about close requests instead of being killed.
+    pub(crate) wm_delete_window: Atom,
+    /// Events produced outside of the loop, e.g. [`WindowEvent::Created`](crate::event::WindowEvent::Created).
+    pub(crate) pending: RefCell<VecDeque<Event>>,
+}
+
+impl Connection {
+    pub(crate) fn push_event(&self, event: Event) {
+        self.pending.borrow_mut().push_back(event);
+    }
+
+    fn pop_event(&self) -> Option<Event> {
+        self.pending.borrow_mut().pop_front()
+    }
+
+    /// Reads one event from the connection and records what it stands for.
+    ///
+    /// Blocks until an event arrives.
+    fn read_event(&self) {
+        let mut event = XEvent::default();
+        unsafe { XNextEvent(self.display, &mut event) };
+        let mut events = Vec::new();
+        let window = match unsafe { event.type_ } {
+            KeyPress | KeyRelease => unsafe {
+                let mut text = [0 as c_char; 32];
+                let text_len = XLookupString(
+                    &mut event.xkey,
+                    text.as_mut_ptr(),
+                    text.len() as c_int,
+                    null_mut(),
+                    null_mut(),
+                );
+                let text: Vec<u8> = text[..text_len.max(0) as usize]
+                    .iter()
+                    .map(|&byte| byte as u8)
+                    .collect();
+                // Unlike the text, the key doesn't depend on the modifiers.
+                let keysym = XLookupKeysym(&mut event.xkey, 0);
+                decode_key_event(&event.xkey, keysym, &text, |event| events.push(event));
+                event.xkey.window
+            },
+            ConfigureNotify => {
+                let window =
+                    decode_event(&event, self.wm_delete_window, |event| events.push(event));
+                // The real ones don't say where the window is on the screen, ask for it.
+                if unsafe { event.xconfigure.send_event } == False {
+                    if let Some([x, y]) = self.root_position(window) {
+                        events.push(WindowEvent::Moved { x, y });
+                    }
+                }
+                window
+            }
+            _ => decode_event(&event, self.wm_delete_window, |event| events.push(event)),
+        };
+        for event in events {
+            self.push_event(Event::WindowEvent {
+                window_id: WindowId(window as usize),
+                event,
+            });
+        }
+    }
+
+    /// Where the top left corner of `window` is, relative to the root window.
+    ///
+    /// See [`XTranslateCoordinates`](https://www.x.org/releases/current/doc/libX11/libX11/libX11.html#XTranslateCoordinates)
+    fn root_position(&self, window: Window) -> Option<[i32; 2]> {
+        let [mut x, mut y] = [0, 0];
+        let mut child = 0;
+        let same_screen = unsafe {
+            XTranslateCoordinates(
+                self.display,
+                window,
+                XRootWindow(self.display, self.screen),
+                0,
+                0,
+                &mut x,
+                &mut y,
+                &mut child,
+            )
+        };
+        (same_screen != False).then_some([x, y])
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        unsafe { XCloseDisplay(self.display) };
+    }
+}
+
+/// Runs the windows of a connection to the X server, see [`EventLoop::run`].
+pub struct EventLoop {
+    connection: Rc<Connection>,
+}
+
+impl EventLoop {
+    /// Connects to the X server named by the `DISPLAY` environment variable.
+    ///
+    /// See [`XOpenDisplay`](https://www.x.org/releases/current/doc/libX11/libX11/libX11.html#XOpenDisplay)
+    pub fn new() -> Result<Self, X11Error> {
+        let display = unsafe { XOpenDisplay(null()) };
+        if display.is_null() {
+            return Err(X11Error("could not open the display"));
+        }
+        let wm_delete_window = c_string("WM_DELETE_WINDOW");
+        let connection = unsafe {
+            Connection {
+                display,
+                screen: XDefaultScreen(display),
+                wm_delete_window: XInternAtom(display, wm_delete_window.as_ptr(), False),
+                pending: RefCell::new(VecDeque::new()),
+            }
+        };
+        Ok(Self {
+            connection: Rc::new(connection),
+        })
+    }
+
+    pub(crate) fn connection(&self) -> &Rc<Connection> {
+        &self.connection
+    }
+
+    /// Runs the loop until the handler asks to [`ControlFlow::Exit`].
+    ///
+    /// The handler is called for every event, then once with [`Event::MainEventsCleared`] before
+    /// the loop waits or polls again depending on the [`ControlFlow`] it left behind.
+    ///
+    /// **Returns:** The exit code.
+    pub fn run<F>(&mut self, mut handler: F) -> Result<i32, X11Error>
+    where
+        F: FnMut(Event, &mut ControlFlow),
+    {
+        let connection = Rc::clone(&self.connection);
+        let mut control_flow = ControlFlow::default();
+        let exit_code = loop {
+            // Don't wait when there is already something to handle.
+            if control_flow == ControlFlow::Wait && connection.pending.borrow().is_empty() {
+                connection.read_event();
+            }
+            while unsafe { XPending(connection.display) } > 0 {
+                connection.read_event();
+            }
+            while let Some(event) = connection.pop_event() {
+                handler(event, &mut control_flow);
+            }
+            handler(Event::MainEventsCleared, &mut control_flow);
+            if let ControlFlow::Exit(exit_code) = control_flow {
+                break exit_code;
+            }
+        };
+        // Don't leak events into a later run.
+        connection.pending.borrow_mut().clear();
+        Ok(exit_code)
+    }
+}
+
+impl PlatformEventLoop for EventLoop {
+    type Error = X11Error;
+
+    fn run<F>(&mut self, handler: F) -> Result<i32, X11Error>
+    where
+        F: FnMut(Event, &mut ControlFlow),
+    {
+        EventLoop::run(self, handler)
+    }
+}
@@ -0,0 +1,276 @@
+//! An owned window with typed per-window state.
+//!
+//! X keeps no user data for us, so unlike on Win32 the state simply lives in the [`Window`].
+
+use super::{event_loop::Connection, event_loop::EventLoop, *};
+use crate::{
+    dpi::{Dpi, PhysicalSize},
+    event::{Event, WindowEvent, WindowId},
+    raster::Framebuffer,
+};
+use core::ptr::null_mut;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+/// A window that owns its X window and a state of type `S`.
+///
+/// * The state lives as long as the window does, and is dropped when the window is destroyed.
+/// * Dropping the `Window` destroys the window if that didn't happen already.
+pub struct Window<S: 'static> {
+    id: WindowId,
+    connection: Rc<Connection>,
+    /// The X window, `0` (`None` in Xlib parlance) once destroyed.
+    window: super::Window,
+    visible: Cell<bool>,
+    /// The pixels handed over to `XPutImage` by [`Window::present`], kept around between frames.
+    backbuffer: RefCell<Vec<u8>>,
+    state: Option<S>,
+}
+
+impl<S: 'static> Window<S> {
+    /// Creates a window handled by `event_loop`, moving `state` into it.
+    ///
+    /// * The window is not initially shown, see [`Window::show`].
+    ///
+    /// See [`XCreateSimpleWindow`](https://www.x.org/releases/current/doc/libX11/libX11/libX11.html#XCreateSimpleWindow)
+    pub fn new(
+        event_loop: &EventLoop,
+        window_name: &str,
+        coordinates: Option<[i32; 2]>,
+        size: [i32; 2],
+        state: S,
+    ) -> Result<Self, X11Error> {
+        let connection = Rc::clone(event_loop.connection());
+        let display = connection.display;
+        let [x, y] = coordinates.unwrap_or([0, 0]);
+        let [width, height] = [size[0].max(1) as c_uint, size[1].max(1) as c_uint];
+        let window = unsafe {
+            XCreateSimpleWindow(
+                display,
+                XRootWindow(display, connection.screen),
+                x,
+                y,
+                width,
+                height,
+                0,
+                XBlackPixel(display, connection.screen),
+                XBlackPixel(display, connection.screen),
+            )
+        };
+        if window == 0 {
+            return Err(X11Error("could not create the window"));
+        }
+        let window_name = c_string(window_name);
+        let mut protocols = [connection.wm_delete_window];
+        unsafe {
+            XStoreName(display, window, window_name.as_ptr());
+            XSelectInput(
+                display,
+                window,
+                ExposureMask
+                    | StructureNotifyMask
+                    | FocusChangeMask
+                    | KeyPressMask
+                    | KeyReleaseMask
+                    | PointerMotionMask
+                    | ButtonPressMask
+                    | ButtonReleaseMask
+                    | EnterWindowMask
+                    | LeaveWindowMask,
+            );
+            XSetWMProtocols(display, window, protocols.as_mut_ptr(), 1);
+        }
+
+        let id = WindowId(window as usize);
+        // X reports neither, Win32 does.
+        connection.push_event(Event::WindowEvent {
+            window_id: id,
+            event: WindowEvent::Created,
+        });
+        connection.push_event(Event::WindowEvent {
+            window_id: id,
+            event: WindowEvent::Resized {
+                size: PhysicalSize::new(width, height),
+                dpi: Dpi::BASE,
+            },
+        });
+        Ok(Self {
+            id,
+            connection,
+            window,
+            visible: Cell::new(false),
+            backbuffer: RefCell::new(Vec::new()),
+            state: Some(state),
+        })
+    }
+
+    /// The identifier used for this window in the events.
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
+    /// The X window, `0` once the window has been destroyed.
+    pub fn xid(&self) -> super::Window {
+        self.window
+    }
+
+    /// Whether the window still exists.
+    pub fn is_alive(&self) -> bool {
+        self.window != 0
+    }
+
+    /// Shows the window.
+    ///
+    /// **Returns:** Whether the window was previously visible.
+    ///
+    /// See [`XMapWindow`](https://www.x.org/releases/current/doc/libX11/libX11/libX11.html#XMapWindow)
+    pub fn show(&self) -> bool {
+        if !self.is_alive() {
+            return false;
+        }
+        unsafe {
+            XMapWindow(self.connection.display, self.window);
+            XFlush(self.connection.display);
+        }
+        self.visible.replace(true)
+    }
+
+    /// The DPI of the window, always [`Dpi::BASE`]: X11 has no DPI scaling of its own.
+    pub fn dpi(&self) -> Dpi {
+        Dpi::BASE
+    }
+
+    /// The size of the window, in pixels.
+    ///
+    /// See [`XGetGeometry`](https://www.x.org/releases/current/doc/libX11/libX11/libX11.html#XGetGeometry)
+    pub fn client_size(&self) -> Result<[u32; 2], X11Error> {
+        let [mut x, mut y] = [0, 0];
+        let [mut width, mut height, mut border_width, mut depth] = [0; 4];
+        let mut root = 0;
+        let status = unsafe {
+            XGetGeometry(
+                self.connection.display,
+                self.window,
+                &mut root,
+                &mut x,
+                &mut y,
+                &mut width,
+                &mut height,
+                &mut border_width,
+                &mut depth,
+            )
+        };
+        if status == 0 {
+            Err(X11Error("could not get the window geometry"))
+        } else {
+            Ok([width, height])
+        }
+    }
+
+    /// Copies `framebuffer` to the top left corner of the window.
+    ///
+    /// Unlike on Win32 the framebuffer is not stretched, X has no cheap way to do it. Can be
+    /// called at any time, not only while handling a paint event.
+    ///
+    /// See [`XPutImage`](https://www.x.org/releases/current/doc/libX11/libX11/libX11.html#XPutImage)
+    pub fn present(&self, framebuffer: &Framebuffer) -> Result<(), X11Error> {
+        if !self.is_alive() || framebuffer.width() == 0 || framebuffer.height() == 0 {
+            return Ok(());
+        }
+        let display = self.connection.display;
+        let screen = self.connection.screen;
+        // `XCreateSimpleWindow` gave the window the default depth, which the image must match or
+        // `XPutImage` fails with `BadMatch`. Both depths take 32 bits per pixel, a depth of 32
+        // having alpha in the top byte.
+        let depth = unsafe { XDefaultDepth(display, screen) };
+        if depth != 24 && depth != 32 {
+            return Err(X11Error("the default visual is not a true color one"));
+        }
+
+        // 32 bits per pixel, least significant byte first, is BGRA like on Win32. The alpha is
+        // opaque, for compositors to not see through a 32 bits deep window.
+        let mut backbuffer = self.backbuffer.borrow_mut();
+        backbuffer.clear();
+        backbuffer.extend(
+            framebuffer
+                .pixels()
+                .iter()
+                .flat_map(|pixel| [pixel.b, pixel.g, pixel.r, 0xFF]),
+        );
+
+        let mut image = XImage {
+            width: framebuffer.width() as c_int,
+            height: framebuffer.height() as c_int,
+            format: ZPixmap,
+            data: backbuffer.as_mut_ptr().cast(),
+            byte_order: LSBFirst,
+            bitmap_unit: 32,
+            bitmap_bit_order: LSBFirst,
+            bitmap_pad: 32,
+            depth,
+            bytes_per_line: framebuffer.width() as c_int * 4,
+            bits_per_pixel: 32,
+            red_mask: 0xFF_0000,
+            green_mask: 0x00_FF00,
+            blue_mask: 0x00_00FF,
+            obdata: null_mut(),
+            ..XImage::default()
+        };
+        // The image borrows the backbuffer, so it must never go through `XDestroyImage`.
+        if 0 == unsafe { XInitImage(&mut image) } {
+            return Err(X11Error("could not initialize the image"));
+        }
+        unsafe {
+            XPutImage(
+                display,
+                self.window,
+                XDefaultGC(display, screen),
+                &mut image,
+                0,
+                0,
+                0,
+                0,
+                framebuffer.width(),
+                framebuffer.height(),
+            );
+            XFlush(display);
+        }
+        Ok(())
+    }
+
+    /// The state of the window, if it still exists.
+    pub fn state(&self) -> Option<&S> {
+        self.state.as_ref()
+    }
+
+    /// The state of the window, if it still exists.
+    pub fn state_mut(&mut self) -> Option<&mut S> {
+        self.state.as_mut()
+    }
+
+    /// Destroys the window, dropping its state.
+    ///
+    /// Does nothing if the window is already gone.
+    ///
+    /// See [`XDestroyWindow`](https://www.x.org/releases/current/doc/libX11/libX11/libX11.html#XDestroyWindow)
+    pub fn destroy(&mut self) -> Result<(), X11Error> {
+        if self.is_alive() {
+            unsafe {
+                XDestroyWindow(self.connection.display, self.window);
+                XFlush(self.connection.display);
+            }
+            self.window = 0;
+            self.state = None;
+        }
+        Ok(())
+    }
+}
+
+impl<S: 'static> Drop for Window<S> {
+    fn drop(&mut self) {
+        // Call `destroy` first to find out whether it failed.
+        let _ = self.destroy();
+    }
+}
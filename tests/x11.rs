@@ -0,0 +1,123 @@
+//! The X11 backend against a real X server.
+//!
+//! Skipped when `DISPLAY` is not set. Meant for a bare server without a window manager, which
+//! could put the window elsewhere than asked:
+//!
+//! ```sh
+//! xvfb-run cargo test --test x11
+//! ```
+#![cfg(all(unix, not(target_os = "macos")))]
+
+use core::ptr::null;
+use std::time::{Duration, Instant};
+use triangle_from_scratch::{
+    dpi::{Dpi, PhysicalSize},
+    event::{ControlFlow, Event, WindowEvent},
+    raster::{Framebuffer, Rgba8},
+    x11::{event_loop::EventLoop, window::Window, Window as XWindow, *},
+};
+
+/// What the test waits for next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Shown,
+    Moved,
+    Presented,
+    Destroyed,
+}
+
+/// The color of the pixel at `[x, y]` of `window`, as `0xRRGGBB`, through another connection.
+fn read_pixel(display: *mut Display, window: XWindow, [x, y]: [i32; 2]) -> u32 {
+    unsafe {
+        XSync(display, False);
+        let image = XGetImage(display, window, x, y, 1, 1, AllPlanes, ZPixmap);
+        assert!(!image.is_null(), "could not read the window back");
+        let pixel = XGetPixel(image, 0, 0);
+        XDestroyImage(image);
+        (pixel & 0xFF_FFFF) as u32
+    }
+}
+
+#[test]
+fn window_lifecycle() {
+    if std::env::var_os("DISPLAY").is_none() {
+        eprintln!("DISPLAY is not set, skipping");
+        return;
+    }
+    // The test's own connection, to do what the backend doesn't and check what it did.
+    let display = unsafe { XOpenDisplay(null()) };
+    assert!(!display.is_null());
+
+    let mut event_loop = EventLoop::new().unwrap();
+    let mut window = Window::new(&event_loop, "x11 test", Some([10, 20]), [64, 48], ()).unwrap();
+    let window_id = window.id();
+    let xid = window.xid();
+    assert!(!window.show());
+    assert_eq!(window.client_size().unwrap(), [64, 48]);
+
+    let mut framebuffer = Framebuffer::new(64, 48);
+    framebuffer.clear(Rgba8::new(0x33, 0x66, 0x99, 0xFF));
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let mut events = Vec::new();
+    let mut step = Step::Shown;
+    let exit_code = event_loop
+        .run(|event, control_flow| {
+            *control_flow = ControlFlow::Poll;
+            match event {
+                Event::WindowEvent {
+                    window_id: id,
+                    event,
+                } => {
+                    assert_eq!(id, window_id);
+                    events.push(event);
+                    return;
+                }
+                Event::MainEventsCleared => {}
+                _ => return,
+            }
+            assert!(
+                Instant::now() < deadline,
+                "stuck at {:?}, after {:?}",
+                step,
+                events
+            );
+            match step {
+                Step::Shown if events.contains(&WindowEvent::Paint) => {
+                    unsafe {
+                        XMoveWindow(display, xid, 30, 40);
+                        XFlush(display);
+                    }
+                    step = Step::Moved;
+                }
+                // Moved by someone else, which the backend only learns through a ConfigureNotify.
+                Step::Moved if events.contains(&WindowEvent::Moved { x: 30, y: 40 }) => {
+                    window.present(&framebuffer).unwrap();
+                    step = Step::Presented;
+                }
+                Step::Presented => {
+                    if read_pixel(display, xid, [5, 5]) == 0x33_6699 {
+                        window.destroy().unwrap();
+                        step = Step::Destroyed;
+                    }
+                }
+                Step::Destroyed if events.contains(&WindowEvent::Destroyed) => {
+                    *control_flow = ControlFlow::Exit(0);
+                }
+                _ => std::thread::sleep(Duration::from_millis(10)),
+            }
+        })
+        .unwrap();
+    assert_eq!(exit_code, 0);
+    assert_eq!(
+        events[..2],
+        [
+            WindowEvent::Created,
+            WindowEvent::Resized {
+                size: PhysicalSize::new(64, 48),
+                dpi: Dpi::BASE,
+            },
+        ]
+    );
+    assert!(!window.is_alive());
+    unsafe { XCloseDisplay(display) };
+}